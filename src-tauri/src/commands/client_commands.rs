@@ -1,23 +1,87 @@
-use tauri::AppHandle;
+use tauri::{AppHandle, Runtime};
 use crate::services::ClientService;
-use crate::repository::client_repository::Client;
+use crate::models::{Client, ClientInput, CommLogEntry};
 
 #[tauri::command]
-pub async fn save_client(app_handle: AppHandle, client: Client) -> Result<(), String> {
-    ClientService::create_client(app_handle, client).await
+pub async fn save_client<R: Runtime>(app_handle: AppHandle<R>, client: ClientInput) -> Result<(), String> {
+    ClientService::save_client(app_handle, client).await
 }
 
 #[tauri::command]
-pub async fn load_client(app_handle: AppHandle, client_id: String) -> Result<Option<Client>, String> {
+pub async fn create_client_auto_id<R: Runtime>(app_handle: AppHandle<R>, client: ClientInput) -> Result<String, String> {
+    ClientService::create_client_auto_id(app_handle, client).await
+}
+
+#[tauri::command]
+pub async fn load_client<R: Runtime>(app_handle: AppHandle<R>, client_id: String) -> Result<Option<Client>, String> {
     ClientService::get_client_by_id(app_handle, client_id).await
 }
 
 #[tauri::command]
-pub async fn load_all_clients(app_handle: AppHandle) -> Result<Vec<Client>, String> {
+pub async fn load_all_clients<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<Client>, String> {
     ClientService::get_all_clients(app_handle).await
 }
 
 #[tauri::command]
-pub async fn delete_client(app_handle: AppHandle, client_id: String) -> Result<(), String> {
+pub async fn delete_client<R: Runtime>(app_handle: AppHandle<R>, client_id: String) -> Result<(), String> {
     ClientService::delete_client(app_handle, client_id).await
 }
+
+#[tauri::command]
+pub async fn export_and_delete_client<R: Runtime>(
+    app_handle: AppHandle<R>,
+    client_id: String,
+    dest: String,
+) -> Result<String, String> {
+    ClientService::export_and_delete_client(app_handle, client_id, dest).await
+}
+
+#[tauri::command]
+pub async fn export_clients_vcard<R: Runtime>(app_handle: AppHandle<R>, dest: String) -> Result<String, String> {
+    ClientService::export_clients_vcard(app_handle, dest).await
+}
+
+#[tauri::command]
+pub async fn get_client_avatar_seed<R: Runtime>(app_handle: AppHandle<R>, client_id: String) -> Result<String, String> {
+    ClientService::get_client_avatar_seed(app_handle, client_id).await
+}
+
+#[tauri::command]
+pub async fn add_client_communication<R: Runtime>(
+    app_handle: AppHandle<R>,
+    client_id: String,
+    channel: String,
+    note: String,
+) -> Result<CommLogEntry, String> {
+    ClientService::add_client_communication(app_handle, client_id, channel, note).await
+}
+
+#[tauri::command]
+pub async fn get_client_communications<R: Runtime>(app_handle: AppHandle<R>, client_id: String) -> Result<Vec<CommLogEntry>, String> {
+    ClientService::get_client_communications(app_handle, client_id).await
+}
+
+#[tauri::command]
+pub async fn normalize_contact(kind: String, value: String) -> Result<String, String> {
+    ClientService::normalize_contact(kind, value).await
+}
+
+#[tauri::command]
+pub async fn is_client_id_available<R: Runtime>(app_handle: AppHandle<R>, client_id: String) -> Result<bool, String> {
+    ClientService::is_client_id_available(app_handle, client_id).await
+}
+
+#[tauri::command]
+pub async fn get_contact_type_breakdown<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<(String, usize)>, String> {
+    ClientService::get_contact_type_breakdown(app_handle).await
+}
+
+#[tauri::command]
+pub async fn find_duplicate_clients<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<Vec<Client>>, String> {
+    ClientService::find_duplicate_clients(app_handle).await
+}
+
+#[tauri::command]
+pub async fn get_repeat_clients<R: Runtime>(app_handle: AppHandle<R>, min_count: usize) -> Result<Vec<(Client, usize)>, String> {
+    ClientService::get_repeat_clients(app_handle, min_count).await
+}
@@ -1,20 +1,52 @@
-use tauri::AppHandle;
-use crate::services::{CommissionService, ImageService};
-use crate::repository::commission_repository::Commission;
+use std::collections::HashMap;
+use tauri::{AppHandle, Runtime};
+use crate::services::{BundleService, CommissionService, ImageService, InvoiceService, ShareService};
+use crate::services::bundle_service::BundleImport;
+use crate::services::invoice_service::Statement;
+use crate::services::commission_service::{AnnotatedCommission, Board, BulkResult, CommissionBreakdown, PriceStats, RefundPolicy};
+use crate::services::image_service::{CompressionReport, CorruptImage, DedupReport, DedupResult, DownscaleReport, FormatMismatch, GalleryItem, SavedImage};
+use crate::models::{Commission, CommissionEvent, CommissionInput};
 
 #[tauri::command]
-pub async fn save_commission(app_handle: AppHandle, commission: Commission) -> Result<(), String> {
-    CommissionService::create_commission(app_handle, commission).await
+pub async fn save_commission<R: Runtime>(app_handle: AppHandle<R>, commission: CommissionInput) -> Result<Vec<String>, String> {
+    CommissionService::save_commission(app_handle, commission).await
 }
 
 #[tauri::command]
-pub async fn load_commissions(app_handle: AppHandle, status: String) -> Result<Vec<Commission>, String> {
-    CommissionService::get_commissions_by_status(app_handle, status).await
+pub async fn load_commissions<R: Runtime>(
+    app_handle: AppHandle<R>,
+    status: String,
+    include_archived: Option<bool>,
+) -> Result<Vec<Commission>, String> {
+    CommissionService::get_commissions_by_status(app_handle, status, include_archived.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub async fn load_commissions_prioritized<R: Runtime>(app_handle: AppHandle<R>, status: String) -> Result<Vec<Commission>, String> {
+    CommissionService::get_commissions_prioritized(app_handle, status).await
+}
+
+#[tauri::command]
+pub async fn get_board<R: Runtime>(app_handle: AppHandle<R>) -> Result<Board, String> {
+    CommissionService::get_board(app_handle).await
 }
 
 #[tauri::command]
-pub async fn move_commission(
-    app_handle: AppHandle,
+pub async fn load_commissions_annotated<R: Runtime>(
+    app_handle: AppHandle<R>,
+    status: String,
+) -> Result<Vec<AnnotatedCommission>, String> {
+    CommissionService::get_commissions_annotated(app_handle, status).await
+}
+
+#[tauri::command]
+pub async fn get_client_commissions<R: Runtime>(app_handle: AppHandle<R>, client_id: String) -> Result<Vec<Commission>, String> {
+    CommissionService::get_client_commissions(app_handle, client_id).await
+}
+
+#[tauri::command]
+pub async fn move_commission<R: Runtime>(
+    app_handle: AppHandle<R>,
     commission_id: String,
     from_status: String,
     to_status: String,
@@ -23,8 +55,36 @@ pub async fn move_commission(
 }
 
 #[tauri::command]
-pub async fn delete_commission(
-    app_handle: AppHandle,
+pub async fn set_commission_started<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    started: bool,
+) -> Result<(), String> {
+    CommissionService::set_commission_started(app_handle, commission_id, started).await
+}
+
+#[tauri::command]
+pub async fn set_commission_archived<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    archived: bool,
+) -> Result<(), String> {
+    CommissionService::set_commission_archived(app_handle, commission_id, archived).await
+}
+
+#[tauri::command]
+pub async fn revert_commission<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    status: String,
+    to_timestamp: String,
+) -> Result<Commission, String> {
+    CommissionService::revert_commission(app_handle, commission_id, status, to_timestamp).await
+}
+
+#[tauri::command]
+pub async fn delete_commission<R: Runtime>(
+    app_handle: AppHandle<R>,
     commission_id: String,
     status: String,
 ) -> Result<(), String> {
@@ -32,12 +92,323 @@ pub async fn delete_commission(
 }
 
 #[tauri::command]
-pub async fn save_commission_image(
-    app_handle: AppHandle,
+pub async fn update_commission_title<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    status: String,
+    new_title: String,
+) -> Result<(), String> {
+    CommissionService::update_commission_title(app_handle, commission_id, status, new_title).await
+}
+
+#[tauri::command]
+pub async fn change_commission_id<R: Runtime>(
+    app_handle: AppHandle<R>,
+    status: String,
+    old_id: String,
+    new_id: String,
+) -> Result<(), String> {
+    CommissionService::change_commission_id(app_handle, status, old_id, new_id).await
+}
+
+#[tauri::command]
+pub async fn set_commission_cover<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    status: String,
+    relative_path: Option<String>,
+) -> Result<(), String> {
+    CommissionService::set_commission_cover(app_handle, commission_id, status, relative_path).await
+}
+
+#[tauri::command]
+pub async fn split_commission<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    status: String,
+    titles: Vec<String>,
+    keep_original: bool,
+) -> Result<Vec<String>, String> {
+    CommissionService::split_commission(app_handle, commission_id, status, titles, keep_original).await
+}
+
+#[tauri::command]
+pub async fn get_revenue_in_currency<R: Runtime>(
+    app_handle: AppHandle<R>,
+    target_currency: String,
+    rates: HashMap<String, f64>,
+) -> Result<i64, String> {
+    CommissionService::get_revenue_in_currency(app_handle, target_currency, rates).await
+}
+
+#[tauri::command]
+pub async fn get_activity_calendar<R: Runtime>(app_handle: AppHandle<R>, year: i32) -> Result<HashMap<String, usize>, String> {
+    CommissionService::get_activity_calendar(app_handle, year).await
+}
+
+#[tauri::command]
+pub async fn compute_refund<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    status: String,
+    policy: RefundPolicy,
+) -> Result<i64, String> {
+    CommissionService::compute_refund(app_handle, commission_id, status, policy).await
+}
+
+#[tauri::command]
+pub async fn get_deposit_coverage<R: Runtime>(app_handle: AppHandle<R>) -> Result<f64, String> {
+    CommissionService::get_deposit_coverage(app_handle).await
+}
+
+#[tauri::command]
+pub async fn get_revenue_by_tag<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<(String, i64)>, String> {
+    CommissionService::get_revenue_by_tag(app_handle).await
+}
+
+#[tauri::command]
+pub async fn get_completion_cadence<R: Runtime>(
+    app_handle: AppHandle<R>,
+    granularity: String,
+) -> Result<Vec<(String, usize)>, String> {
+    CommissionService::get_completion_cadence(app_handle, granularity).await
+}
+
+#[tauri::command]
+pub async fn get_commission_timeline<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    status: String,
+) -> Result<Vec<CommissionEvent>, String> {
+    CommissionService::get_commission_timeline(app_handle, commission_id, status).await
+}
+
+#[tauri::command]
+pub async fn get_commission_by_reference<R: Runtime>(app_handle: AppHandle<R>, reference_number: u64) -> Result<Option<Commission>, String> {
+    CommissionService::get_commission_by_reference(app_handle, reference_number).await
+}
+
+#[tauri::command]
+pub async fn backfill_reference_numbers<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+    CommissionService::backfill_reference_numbers(app_handle).await
+}
+
+#[tauri::command]
+pub async fn get_commission_breakdown<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    status: String,
+) -> Result<CommissionBreakdown, String> {
+    CommissionService::get_commission_breakdown(app_handle, commission_id, status).await
+}
+
+#[tauri::command]
+pub async fn export_progress_snapshot<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    status: String,
+    dest: String,
+) -> Result<String, String> {
+    ShareService::export_progress_snapshot(app_handle, commission_id, status, dest).await
+}
+
+#[tauri::command]
+pub async fn export_invoice_pdf<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    status: String,
+    dest: String,
+) -> Result<String, String> {
+    InvoiceService::export_invoice_pdf(app_handle, commission_id, status, dest).await
+}
+
+#[tauri::command]
+pub async fn export_unpaid_csv<R: Runtime>(app_handle: AppHandle<R>, dest: String) -> Result<String, String> {
+    InvoiceService::export_unpaid_csv(app_handle, dest).await
+}
+
+#[tauri::command]
+pub async fn add_tag_bulk<R: Runtime>(
+    app_handle: AppHandle<R>,
+    ids: Vec<String>,
+    status: String,
+    tag: String,
+) -> Result<BulkResult, String> {
+    CommissionService::add_tag_bulk(app_handle, ids, status, tag).await
+}
+
+#[tauri::command]
+pub async fn rename_tag<R: Runtime>(app_handle: AppHandle<R>, old_tag: String, new_tag: String) -> Result<usize, String> {
+    CommissionService::rename_tag(app_handle, old_tag, new_tag).await
+}
+
+#[tauri::command]
+pub async fn get_client_monthly_statement<R: Runtime>(
+    app_handle: AppHandle<R>,
+    client_id: String,
+    year: i32,
+    month: u32,
+) -> Result<Statement, String> {
+    InvoiceService::get_client_monthly_statement(app_handle, client_id, year, month).await
+}
+
+#[tauri::command]
+pub async fn export_commission_bundle<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    status: String,
+    dest: String,
+) -> Result<String, String> {
+    BundleService::export_commission_bundle(app_handle, commission_id, status, dest).await
+}
+
+#[tauri::command]
+pub async fn import_commission_bundle<R: Runtime>(app_handle: AppHandle<R>, zip_path: String) -> Result<BundleImport, String> {
+    BundleService::import_commission_bundle(app_handle, zip_path).await
+}
+
+#[tauri::command]
+pub async fn complete_all_for_client<R: Runtime>(app_handle: AppHandle<R>, client_id: String) -> Result<usize, String> {
+    CommissionService::complete_all_for_client(app_handle, client_id).await
+}
+
+#[tauri::command]
+pub async fn rebuild_commission_client_names<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+    CommissionService::rebuild_commission_client_names(app_handle).await
+}
+
+#[tauri::command]
+pub async fn suggest_price<R: Runtime>(app_handle: AppHandle<R>, hours: f64) -> Result<i64, String> {
+    CommissionService::suggest_price(app_handle, hours).await
+}
+
+#[tauri::command]
+pub async fn suggest_due_date<R: Runtime>(app_handle: AppHandle<R>, estimated_days: u32) -> Result<String, String> {
+    CommissionService::suggest_due_date(app_handle, estimated_days).await
+}
+
+#[tauri::command]
+pub async fn estimate_queue_clear_date<R: Runtime>(app_handle: AppHandle<R>) -> Result<String, String> {
+    CommissionService::estimate_queue_clear_date(app_handle).await
+}
+
+#[tauri::command]
+pub async fn save_commission_image<R: Runtime>(
+    app_handle: AppHandle<R>,
     commission_id: String,
     client_name: String,
     image_data: Vec<u8>,
     filename: String,
-) -> Result<String, String> {
+) -> Result<SavedImage, String> {
     ImageService::save_commission_image(app_handle, commission_id, client_name, image_data, filename).await
 }
+
+#[tauri::command]
+pub async fn reconcile_payment_status<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+    CommissionService::reconcile_payment_status(app_handle).await
+}
+
+#[tauri::command]
+pub async fn get_commissions_due_within<R: Runtime>(app_handle: AppHandle<R>, days: u32) -> Result<Vec<Commission>, String> {
+    CommissionService::get_commissions_due_within(app_handle, days).await
+}
+
+#[tauri::command]
+pub async fn get_price_statistics<R: Runtime>(app_handle: AppHandle<R>, status: Option<String>) -> Result<PriceStats, String> {
+    CommissionService::get_price_statistics(app_handle, status).await
+}
+
+#[tauri::command]
+pub async fn get_top_commissions<R: Runtime>(
+    app_handle: AppHandle<R>,
+    limit: usize,
+    status: Option<String>,
+) -> Result<Vec<Commission>, String> {
+    CommissionService::get_top_commissions(app_handle, limit, status).await
+}
+
+#[tauri::command]
+pub async fn find_commissions_without_due_date<R: Runtime>(app_handle: AppHandle<R>, status: String) -> Result<Vec<Commission>, String> {
+    CommissionService::find_commissions_without_due_date(app_handle, status).await
+}
+
+#[tauri::command]
+pub async fn find_stale_in_progress<R: Runtime>(app_handle: AppHandle<R>, older_than_days: u32) -> Result<Vec<Commission>, String> {
+    CommissionService::find_stale_in_progress(app_handle, older_than_days).await
+}
+
+#[tauri::command]
+pub async fn get_commissions_modified_since<R: Runtime>(app_handle: AppHandle<R>, since: String) -> Result<Vec<Commission>, String> {
+    CommissionService::get_commissions_modified_since(app_handle, since).await
+}
+
+#[tauri::command]
+pub async fn export_watermarked_image<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    client_name: String,
+    status: String,
+    relative_path: String,
+    text: String,
+) -> Result<String, String> {
+    ImageService::export_watermarked_image(app_handle, commission_id, client_name, status, relative_path, text).await
+}
+
+#[tauri::command]
+pub async fn estimate_dedup_savings<R: Runtime>(app_handle: AppHandle<R>) -> Result<DedupReport, String> {
+    ImageService::estimate_dedup_savings(app_handle).await
+}
+
+#[tauri::command]
+pub async fn deduplicate_all_images<R: Runtime>(app_handle: AppHandle<R>) -> Result<DedupResult, String> {
+    ImageService::deduplicate_all_images(app_handle).await
+}
+
+#[tauri::command]
+pub async fn compress_completed_images<R: Runtime>(app_handle: AppHandle<R>, quality: u8) -> Result<CompressionReport, String> {
+    ImageService::compress_completed_images(app_handle, quality).await
+}
+
+#[tauri::command]
+pub async fn get_commission_gallery<R: Runtime>(
+    app_handle: AppHandle<R>,
+    commission_id: String,
+    status: String,
+) -> Result<Vec<GalleryItem>, String> {
+    ImageService::get_commission_gallery(app_handle, commission_id, status).await
+}
+
+#[tauri::command]
+pub async fn find_inline_image_commissions<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<String>, String> {
+    ImageService::find_inline_image_commissions(app_handle).await
+}
+
+#[tauri::command]
+pub async fn get_image_format_breakdown<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<(String, usize)>, String> {
+    ImageService::get_image_format_breakdown(app_handle).await
+}
+
+#[tauri::command]
+pub async fn find_image_format_mismatches<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<FormatMismatch>, String> {
+    ImageService::find_image_format_mismatches(app_handle).await
+}
+
+#[tauri::command]
+pub async fn verify_image_integrity<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<CorruptImage>, String> {
+    ImageService::verify_image_integrity(app_handle).await
+}
+
+#[tauri::command]
+pub async fn find_orphaned_images<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<String>, String> {
+    ImageService::find_orphaned_images(app_handle).await
+}
+
+#[tauri::command]
+pub async fn downscale_all_images<R: Runtime>(app_handle: AppHandle<R>, max_edge: u32) -> Result<DownscaleReport, String> {
+    ImageService::downscale_all_images(app_handle, max_edge).await
+}
+
+#[tauri::command]
+pub async fn backfill_thumbnails<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+    ImageService::backfill_thumbnails(app_handle).await
+}
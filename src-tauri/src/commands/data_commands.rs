@@ -1,61 +1,77 @@
-use std::path::PathBuf;
-use tauri::AppHandle;
+use tauri::{AppHandle, Runtime};
 use crate::repository::FileStorage;
+use crate::repository::file_storage::EXPORT_MARKER_FILE;
+use crate::services::{BackupService, DataService, HealthService, ImportService, SearchService};
+use crate::services::backup_service::{BackupInfo, RestoreSummary};
+use crate::services::data_service::{AppInfo, TreeNode};
+use crate::services::health_service::{DriftIssue, EnumIssue, FolderAudit, HealthReport, MissingClient, OversizedRecord, SplitGroup, TimestampIssue};
+use crate::services::import_service::{ImportInspection, ImportSummary};
+use crate::services::search_service::SearchResults;
 
 #[tauri::command]
-pub async fn get_data_directory_path(app_handle: AppHandle) -> Result<String, String> {
+pub async fn get_data_directory_path<R: Runtime>(app_handle: AppHandle<R>) -> Result<String, String> {
     let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
     Ok(data_dir.to_string_lossy().to_string())
 }
 
 #[tauri::command]
-pub async fn export_all_data(app_handle: AppHandle) -> Result<String, String> {
+pub async fn relocate_data_directory<R: Runtime>(
+    app_handle: AppHandle<R>,
+    new_path: String,
+    remove_old: bool,
+) -> Result<(), String> {
+    DataService::relocate_data_directory(app_handle, new_path, remove_old).await
+}
+
+#[tauri::command]
+pub async fn export_audit_csv<R: Runtime>(app_handle: AppHandle<R>, dest: String) -> Result<String, String> {
+    DataService::export_audit_csv(app_handle, dest).await
+}
+
+#[tauri::command]
+pub async fn get_data_tree<R: Runtime>(app_handle: AppHandle<R>, max_depth: usize) -> Result<TreeNode, String> {
+    DataService::get_data_tree(app_handle, max_depth).await
+}
+
+#[tauri::command]
+pub async fn partition_history_by_year<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+    DataService::partition_history_by_year(app_handle).await
+}
+
+#[tauri::command]
+pub async fn reset_all_data<R: Runtime>(app_handle: AppHandle<R>, confirmation: String) -> Result<(), String> {
+    DataService::reset_all_data(app_handle, confirmation).await
+}
+
+#[tauri::command]
+pub async fn delete_completed_in_range<R: Runtime>(
+    app_handle: AppHandle<R>,
+    start: String,
+    end: String,
+    confirmation: String,
+) -> Result<usize, String> {
+    DataService::delete_completed_in_range(app_handle, start, end, confirmation).await
+}
+
+#[tauri::command]
+pub async fn cleanup_temp_files<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+    DataService::cleanup_temp_files(app_handle).await
+}
+
+#[tauri::command]
+pub async fn export_all_data<R: Runtime>(app_handle: AppHandle<R>) -> Result<String, String> {
     let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
     
+    // Mark the folder as app-produced so it can be imported through the trusted path
+    FileStorage::write_json_file(&data_dir.join(EXPORT_MARKER_FILE), &FileStorage::export_marker_json())?;
+    
     // Create a ZIP archive or just return the data directory path for manual copy
     Ok(data_dir.to_string_lossy().to_string())
 }
 
 #[tauri::command]
-pub async fn import_data(app_handle: AppHandle, import_path: String) -> Result<(), String> {
-    // Validate import path to prevent path traversal
-    if import_path.is_empty() {
-        return Err("Import path cannot be empty".to_string());
-    }
-    
-    if import_path.contains("..") || import_path.contains("~") {
-        return Err("Invalid import path - path traversal detected".to_string());
-    }
-    
-    // Only allow paths within specific safe directories
-    let import_dir = PathBuf::from(&import_path);
-    if !import_dir.is_absolute() {
-        return Err("Import path must be absolute".to_string());
-    }
-    
-    // Verify the path exists and is a directory
-    if !import_dir.exists() {
-        return Err("Import directory does not exist".to_string());
-    }
-    
-    if !import_dir.is_dir() {
-        return Err("Import path must be a directory".to_string());
-    }
-    
-    // Additional security: Check if import directory is within allowed locations
-    let home_dir = std::env::var("HOME").unwrap_or_default();
-    let allowed_prefixes = [
-        "/tmp/",
-        "/var/tmp/",
-        &format!("{}/Downloads/", home_dir),
-        &format!("{}/Documents/", home_dir),
-        &format!("{}/Desktop/", home_dir),
-    ];
-    
-    let import_path_str = import_dir.to_string_lossy();
-    if !allowed_prefixes.iter().any(|prefix| import_path_str.starts_with(prefix)) {
-        return Err("Import path not in allowed location".to_string());
-    }
+pub async fn import_data<R: Runtime>(app_handle: AppHandle<R>, import_path: String) -> Result<(), String> {
+    let import_dir = ImportService::validate_import_dir(&import_path)?;
     
     let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
     
@@ -68,7 +84,107 @@ pub async fn import_data(app_handle: AppHandle, import_path: String) -> Result<(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn inspect_import<R: Runtime>(app_handle: AppHandle<R>, import_path: String) -> Result<ImportInspection, String> {
+    ImportService::inspect_import(app_handle, import_path).await
+}
+
+#[tauri::command]
+pub async fn import_trusted_data<R: Runtime>(app_handle: AppHandle<R>, import_path: String) -> Result<ImportSummary, String> {
+    ImportService::import_trusted_data(app_handle, import_path).await
+}
+
+#[tauri::command]
+pub async fn import_legacy_export<R: Runtime>(app_handle: AppHandle<R>, import_path: String) -> Result<ImportSummary, String> {
+    ImportService::import_legacy_export(app_handle, import_path).await
+}
+
+#[tauri::command]
+pub async fn create_backup<R: Runtime>(app_handle: AppHandle<R>) -> Result<String, String> {
+    BackupService::create_backup(app_handle).await
+}
+
+#[tauri::command]
+pub async fn verify_backup<R: Runtime>(app_handle: AppHandle<R>, zip_path: String) -> Result<BackupInfo, String> {
+    BackupService::verify_backup(app_handle, zip_path).await
+}
+
+#[tauri::command]
+pub async fn restore_backup<R: Runtime>(
+    app_handle: AppHandle<R>,
+    zip_path: String,
+    mode: String,
+    snapshot_first: bool,
+) -> Result<RestoreSummary, String> {
+    BackupService::restore_backup(app_handle, zip_path, mode, snapshot_first).await
+}
+
+#[tauri::command]
+pub async fn analyze_backup_sizes<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<(String, u64)>, String> {
+    BackupService::analyze_backup_sizes(app_handle).await
+}
+
 #[tauri::command]
 pub async fn get_app_version() -> Result<String, String> {
     Ok(env!("CARGO_PKG_VERSION").to_string())
 }
+
+#[tauri::command]
+pub async fn get_app_info<R: Runtime>(app_handle: AppHandle<R>) -> Result<AppInfo, String> {
+    DataService::get_app_info(app_handle).await
+}
+
+#[tauri::command]
+pub async fn global_search<R: Runtime>(app_handle: AppHandle<R>, query: String) -> Result<SearchResults, String> {
+    SearchService::global_search(app_handle, query).await
+}
+
+#[tauri::command]
+pub async fn run_health_check<R: Runtime>(app_handle: AppHandle<R>) -> Result<HealthReport, String> {
+    HealthService::run_health_check(app_handle).await
+}
+
+#[tauri::command]
+pub async fn find_invalid_enum_values<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<EnumIssue>, String> {
+    HealthService::find_invalid_enum_values(app_handle).await
+}
+
+#[tauri::command]
+pub async fn audit_client_folders<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<FolderAudit>, String> {
+    HealthService::audit_client_folders(app_handle).await
+}
+
+#[tauri::command]
+pub async fn find_folder_name_drift<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<DriftIssue>, String> {
+    HealthService::find_folder_name_drift(app_handle).await
+}
+
+#[tauri::command]
+pub async fn fix_folder_name_drift<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+    HealthService::fix_folder_name_drift(app_handle).await
+}
+
+#[tauri::command]
+pub async fn find_missing_client_records<R: Runtime>(app_handle: AppHandle<R>, create_stubs: bool) -> Result<Vec<MissingClient>, String> {
+    HealthService::find_missing_client_records(app_handle, create_stubs).await
+}
+
+#[tauri::command]
+pub async fn find_split_client_folders<R: Runtime>(app_handle: AppHandle<R>, merge: bool) -> Result<Vec<SplitGroup>, String> {
+    HealthService::find_split_client_folders(app_handle, merge).await
+}
+
+#[tauri::command]
+pub async fn find_oversized_records<R: Runtime>(app_handle: AppHandle<R>, threshold_bytes: u64) -> Result<Vec<OversizedRecord>, String> {
+    HealthService::find_oversized_records(app_handle, threshold_bytes).await
+}
+
+#[tauri::command]
+pub async fn find_timestamp_anomalies<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<TimestampIssue>, String> {
+    HealthService::find_timestamp_anomalies(app_handle).await
+}
+
+#[tauri::command]
+pub async fn clamp_updated_timestamps<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+    HealthService::clamp_updated_timestamps(app_handle).await
+}
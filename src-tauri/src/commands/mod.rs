@@ -1,7 +1,9 @@
 pub mod client_commands;
 pub mod commission_commands;
 pub mod data_commands;
+pub mod settings_commands;
 
 pub use client_commands::*;
 pub use commission_commands::*;
 pub use data_commands::*;
+pub use settings_commands::*;
@@ -0,0 +1,54 @@
+use tauri::{AppHandle, Runtime};
+use crate::services::SettingsService;
+use crate::services::settings_service::ConfigReport;
+use crate::repository::config_repository::{ImageSettings, PricingSettings, StorageSettings, WatermarkSettings};
+
+#[tauri::command]
+pub async fn get_image_settings<R: Runtime>(app_handle: AppHandle<R>) -> Result<ImageSettings, String> {
+    SettingsService::get_image_settings(app_handle).await
+}
+
+#[tauri::command]
+pub async fn update_image_settings<R: Runtime>(app_handle: AppHandle<R>, settings: ImageSettings) -> Result<(), String> {
+    SettingsService::update_image_settings(app_handle, settings).await
+}
+
+#[tauri::command]
+pub async fn get_storage_settings<R: Runtime>(app_handle: AppHandle<R>) -> Result<StorageSettings, String> {
+    SettingsService::get_storage_settings(app_handle).await
+}
+
+#[tauri::command]
+pub async fn update_storage_settings<R: Runtime>(app_handle: AppHandle<R>, settings: StorageSettings) -> Result<(), String> {
+    SettingsService::update_storage_settings(app_handle, settings).await
+}
+
+#[tauri::command]
+pub async fn get_watermark_settings<R: Runtime>(app_handle: AppHandle<R>) -> Result<WatermarkSettings, String> {
+    SettingsService::get_watermark_settings(app_handle).await
+}
+
+#[tauri::command]
+pub async fn update_watermark_settings<R: Runtime>(app_handle: AppHandle<R>, settings: WatermarkSettings) -> Result<(), String> {
+    SettingsService::update_watermark_settings(app_handle, settings).await
+}
+
+#[tauri::command]
+pub async fn get_pricing_settings<R: Runtime>(app_handle: AppHandle<R>) -> Result<PricingSettings, String> {
+    SettingsService::get_pricing_settings(app_handle).await
+}
+
+#[tauri::command]
+pub async fn update_pricing_settings<R: Runtime>(app_handle: AppHandle<R>, settings: PricingSettings) -> Result<(), String> {
+    SettingsService::update_pricing_settings(app_handle, settings).await
+}
+
+#[tauri::command]
+pub async fn validate_config<R: Runtime>(app_handle: AppHandle<R>) -> Result<ConfigReport, String> {
+    SettingsService::validate_config(app_handle).await
+}
+
+#[tauri::command]
+pub async fn reset_config_to_defaults<R: Runtime>(app_handle: AppHandle<R>) -> Result<Option<String>, String> {
+    SettingsService::reset_config_to_defaults(app_handle).await
+}
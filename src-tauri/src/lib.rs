@@ -1,24 +1,127 @@
 mod commands;
+mod models;
 mod repository;
 mod services;
+#[cfg(test)]
+mod test_support;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
   tauri::Builder::default()
     .invoke_handler(tauri::generate_handler![
       commands::save_client,
+      commands::create_client_auto_id,
       commands::load_client,
       commands::load_all_clients,
       commands::delete_client,
+      commands::export_and_delete_client,
+      commands::export_clients_vcard,
+      commands::get_client_avatar_seed,
+      commands::add_client_communication,
+      commands::get_client_communications,
+      commands::is_client_id_available,
+      commands::normalize_contact,
+      commands::get_contact_type_breakdown,
+      commands::find_duplicate_clients,
+      commands::get_repeat_clients,
       commands::save_commission,
       commands::load_commissions,
+      commands::load_commissions_annotated,
+      commands::get_board,
+      commands::load_commissions_prioritized,
+      commands::get_client_commissions,
       commands::move_commission,
+      commands::set_commission_started,
+      commands::set_commission_archived,
       commands::delete_commission,
+      commands::revert_commission,
+      commands::update_commission_title,
+      commands::set_commission_cover,
+      commands::change_commission_id,
+      commands::split_commission,
+      commands::get_commission_timeline,
+      commands::get_commission_by_reference,
+      commands::backfill_reference_numbers,
+      commands::get_commission_breakdown,
+      commands::export_invoice_pdf,
+      commands::export_progress_snapshot,
+      commands::export_unpaid_csv,
+      commands::get_client_monthly_statement,
+      commands::export_commission_bundle,
+      commands::import_commission_bundle,
+      commands::complete_all_for_client,
+      commands::rename_tag,
+      commands::add_tag_bulk,
       commands::save_commission_image,
+      commands::export_watermarked_image,
+      commands::estimate_dedup_savings,
+      commands::deduplicate_all_images,
+      commands::compress_completed_images,
+      commands::downscale_all_images,
+      commands::find_orphaned_images,
+      commands::verify_image_integrity,
+      commands::get_image_format_breakdown,
+      commands::find_image_format_mismatches,
+      commands::get_commission_gallery,
+      commands::find_inline_image_commissions,
+      commands::backfill_thumbnails,
+      commands::reconcile_payment_status,
+      commands::get_commissions_due_within,
+      commands::find_commissions_without_due_date,
+      commands::find_stale_in_progress,
+      commands::get_commissions_modified_since,
+      commands::get_top_commissions,
+      commands::get_price_statistics,
+      commands::get_revenue_in_currency,
+      commands::get_revenue_by_tag,
+      commands::compute_refund,
+      commands::get_deposit_coverage,
+      commands::get_completion_cadence,
+      commands::get_activity_calendar,
+      commands::suggest_price,
+      commands::suggest_due_date,
+      commands::estimate_queue_clear_date,
+      commands::rebuild_commission_client_names,
       commands::get_data_directory_path,
+      commands::relocate_data_directory,
+      commands::partition_history_by_year,
+      commands::cleanup_temp_files,
+      commands::reset_all_data,
+      commands::delete_completed_in_range,
+      commands::get_data_tree,
+      commands::export_audit_csv,
       commands::export_all_data,
       commands::import_data,
-      commands::get_app_version
+      commands::inspect_import,
+      commands::import_trusted_data,
+      commands::import_legacy_export,
+      commands::create_backup,
+      commands::verify_backup,
+      commands::restore_backup,
+      commands::analyze_backup_sizes,
+      commands::get_app_version,
+      commands::get_app_info,
+      commands::global_search,
+      commands::run_health_check,
+      commands::find_invalid_enum_values,
+      commands::audit_client_folders,
+      commands::find_folder_name_drift,
+      commands::fix_folder_name_drift,
+      commands::find_missing_client_records,
+      commands::find_split_client_folders,
+      commands::find_oversized_records,
+      commands::find_timestamp_anomalies,
+      commands::clamp_updated_timestamps,
+      commands::get_image_settings,
+      commands::update_image_settings,
+      commands::get_storage_settings,
+      commands::update_storage_settings,
+      commands::get_watermark_settings,
+      commands::update_watermark_settings,
+      commands::get_pricing_settings,
+      commands::update_pricing_settings,
+      commands::validate_config,
+      commands::reset_config_to_defaults
     ])
     .setup(|app| {
       if cfg!(debug_assertions) {
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Client {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+    pub contact: String,
+    pub profile_image: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub contacts: Vec<ClientContact>,
+    #[serde(default)]
+    pub avatar_seed: Option<String>, // Derived from the id; filled in the first time it's asked for
+    #[serde(default)]
+    pub communications: Vec<CommLogEntry>, // Oldest first
+}
+
+/// A client as the frontend saves it. Contacts left out keep the stored ones, while a list that
+/// is sent (even an empty one) replaces them. The avatar seed and communication log are managed
+/// by their own commands, so they always come from the stored copy.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientInput {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+    pub contact: String,
+    pub profile_image: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub contacts: Option<Vec<ClientContact>>,
+}
+
+impl ClientInput {
+    pub fn into_client(self, existing: Option<Client>) -> Client {
+        let (contacts, avatar_seed, communications) = match existing {
+            Some(existing) => (existing.contacts, existing.avatar_seed, existing.communications),
+            None => (Vec::new(), None, Vec::new()),
+        };
+        Client {
+            id: self.id,
+            name: self.name,
+            email: self.email,
+            contact: self.contact,
+            profile_image: self.profile_image,
+            notes: self.notes,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            contacts: self.contacts.unwrap_or(contacts),
+            avatar_seed,
+            communications,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientContact {
+    pub kind: String, // e.g. "email", "phone", "discord"
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommLogEntry {
+    pub timestamp: String,
+    pub channel: String, // Same vocabulary as contact kinds, e.g. "email", "discord"
+    pub note: String,
+}
@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Commission {
+    pub id: String,
+    pub client_id: String,
+    pub client_name: String,
+    pub title: String,
+    pub description: String,
+    pub price_cents: i64,
+    #[serde(default)]
+    pub paid_cents: i64,
+    #[serde(default)]
+    pub deposit_cents: i64, // Non-refundable part of the price, kept when a "keep_deposit" refund is made
+    #[serde(default)]
+    pub tax_cents: i64, // Included in price_cents
+    #[serde(default)]
+    pub fee_cents: i64, // Platform fees, included in price_cents
+    #[serde(default = "default_currency")]
+    pub currency: String, // ISO 4217 code; every amount on the commission is in this currency
+    pub payment_status: String,
+    pub status: String,
+    #[serde(default = "default_priority")]
+    pub priority: String, // "Low", "Normal", "High"
+    #[serde(default)]
+    pub reference_number: Option<u64>, // The "commission #" clients quote; None in legacy files until backfilled
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub completed_at: Option<String>, // Set once on completion; the history year folder goes by it
+    pub images: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub estimated_hours: Option<f64>,
+    #[serde(default)]
+    pub events: Vec<CommissionEvent>,
+    #[serde(default)]
+    pub archived: bool, // Completed work hidden from listings unless they ask for archived items
+    #[serde(default)]
+    pub cover_image: Option<String>, // One of images; None means the UI falls back to images[0]
+    #[serde(default)]
+    pub content_hash: Option<String>, // Written on save, checked on load; absent in legacy files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity_warning: Option<String>, // Set on load when content_hash doesn't match; never stored
+}
+
+// Commissions from before currencies were tracked were all priced in dollars
+pub(crate) fn default_currency() -> String {
+    "USD".to_string()
+}
+
+// Commissions from before priorities were tracked are all equally urgent
+pub(crate) fn default_priority() -> String {
+    "Normal".to_string()
+}
+
+/// A commission as the frontend saves it. Its Commission type predates the later fields, so
+/// those are optional here: a field left out keeps the stored value, while one that is sent
+/// (even as 0, [] or null) replaces it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommissionInput {
+    pub id: String,
+    pub client_id: String,
+    pub client_name: String,
+    pub title: String,
+    pub description: String,
+    pub price_cents: i64,
+    pub payment_status: String,
+    pub status: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub images: Vec<String>,
+    #[serde(default)]
+    pub paid_cents: Option<i64>,
+    #[serde(default)]
+    pub deposit_cents: Option<i64>,
+    #[serde(default)]
+    pub tax_cents: Option<i64>,
+    #[serde(default)]
+    pub fee_cents: Option<i64>,
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub due_date: Option<Option<String>>, // Some(None) clears it
+    #[serde(default, deserialize_with = "present")]
+    pub estimated_hours: Option<Option<f64>>,
+    #[serde(default)]
+    pub archived: Option<bool>,
+    #[serde(default, deserialize_with = "present")]
+    pub cover_image: Option<Option<String>>,
+}
+
+// Tells an explicit null (Some(None)) apart from a missing key (None, via the default)
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+impl CommissionInput {
+    /// Fills the fields the caller left out from the stored copy, or with the defaults when
+    /// there is none. Events, the reference number and the hash are set when saving.
+    pub fn into_commission(self, existing: Option<&Commission>) -> Commission {
+        let stored = |field: fn(&Commission) -> i64| existing.map(field).unwrap_or_default();
+        Commission {
+            paid_cents: self.paid_cents.unwrap_or_else(|| stored(|c| c.paid_cents)),
+            deposit_cents: self.deposit_cents.unwrap_or_else(|| stored(|c| c.deposit_cents)),
+            tax_cents: self.tax_cents.unwrap_or_else(|| stored(|c| c.tax_cents)),
+            fee_cents: self.fee_cents.unwrap_or_else(|| stored(|c| c.fee_cents)),
+            currency: self.currency
+                .or_else(|| existing.map(|c| c.currency.clone()))
+                .unwrap_or_else(default_currency),
+            priority: self.priority
+                .or_else(|| existing.map(|c| c.priority.clone()))
+                .unwrap_or_else(default_priority),
+            tags: self.tags.or_else(|| existing.map(|c| c.tags.clone())).unwrap_or_default(),
+            due_date: self.due_date.unwrap_or_else(|| existing.and_then(|c| c.due_date.clone())),
+            estimated_hours: self.estimated_hours.unwrap_or_else(|| existing.and_then(|c| c.estimated_hours)),
+            archived: self.archived.unwrap_or_else(|| existing.is_some_and(|c| c.archived)),
+            cover_image: self.cover_image.unwrap_or_else(|| existing.and_then(|c| c.cover_image.clone())),
+            id: self.id,
+            client_id: self.client_id,
+            client_name: self.client_name,
+            title: self.title,
+            description: self.description,
+            price_cents: self.price_cents,
+            payment_status: self.payment_status,
+            status: self.status,
+            reference_number: None,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            completed_at: existing.and_then(|c| c.completed_at.clone()),
+            images: self.images,
+            events: Vec::new(),
+            content_hash: None,
+            integrity_warning: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommissionEvent {
+    #[serde(rename = "type")]
+    pub kind: String, // "created", "status_changed", "payment_updated", "image_added", "id_changed", "reverted"
+    pub timestamp: String,
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+impl CommissionEvent {
+    pub fn new(kind: &str, detail: Option<String>) -> Self {
+        Self {
+            kind: kind.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            detail,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frontend_shaped_commission_round_trips() {
+        let from_frontend = serde_json::json!({
+            "id": "c1",
+            "client_id": "alice",
+            "client_name": "Alice",
+            "title": "Portrait",
+            "description": "",
+            "price_cents": 2500,
+            "payment_status": "Half Paid",
+            "status": "in-progress",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-02T00:00:00Z",
+            "images": ["images/c1_ref.png"]
+        });
+
+        let commission: Commission = serde_json::from_value(from_frontend.clone()).unwrap();
+        assert_eq!((commission.currency.as_str(), commission.priority.as_str()), ("USD", "Normal"));
+
+        let serialized = serde_json::to_value(&commission).unwrap();
+        for (key, value) in from_frontend.as_object().unwrap() {
+            assert_eq!(&serialized[key], value, "{} changed", key);
+        }
+        assert!(serialized.get("integrity_warning").is_none());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod client;
+pub mod commission;
+
+pub use client::{Client, ClientInput, CommLogEntry};
+pub use commission::{Commission, CommissionEvent, CommissionInput};
@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+use super::file_storage::FileStorage;
+
+// JSON lines, one entry per change, appended in the data directory
+pub const AUDIT_LOG_FILE: &str = "audit.log";
+// Past this the oldest entries are dropped, keeping roughly the newest half
+const MAX_AUDIT_LOG_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: String,
+    pub action: String,      // "save", "delete", "move", "revert"
+    pub entity_type: String, // "client", "commission"
+    pub entity_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<String>, // JSON of the record as it was written, kept for reverts
+}
+
+pub struct AuditRepository;
+
+impl AuditRepository {
+    pub fn log_path(data_dir: &Path) -> PathBuf {
+        data_dir.join(AUDIT_LOG_FILE)
+    }
+
+    pub fn append<R: Runtime>(
+        app_handle: &AppHandle<R>,
+        action: &str,
+        entity_type: &str,
+        entity_id: &str,
+        snapshot: Option<String>,
+    ) -> Result<(), String> {
+        let data_dir = FileStorage::get_app_data_dir(app_handle)?;
+        let entry = AuditEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            action: action.to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.to_string(),
+            snapshot,
+        };
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| format!("Failed to serialize audit entry: {}", e))?;
+
+        let log_file = Self::log_path(&data_dir);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_file)
+            .map_err(|e| format!("Failed to open audit log: {}", e))?;
+        writeln!(file, "{}", line)
+            .map_err(|e| format!("Failed to write audit log: {}", e))?;
+        let size = file.metadata()
+            .map_err(|e| format!("Failed to read audit log metadata: {}", e))?
+            .len();
+        drop(file);
+        if size > MAX_AUDIT_LOG_BYTES {
+            Self::trim(&log_file)?;
+        }
+        Ok(())
+    }
+
+    // Keeps the newest whole lines that fit in half the cap
+    fn trim(log_file: &Path) -> Result<(), String> {
+        let content = fs::read_to_string(log_file)
+            .map_err(|e| format!("Failed to read audit log: {}", e))?;
+        let budget = (MAX_AUDIT_LOG_BYTES / 2) as usize;
+        let mut kept = 0;
+        for line in content.lines().rev() {
+            if kept + line.len() + 1 > budget {
+                break;
+            }
+            kept += line.len() + 1;
+        }
+        let start = content.len() - kept;
+        fs::write(log_file, &content[start..])
+            .map_err(|e| format!("Failed to write audit log: {}", e))
+    }
+
+    /// Appends an entry, logging instead of failing: a change that already happened shouldn't
+    /// be reported as an error because its audit line couldn't be written.
+    pub fn record<R: Runtime>(app_handle: &AppHandle<R>, action: &str, entity_type: &str, entity_id: &str) {
+        if let Err(e) = Self::append(app_handle, action, entity_type, entity_id, None) {
+            eprintln!("Failed to record audit entry for {} {}: {}", entity_type, entity_id, e);
+        }
+    }
+
+    /// Like record, also keeping the JSON that was written so the change can be reverted.
+    /// Inline data URLs are left out of the snapshot: they would bloat the log with every save.
+    pub fn record_snapshot<R: Runtime, T: Serialize>(
+        app_handle: &AppHandle<R>,
+        action: &str,
+        entity_type: &str,
+        entity_id: &str,
+        record: &T,
+    ) {
+        let snapshot = serde_json::to_value(record).and_then(|mut value| {
+            Self::strip_inline_data(&mut value);
+            serde_json::to_string(&value)
+        });
+        let snapshot = match snapshot {
+            Ok(snapshot) => Some(snapshot),
+            Err(e) => {
+                eprintln!("Failed to snapshot {} {}: {}", entity_type, entity_id, e);
+                None
+            }
+        };
+        if let Err(e) = Self::append(app_handle, action, entity_type, entity_id, snapshot) {
+            eprintln!("Failed to record audit entry for {} {}: {}", entity_type, entity_id, e);
+        }
+    }
+
+    // Drops data URLs from lists and blanks fields holding one
+    fn strip_inline_data(value: &mut Value) {
+        match value {
+            Value::Array(items) => {
+                items.retain(|item| !Self::is_inline_data(item));
+                items.iter_mut().for_each(Self::strip_inline_data);
+            }
+            Value::Object(fields) => {
+                for field in fields.values_mut() {
+                    if Self::is_inline_data(field) {
+                        *field = Value::Null;
+                    } else {
+                        Self::strip_inline_data(field);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn is_inline_data(value: &Value) -> bool {
+        value.as_str().is_some_and(|s| s.starts_with("data:"))
+    }
+
+    /// Reads the log back, one raw line per entry. A missing log is an empty one.
+    pub fn read_lines<R: Runtime>(app_handle: &AppHandle<R>) -> Result<Vec<String>, String> {
+        let data_dir = FileStorage::get_app_data_dir(app_handle)?;
+        let log_file = Self::log_path(&data_dir);
+        if !log_file.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&log_file)
+            .map_err(|e| format!("Failed to read audit log: {}", e))?;
+
+        Ok(content.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.to_string())
+            .collect())
+    }
+
+    /// Clears the snapshots kept for the given records, leaving the entries themselves. Lines
+    /// that don't parse are kept as they are.
+    pub fn remove_snapshots<R: Runtime>(
+        app_handle: &AppHandle<R>,
+        entity_type: &str,
+        entity_ids: &HashSet<String>,
+    ) -> Result<usize, String> {
+        let data_dir = FileStorage::get_app_data_dir(app_handle)?;
+        let mut removed = 0;
+
+        let mut content = String::new();
+        for line in Self::read_lines(app_handle)? {
+            let entry = serde_json::from_str::<AuditEntry>(&line).ok()
+                .filter(|entry| entry.snapshot.is_some())
+                .filter(|entry| entry.entity_type == entity_type && entity_ids.contains(&entry.entity_id));
+            match entry {
+                Some(mut entry) => {
+                    entry.snapshot = None;
+                    let stripped = serde_json::to_string(&entry)
+                        .map_err(|e| format!("Failed to serialize audit entry: {}", e))?;
+                    content.push_str(&stripped);
+                    removed += 1;
+                }
+                None => content.push_str(&line),
+            }
+            content.push('\n');
+        }
+
+        if removed > 0 {
+            fs::write(Self::log_path(&data_dir), content)
+                .map_err(|e| format!("Failed to write audit log: {}", e))?;
+        }
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestEnv;
+    use serde_json::json;
+    #[test]
+    fn snapshots_leave_out_inline_data() {
+        let env = TestEnv::new();
+        let record = json!({
+            "id": "c1",
+            "images": ["images/a.png", "data:image/png;base64,AAAA"],
+            "cover_image": "data:image/png;base64,AAAA",
+            "title": "data: not a url"
+        });
+        AuditRepository::record_snapshot(env.app(), "save", "commission", "c1", &record);
+        let lines = AuditRepository::read_lines(env.app()).unwrap();
+        let entry: AuditEntry = serde_json::from_str(&lines[0]).unwrap();
+        let snapshot: Value = serde_json::from_str(&entry.snapshot.unwrap()).unwrap();
+        assert_eq!(snapshot["images"], json!(["images/a.png"]));
+        assert_eq!(snapshot["cover_image"], Value::Null);
+        assert_eq!(snapshot["id"], "c1");
+    }
+
+    #[test]
+    fn an_oversized_log_keeps_only_its_newest_entries() {
+        let env = TestEnv::new();
+        let log_file = AuditRepository::log_path(env.data_dir());
+        let old_line = format!("{}\n", "x".repeat(1023));
+        fs::write(&log_file, old_line.repeat((MAX_AUDIT_LOG_BYTES / 1024) as usize)).unwrap();
+        AuditRepository::append(env.app(), "save", "client", "alice", None).unwrap();
+        let size = fs::metadata(&log_file).unwrap().len();
+        assert!(size <= MAX_AUDIT_LOG_BYTES / 2, "{}", size);
+        let lines = AuditRepository::read_lines(env.app()).unwrap();
+        assert!(lines.len() > 1);
+        assert!(lines[..lines.len() - 1].iter().all(|line| line.len() == 1023));
+        let newest: AuditEntry = serde_json::from_str(lines.last().unwrap()).unwrap();
+        assert_eq!(newest.entity_id, "alice");
+    }
+}
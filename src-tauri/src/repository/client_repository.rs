@@ -1,30 +1,18 @@
-use serde::{Deserialize, Serialize};
-use tauri::AppHandle;
+use tauri::{AppHandle, Runtime};
+use crate::models::Client;
 use super::file_storage::FileStorage;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Client {
-    pub id: String,
-    pub name: String,
-    pub email: String,
-    pub contact: String,
-    pub profile_image: Option<String>,
-    pub notes: Option<String>,
-    pub created_at: String,
-    pub updated_at: String,
-}
-
 pub struct ClientRepository;
 
 impl ClientRepository {
-    pub async fn save(app_handle: &AppHandle, client: &Client) -> Result<(), String> {
+    pub async fn save<R: Runtime>(app_handle: &AppHandle<R>, client: &Client) -> Result<(), String> {
         let data_dir = FileStorage::get_app_data_dir(app_handle)?;
         FileStorage::ensure_data_folders(&data_dir)?;
         
         let clients_dir = data_dir.join("clients");
         let client_file = clients_dir.join(format!("{}.json", client.id));
         
-        let client_json = serde_json::to_string_pretty(client)
+        let client_json = FileStorage::to_json(app_handle, client)
             .map_err(|e| format!("Failed to serialize client: {}", e))?;
         
         FileStorage::write_json_file(&client_file, &client_json)?;
@@ -32,7 +20,7 @@ impl ClientRepository {
         Ok(())
     }
 
-    pub async fn find_by_id(app_handle: &AppHandle, client_id: &str) -> Result<Option<Client>, String> {
+    pub async fn find_by_id<R: Runtime>(app_handle: &AppHandle<R>, client_id: &str) -> Result<Option<Client>, String> {
         let data_dir = FileStorage::get_app_data_dir(app_handle)?;
         let clients_dir = data_dir.join("clients");
         let client_file = clients_dir.join(format!("{}.json", client_id));
@@ -50,7 +38,14 @@ impl ClientRepository {
         Ok(Some(client))
     }
 
-    pub async fn find_all(app_handle: &AppHandle) -> Result<Vec<Client>, String> {
+    pub async fn exists<R: Runtime>(app_handle: &AppHandle<R>, client_id: &str) -> Result<bool, String> {
+        let data_dir = FileStorage::get_app_data_dir(app_handle)?;
+        let client_file = data_dir.join("clients").join(format!("{}.json", client_id));
+        
+        Ok(client_file.exists())
+    }
+
+    pub async fn find_all<R: Runtime>(app_handle: &AppHandle<R>) -> Result<Vec<Client>, String> {
         let data_dir = FileStorage::get_app_data_dir(app_handle)?;
         FileStorage::ensure_data_folders(&data_dir)?;
         
@@ -65,16 +60,47 @@ impl ClientRepository {
             }
         }
         
+        // Directory order is arbitrary, so sort to keep lists stable between loads
+        clients.sort_by(|a, b| {
+            a.name.to_lowercase().cmp(&b.name.to_lowercase())
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        
         Ok(clients)
     }
 
-    pub async fn delete(app_handle: &AppHandle, client_id: &str) -> Result<(), String> {
+    pub async fn delete<R: Runtime>(app_handle: &AppHandle<R>, client_id: &str) -> Result<(), String> {
         let data_dir = FileStorage::get_app_data_dir(app_handle)?;
         let clients_dir = data_dir.join("clients");
         let client_file = clients_dir.join(format!("{}.json", client_id));
-        
+
+        // Let the frontend tell "deleted" apart from "was never there"
+        if !client_file.exists() {
+            return Err(format!("Client {} not found", client_id));
+        }
+
         FileStorage::delete_file(&client_file)?;
         
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{client, TestEnv};
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn find_all_orders_by_name_then_id() {
+        let env = TestEnv::new();
+        for (id, name) in [("z1", "bob"), ("b2", "Alice"), ("a9", "alice"), ("c3", "Carol")] {
+            block_on(ClientRepository::save(env.app(), &client(id, name))).unwrap();
+        }
+        
+        let order = || -> Vec<String> {
+            block_on(ClientRepository::find_all(env.app())).unwrap().into_iter().map(|c| c.id).collect()
+        };
+        assert_eq!(order(), vec!["a9", "b2", "z1", "c3"]);
+        assert_eq!(order(), order());
+    }
+}
@@ -1,34 +1,187 @@
-use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::fs;
-use tauri::AppHandle;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::{AppHandle, Runtime};
+use crate::models::{Commission, CommissionEvent};
+use crate::models::commission::{default_currency, default_priority};
+use super::config_repository::ConfigRepository;
 use super::file_storage::FileStorage;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Commission {
-    pub id: String,
-    pub client_id: String,
-    pub client_name: String,
-    pub title: String,
-    pub description: String,
-    pub price_cents: i64,
-    pub payment_status: String,
-    pub status: String,
-    pub created_at: String,
-    pub updated_at: String,
-    pub images: Vec<String>,
+/// Every status a commission can have.
+pub const COMMISSION_STATUSES: [&str; 4] = ["pending", "in-progress", "completed", "cancelled"];
+
+/// Every folder commissions are stored in.
+pub const COMMISSION_FOLDERS: [&str; 3] = ["pendings", "history", "cancelled"];
+
+/// One status per commission folder, for reading every commission through find_by_status:
+/// "pending" also returns in-progress work.
+pub const FOLDER_STATUSES: [&str; 3] = ["pending", "completed", "cancelled"];
+
+// Left out of content_hash: the hash itself, the load-time warning, and the event log
+const UNHASHED_FIELDS: [&str; 3] = ["content_hash", "integrity_warning", "events"];
+
+// Last reference number handed out, in the data directory. It lives outside the commission
+// files so that deleting a commission never frees its number.
+pub const REFERENCE_COUNTER_FILE: &str = "reference_counter";
+
+// Commands run concurrently; two creates must not read the same counter value
+static REFERENCE_COUNTER_LOCK: Mutex<()> = Mutex::new(());
+
+/// Parsed commission files for the duration of one operation, so a command that scans the
+/// same folder several times reads each file once. Entries are keyed by path and reused only
+/// while the file's modification time and size are unchanged. Create one per command; it is
+/// deliberately not shared between commands.
+#[derive(Default)]
+pub struct ScanCache {
+    entries: HashMap<PathBuf, (SystemTime, u64, Commission)>,
+    files_read: usize,
+}
+
+impl ScanCache {
+    /// How many files were actually read from disk through this cache.
+    pub fn files_read(&self) -> usize {
+        self.files_read
+    }
+
+    fn invalidate(&mut self, path: &Path) {
+        self.entries.remove(path);
+    }
 }
 
 pub struct CommissionRepository;
 
 impl CommissionRepository {
-    pub async fn save(app_handle: &AppHandle, commission: &Commission) -> Result<(), String> {
+    /// Writes the commission and returns the file it was written to.
+    pub async fn save<R: Runtime>(app_handle: &AppHandle<R>, commission: &Commission) -> Result<PathBuf, String> {
         let data_dir = FileStorage::get_app_data_dir(app_handle)?;
         FileStorage::ensure_data_folders(&data_dir)?;
+        let partition_history = ConfigRepository::load(app_handle)?.storage.partition_history_by_year;
         
-        // Determine folder based on status
-        let folder_name = if commission.status == "completed" { "history" } else { "pendings" };
-        let commissions_dir = data_dir.join(folder_name);
+        // Saving through the app is what makes the content trusted again
+        let mut stored = commission.clone();
+        // Kept through later edits, so they don't move the record to another history year
+        if stored.status != "completed" {
+            stored.completed_at = None;
+        } else if stored.completed_at.is_none() {
+            stored.completed_at = Some(stored.updated_at.clone());
+        }
+        stored.content_hash = Some(Self::content_hash(&stored));
+        stored.integrity_warning = None;
+        
+        let commission_json = FileStorage::to_json(app_handle, &stored)
+            .map_err(|e| format!("Failed to serialize commission: {}", e))?;
+        
+        let commission_file = Self::file_path(&data_dir, &stored, partition_history);
+        FileStorage::write_json_file(&commission_file, &commission_json)?;
+        
+        // A copy left in the other history layout, or under another year, would show up twice
+        if commission.status == "completed" {
+            for stale_file in Self::history_copies(&data_dir, &stored)? {
+                if stale_file != commission_file {
+                    FileStorage::delete_file(&stale_file)?;
+                }
+            }
+        }
+        
+        Ok(commission_file)
+    }
+
+    /// Reserves the next commission reference number. The counter is replaced through a synced
+    /// temp file and a rename before the number is used, so a crash can skip a number but never
+    /// hand one out twice. Without a counter file, numbering continues after the highest on disk.
+    pub fn next_reference_number<R: Runtime>(app_handle: &AppHandle<R>) -> Result<u64, String> {
+        let _guard = REFERENCE_COUNTER_LOCK.lock()
+            .map_err(|_| "Reference counter is unavailable after an earlier failure".to_string())?;
+        let data_dir = FileStorage::get_app_data_dir(app_handle)?;
+        let counter_file = data_dir.join(REFERENCE_COUNTER_FILE);
+        
+        let last = if counter_file.exists() {
+            fs::read_to_string(&counter_file)
+                .map_err(|e| format!("Failed to read reference counter: {}", e))?
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| format!("Reference counter {} is corrupt", counter_file.display()))?
+        } else {
+            Self::highest_reference_number(&data_dir)?
+        };
+        let next = last + 1;
+        
+        let temp_file = counter_file.with_extension("tmp");
+        let mut file = File::create(&temp_file)
+            .map_err(|e| format!("Failed to write reference counter: {}", e))?;
+        file.write_all(next.to_string().as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| format!("Failed to write reference counter: {}", e))?;
+        fs::rename(&temp_file, &counter_file)
+            .map_err(|e| format!("Failed to replace reference counter: {}", e))?;
+        
+        Ok(next)
+    }
+
+    fn highest_reference_number(data_dir: &Path) -> Result<u64, String> {
+        let mut highest = 0;
+        for folder in COMMISSION_FOLDERS {
+            for client_dir in Self::client_dirs(&data_dir.join(folder))? {
+                for (_, content) in FileStorage::read_directory_json_entries(&client_dir)? {
+                    if let Some(reference_number) = Self::parse_commission(&content).ok().and_then(|c| c.reference_number) {
+                        highest = highest.max(reference_number);
+                    }
+                }
+            }
+        }
+        Ok(highest)
+    }
+
+    /// Every place in history a commission's file could be: the flat layout and each year.
+    fn history_copies(data_dir: &Path, commission: &Commission) -> Result<Vec<PathBuf>, String> {
+        let flat_file = Self::file_path(data_dir, commission, false);
+        let (Some(client_dir), Some(file_name)) = (flat_file.parent(), flat_file.file_name()) else {
+            return Ok(vec![flat_file]);
+        };
+        let client_folder = client_dir.file_name().unwrap_or_default();
+        
+        let history_dir = data_dir.join(Self::folder_for_status("completed"));
+        let mut copies = Vec::new();
+        for year_dir in fs::read_dir(&history_dir).map_err(|e| format!("Failed to read history directory: {}", e))? {
+            let year_dir = year_dir.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let name = year_dir.file_name().to_string_lossy().to_string();
+            if name.len() == 4 && name.chars().all(|c| c.is_ascii_digit()) {
+                let copy = year_dir.path().join(client_folder).join(file_name);
+                if copy.is_file() {
+                    copies.push(copy);
+                }
+            }
+        }
+        copies.push(flat_file);
+        
+        Ok(copies)
+    }
+
+    /// Folder under the data directory that holds commissions with the given status.
+    pub fn folder_for_status(status: &str) -> &'static str {
+        match status {
+            "completed" => "history",
+            "cancelled" => "cancelled",
+            _ => "pendings",
+        }
+    }
+
+    /// Where save() writes a commission: <folder>/<client name>/<id>_<created_at>.json, or
+    /// history/<year>/<client name>/... for completed work when history is partitioned by the
+    /// year it was completed in.
+    pub fn file_path(data_dir: &Path, commission: &Commission, partition_history: bool) -> PathBuf {
+        let mut commissions_dir = data_dir.join(Self::folder_for_status(&commission.status));
+        if partition_history && commission.status == "completed" {
+            let completed_at = commission.completed_at.as_ref().unwrap_or(&commission.updated_at);
+            if let Ok(completed_at) = chrono::DateTime::parse_from_rfc3339(completed_at) {
+                commissions_dir = commissions_dir.join(completed_at.format("%Y").to_string());
+            }
+        }
         
         // Create client subdirectory
         let sanitized_client_name = FileStorage::sanitize_filename(&commission.client_name);
@@ -36,43 +189,88 @@ impl CommissionRepository {
         
         // Create commission file with sanitized filename
         let sanitized_timestamp = FileStorage::sanitize_timestamp(&commission.created_at);
-        let commission_file = client_dir.join(format!("{}_{}.json", commission.id, sanitized_timestamp));
+        client_dir.join(format!("{}_{}.json", commission.id, sanitized_timestamp))
+    }
+
+    /// Client folders under pendings or history, including those inside history/<year>
+    /// folders, so either history layout reads the same.
+    pub fn client_dirs(folder_dir: &Path) -> Result<Vec<PathBuf>, String> {
+        let mut client_dirs = Vec::new();
+        if !folder_dir.is_dir() {
+            return Ok(client_dirs);
+        }
         
-        let commission_json = serde_json::to_string_pretty(commission)
-            .map_err(|e| format!("Failed to serialize commission: {}", e))?;
+        let entries = fs::read_dir(folder_dir)
+            .map_err(|e| format!("Failed to read commissions directory: {}", e))?;
         
-        FileStorage::write_json_file(&commission_file, &commission_json)?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let dir = entry.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            
+            // A client could be named like a year, so it is read as both
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.len() == 4 && name.chars().all(|c| c.is_ascii_digit()) {
+                let year_entries = fs::read_dir(&dir)
+                    .map_err(|e| format!("Failed to read year directory: {}", e))?;
+                for year_entry in year_entries {
+                    let year_entry = year_entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+                    if year_entry.path().is_dir() {
+                        client_dirs.push(year_entry.path());
+                    }
+                }
+            }
+            client_dirs.push(dir);
+        }
         
-        Ok(())
+        Ok(client_dirs)
     }
 
-    pub async fn find_by_status(app_handle: &AppHandle, status: &str) -> Result<Vec<Commission>, String> {
+    pub async fn find_by_status<R: Runtime>(app_handle: &AppHandle<R>, status: &str) -> Result<Vec<Commission>, String> {
+        let entries = Self::find_files_by_status(app_handle, status).await?;
+        Ok(entries.into_iter().map(|(_, commission)| commission).collect())
+    }
+
+    /// Like find_by_status, but keeps the file each commission was read from.
+    pub async fn find_files_by_status<R: Runtime>(
+        app_handle: &AppHandle<R>,
+        status: &str,
+    ) -> Result<Vec<(PathBuf, Commission)>, String> {
+        Self::find_files_by_status_cached(app_handle, status, &mut ScanCache::default()).await
+    }
+
+    pub async fn find_files_by_status_cached<R: Runtime>(
+        app_handle: &AppHandle<R>,
+        status: &str,
+        cache: &mut ScanCache,
+    ) -> Result<Vec<(PathBuf, Commission)>, String> {
         let data_dir = FileStorage::get_app_data_dir(app_handle)?;
         FileStorage::ensure_data_folders(&data_dir)?;
+        let lenient_status = ConfigRepository::load(app_handle)?.storage.lenient_status;
         
         // Determine folder based on status
-        let folder_name = if status == "completed" { "history" } else { "pendings" };
-        let commissions_dir = data_dir.join(folder_name);
+        let commissions_dir = data_dir.join(Self::folder_for_status(status));
         
         let mut commissions = Vec::new();
         
-        if commissions_dir.exists() {
-            let entries = fs::read_dir(&commissions_dir)
-                .map_err(|e| format!("Failed to read commissions directory: {}", e))?;
+        for client_dir in Self::client_dirs(&commissions_dir)? {
+            let client_entries = fs::read_dir(&client_dir)
+                .map_err(|e| format!("Failed to read client directory: {}", e))?;
             
-            for entry in entries {
-                let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
-                let client_dir = entry.path();
+            for client_entry in client_entries {
+                let client_entry = client_entry.map_err(|e| format!("Failed to read client entry: {}", e))?;
+                let path = client_entry.path();
+                if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                    continue;
+                }
                 
-                if client_dir.is_dir() {
-                    let client_json_contents = FileStorage::read_directory_json_files(&client_dir)?;
-                    
-                    for content in client_json_contents {
-                        match Self::parse_commission(&content) {
-                            Ok(commission) => commissions.push(commission),
-                            Err(e) => eprintln!("Failed to parse commission: {}", e),
-                        }
+                if let Some(mut commission) = Self::read_cached(&path, cache)? {
+                    if lenient_status {
+                        Self::normalize_status(&mut commission);
                     }
+                    commissions.push((path, commission));
                 }
             }
         }
@@ -80,67 +278,123 @@ impl CommissionRepository {
         Ok(commissions)
     }
 
-    pub async fn move_commission(
-        app_handle: &AppHandle,
+    fn read_cached(path: &Path, cache: &mut ScanCache) -> Result<Option<Commission>, String> {
+        let metadata = fs::metadata(path)
+            .map_err(|e| format!("Failed to read file metadata: {}", e))?;
+        let modified = metadata.modified()
+            .map_err(|e| format!("Failed to read file modification time: {}", e))?;
+        
+        if let Some((cached_modified, cached_len, commission)) = cache.entries.get(path) {
+            if *cached_modified == modified && *cached_len == metadata.len() {
+                return Ok(Some(commission.clone()));
+            }
+        }
+        
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        cache.files_read += 1;
+        
+        match Self::parse_commission(&content) {
+            Ok(commission) => {
+                cache.entries.insert(path.to_path_buf(), (modified, metadata.len(), commission.clone()));
+                Ok(Some(commission))
+            }
+            Err(e) => {
+                eprintln!("Failed to parse commission: {}", e);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Replaces a status outside the known set (e.g. a typo from hand-editing) with "pending",
+    /// so the record stays reachable through the status-based commands.
+    fn normalize_status(commission: &mut Commission) {
+        if !COMMISSION_STATUSES.contains(&commission.status.as_str()) {
+            eprintln!(
+                "Commission {} has unknown status '{}', treating it as pending",
+                commission.id, commission.status
+            );
+            commission.status = "pending".to_string();
+        }
+    }
+
+    pub async fn find_by_id<R: Runtime>(
+        app_handle: &AppHandle<R>,
+        commission_id: &str,
+        status: &str,
+    ) -> Result<Option<Commission>, String> {
+        let commissions = Self::find_by_status(app_handle, status).await?;
+        Ok(commissions.into_iter().find(|c| c.id == commission_id))
+    }
+
+    pub async fn move_commission<R: Runtime>(
+        app_handle: &AppHandle<R>,
+        commission_id: &str,
+        from_status: &str,
+        to_status: &str,
+    ) -> Result<(), String> {
+        Self::move_commission_cached(app_handle, commission_id, from_status, to_status, &mut ScanCache::default()).await
+    }
+
+    pub async fn move_commission_cached<R: Runtime>(
+        app_handle: &AppHandle<R>,
         commission_id: &str,
         from_status: &str,
         to_status: &str,
+        cache: &mut ScanCache,
     ) -> Result<(), String> {
         // Find the commission in the from folder
-        let commissions = Self::find_by_status(app_handle, from_status).await?;
-        let commission = commissions
+        let commissions = Self::find_files_by_status_cached(app_handle, from_status, cache).await?;
+        let (old_file, commission) = commissions
             .into_iter()
-            .find(|c| c.id == commission_id)
+            .find(|(_, c)| c.id == commission_id)
             .ok_or_else(|| format!("Commission {} not found in {} folder", commission_id, from_status))?;
 
         // Update status and timestamp
         let mut updated_commission = commission;
         updated_commission.status = to_status.to_string();
         updated_commission.updated_at = chrono::Utc::now().to_rfc3339();
+        updated_commission.events.push(CommissionEvent::new(
+            "status_changed",
+            Some(format!("{} -> {}", from_status, to_status)),
+        ));
 
         // Save to new location
-        Self::save(app_handle, &updated_commission).await?;
+        let new_file = Self::save(app_handle, &updated_commission).await?;
 
-        // Remove from old location
-        Self::delete_by_id_and_status(app_handle, commission_id, from_status).await?;
+        // Remove from old location, unless the save just rewrote that same file
+        cache.invalidate(&new_file);
+        if new_file != old_file {
+            FileStorage::delete_file(&old_file)?;
+            cache.invalidate(&old_file);
+        }
 
         Ok(())
     }
 
-    pub async fn delete_by_id_and_status(
-        app_handle: &AppHandle,
+    pub async fn delete_by_id_and_status<R: Runtime>(
+        app_handle: &AppHandle<R>,
         commission_id: &str,
         status: &str,
     ) -> Result<(), String> {
         let data_dir = FileStorage::get_app_data_dir(app_handle)?;
         
-        let folder_name = if status == "completed" { "history" } else { "pendings" };
-        let commissions_dir = data_dir.join(folder_name);
+        let commissions_dir = data_dir.join(Self::folder_for_status(status));
         
-        if commissions_dir.exists() {
-            let entries = fs::read_dir(&commissions_dir)
-                .map_err(|e| format!("Failed to read commissions directory: {}", e))?;
+        for client_dir in Self::client_dirs(&commissions_dir)? {
+            let client_entries = fs::read_dir(&client_dir)
+                .map_err(|e| format!("Failed to read client directory: {}", e))?;
             
-            for entry in entries {
-                let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
-                let client_dir = entry.path();
+            for client_entry in client_entries {
+                let client_entry = client_entry.map_err(|e| format!("Failed to read client entry: {}", e))?;
+                let file_path = client_entry.path();
                 
-                if client_dir.is_dir() {
-                    let client_entries = fs::read_dir(&client_dir)
-                        .map_err(|e| format!("Failed to read client directory: {}", e))?;
-                    
-                    for client_entry in client_entries {
-                        let client_entry = client_entry.map_err(|e| format!("Failed to read client entry: {}", e))?;
-                        let file_path = client_entry.path();
-                        
-                        if file_path.extension().and_then(|s| s.to_str()) == Some("json") {
-                            if let Ok(commission_json) = fs::read_to_string(&file_path) {
-                                if let Ok(commission) = serde_json::from_str::<Commission>(&commission_json) {
-                                    if commission.id == commission_id {
-                                        FileStorage::delete_file(&file_path)?;
-                                        return Ok(());
-                                    }
-                                }
+                if file_path.extension().and_then(|s| s.to_str()) == Some("json") {
+                    if let Ok(commission_json) = fs::read_to_string(&file_path) {
+                        if let Ok(commission) = serde_json::from_str::<Commission>(&commission_json) {
+                            if commission.id == commission_id {
+                                FileStorage::delete_file(&file_path)?;
+                                return Ok(());
                             }
                         }
                     }
@@ -151,7 +405,7 @@ impl CommissionRepository {
         Err("Commission not found".to_string())
     }
 
-    fn parse_commission(json: &str) -> Result<Commission, String> {
+    pub fn parse_commission(json: &str) -> Result<Commission, String> {
         let v: Value = serde_json::from_str(json).map_err(|e| format!("Failed to parse commission JSON: {}", e))?;
         
         // Detect legacy price (float) -> convert
@@ -163,18 +417,180 @@ impl CommissionRepository {
             return Err("Missing price or price_cents".into());
         };
         
-        Ok(Commission {
+        let mut commission = Commission {
             id: v.get("id").and_then(|s| s.as_str()).unwrap_or_default().to_string(),
             client_id: v.get("client_id").and_then(|s| s.as_str()).unwrap_or_default().to_string(),
             client_name: v.get("client_name").and_then(|s| s.as_str()).unwrap_or_default().to_string(),
             title: v.get("title").and_then(|s| s.as_str()).unwrap_or_default().to_string(),
             description: v.get("description").and_then(|s| s.as_str()).unwrap_or("").to_string(),
             price_cents,
+            paid_cents: v.get("paid_cents").and_then(|n| n.as_i64()).unwrap_or(0),
+            deposit_cents: v.get("deposit_cents").and_then(|n| n.as_i64()).unwrap_or(0),
+            tax_cents: v.get("tax_cents").and_then(|n| n.as_i64()).unwrap_or(0),
+            fee_cents: v.get("fee_cents").and_then(|n| n.as_i64()).unwrap_or(0),
+            currency: v.get("currency").and_then(|s| s.as_str()).map(|s| s.to_string()).unwrap_or_else(default_currency),
             payment_status: v.get("payment_status").and_then(|s| s.as_str()).unwrap_or("Not Paid").to_string(),
             status: v.get("status").and_then(|s| s.as_str()).unwrap_or("pending").to_string(),
+            priority: v.get("priority").and_then(|s| s.as_str()).map(|s| s.to_string()).unwrap_or_else(default_priority),
+            reference_number: v.get("reference_number").and_then(|n| n.as_u64()),
             created_at: v.get("created_at").and_then(|s| s.as_str()).unwrap_or_default().to_string(),
             updated_at: v.get("updated_at").and_then(|s| s.as_str()).unwrap_or_default().to_string(),
-            images: v.get("images").and_then(|arr| arr.as_array()).map(|arr| arr.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect()).unwrap_or_else(Vec::new)
-        })
+            completed_at: v.get("completed_at").and_then(|s| s.as_str()).map(|s| s.to_string()),
+            tags: v.get("tags").and_then(|arr| arr.as_array()).map(|arr| arr.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect()).unwrap_or_default(),
+            due_date: v.get("due_date").and_then(|s| s.as_str()).map(|s| s.to_string()),
+            estimated_hours: v.get("estimated_hours").and_then(|n| n.as_f64()),
+            events: v.get("events").and_then(|e| serde_json::from_value(e.clone()).ok()).unwrap_or_default(),
+            archived: v.get("archived").and_then(|b| b.as_bool()).unwrap_or(false),
+            cover_image: v.get("cover_image").and_then(|s| s.as_str()).map(|s| s.to_string()),
+            content_hash: v.get("content_hash").and_then(|s| s.as_str()).map(|s| s.to_string()),
+            integrity_warning: None,
+            images: v.get("images").and_then(|arr| arr.as_array()).map(|arr| arr.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect()).unwrap_or_default()
+        };
+        
+        // Records from before completed_at was kept were completed when they were last updated
+        if commission.status == "completed" && commission.completed_at.is_none() {
+            commission.completed_at = Some(commission.updated_at.clone());
+        }
+        
+        // Flag rather than reject, so an edited record can still be inspected and re-saved.
+        // The hash is checked against the file as written, so fields this version doesn't know
+        // about are covered too.
+        let tampered = commission.content_hash.as_ref().is_some_and(|stored| *stored != Self::hash_value(&v));
+        if tampered {
+            eprintln!("Commission {} does not match its content hash; it was modified outside the app", commission.id);
+            commission.integrity_warning = Some("Content hash mismatch: the file was modified outside the app".to_string());
+        }
+        
+        Ok(commission)
+    }
+
+    /// SHA-256 over the serialized commission, minus UNHASHED_FIELDS.
+    pub fn content_hash(commission: &Commission) -> String {
+        let value = serde_json::to_value(commission).unwrap_or(Value::Null);
+        Self::hash_value(&value)
+    }
+
+    // Keys are sorted so the hash doesn't depend on the order fields appear in the file
+    fn hash_value(value: &Value) -> String {
+        let fields: BTreeMap<&String, &Value> = value.as_object()
+            .map(|object| object.iter().filter(|(key, _)| !UNHASHED_FIELDS.contains(&key.as_str())).collect())
+            .unwrap_or_default();
+        Self::sha256_hex(serde_json::to_string(&fields).unwrap_or_default().as_bytes())
+    }
+
+    fn sha256_hex(bytes: &[u8]) -> String {
+        Sha256::digest(bytes)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{commission, TestEnv};
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn compact_mode_writes_smaller_files_that_still_load() {
+        let env = TestEnv::new();
+        let data_dir = env.data_dir().to_path_buf();
+        let record = commission("c1", "alice", "pending");
+        let path = CommissionRepository::file_path(&data_dir, &record, false);
+        
+        block_on(CommissionRepository::save(env.app(), &record)).unwrap();
+        let pretty_len = fs::metadata(&path).unwrap().len();
+        
+        env.configure(|config| config.storage.compact_json = true);
+        block_on(CommissionRepository::save(env.app(), &record)).unwrap();
+        let compact_len = fs::metadata(&path).unwrap().len();
+        
+        assert!(compact_len < pretty_len);
+        let loaded = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        assert_eq!(loaded.title, record.title);
+        assert!(loaded.integrity_warning.is_none());
+    }
+
+    #[test]
+    fn lenient_mode_loads_bogus_status_as_pending() {
+        let env = TestEnv::new();
+        let mut typo = commission("c1", "alice", "pending");
+        typo.status = "pendnig".to_string();
+        block_on(CommissionRepository::save(env.app(), &typo)).unwrap();
+        
+        let strict = block_on(CommissionRepository::find_by_status(env.app(), "pending")).unwrap();
+        assert_eq!(strict[0].status, "pendnig");
+        
+        env.configure(|config| config.storage.lenient_status = true);
+        let lenient = block_on(CommissionRepository::find_by_status(env.app(), "pending")).unwrap();
+        assert_eq!(lenient.len(), 1);
+        assert_eq!(lenient[0].status, "pending");
+    }
+
+    #[test]
+    fn hand_edited_fields_are_flagged_by_the_content_hash() {
+        let env = TestEnv::new();
+        let mut record = commission("c1", "alice", "pending");
+        record.tags = vec!["sketch".to_string()];
+        let path = block_on(CommissionRepository::save(env.app(), &record)).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        
+        // Fields outside the core set are covered too
+        for (from, to) in [("\"price_cents\": 1000", "\"price_cents\": 1"), ("\"sketch\"", "\"final\"")] {
+            fs::write(&path, saved.replace(from, to)).unwrap();
+            let loaded = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+            assert!(loaded.integrity_warning.is_some(), "edit to {} went unnoticed", from);
+        }
+        
+        // The event log is appended to freely and isn't covered
+        let mut value: Value = serde_json::from_str(&saved).unwrap();
+        value["events"] = serde_json::json!([{ "type": "created", "timestamp": "2026-01-01T00:00:00Z" }]);
+        fs::write(&path, value.to_string()).unwrap();
+        let loaded = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        assert!(loaded.integrity_warning.is_none());
+    }
+
+    #[test]
+    fn cascade_through_a_scan_cache_reads_each_file_once() {
+        let env = TestEnv::new();
+        for id in ["c1", "c2", "c3"] {
+            block_on(CommissionRepository::save(env.app(), &commission(id, "alice", "pending"))).unwrap();
+        }
+        
+        let mut cache = ScanCache::default();
+        let pending = block_on(CommissionRepository::find_files_by_status_cached(env.app(), "pending", &mut cache)).unwrap();
+        assert_eq!(pending.len(), 3);
+        for id in ["c1", "c2"] {
+            block_on(CommissionRepository::move_commission_cached(env.app(), id, "pending", "completed", &mut cache)).unwrap();
+        }
+        
+        assert_eq!(cache.files_read(), 3);
+        assert_eq!(block_on(CommissionRepository::find_by_status(env.app(), "completed")).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn editing_completed_work_keeps_it_in_its_history_year() {
+        let env = TestEnv::new();
+        env.configure(|config| config.storage.partition_history_by_year = true);
+        let mut done = commission("c1", "alice", "completed");
+        done.updated_at = "2025-06-01T00:00:00Z".to_string();
+        let path = block_on(CommissionRepository::save(env.app(), &done)).unwrap();
+        let year_dir = env.data_dir().join("history").join("2025");
+        assert!(path.starts_with(&year_dir));
+        
+        // Also for a record saved before completed_at was kept
+        let mut legacy: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        legacy.as_object_mut().unwrap().remove("completed_at");
+        fs::write(&path, legacy.to_string()).unwrap();
+        
+        let mut edited = block_on(CommissionRepository::find_by_id(env.app(), "c1", "completed")).unwrap().unwrap();
+        edited.title = "Retitled".to_string();
+        edited.updated_at = "2026-02-01T00:00:00Z".to_string();
+        let path = block_on(CommissionRepository::save(env.app(), &edited)).unwrap();
+        assert!(path.starts_with(&year_dir));
+        
+        let files = block_on(CommissionRepository::find_files_by_status(env.app(), "completed")).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].1.completed_at.as_deref(), Some("2025-06-01T00:00:00Z"));
     }
 }
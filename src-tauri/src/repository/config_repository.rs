@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Runtime};
+use super::file_storage::FileStorage;
+
+const CONFIG_FILE_NAME: &str = "config.json";
+
+// The config is loaded on most commands; one warning per corruption is enough
+static CORRUPT_CONFIG_WARNED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub data_directory: Option<String>, // None keeps the Data folder next to the executable
+    pub images: ImageSettings,
+    pub storage: StorageSettings,
+    pub watermark: WatermarkSettings,
+    pub pricing: PricingSettings,
+    pub bundle_signing_key: Option<String>, // Signs exported commission bundles and checks imported ones
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageSettings {
+    pub large_image_policy: String, // "none", "warn", "downscale"
+    pub max_image_edge: u32,
+    pub generate_thumbnails: bool, // Written to images/thumbs when an image is saved
+    pub image_layout: String, // "prefixed" (images/<id>_<name>) or "per_commission" (images/<id>/<name>)
+    pub convert_to_webp: bool, // Store JPEG and PNG uploads as lossless WebP when that is smaller
+}
+
+impl Default for ImageSettings {
+    fn default() -> Self {
+        Self {
+            large_image_policy: "none".to_string(),
+            max_image_edge: 4000,
+            generate_thumbnails: false,
+            image_layout: "prefixed".to_string(),
+            convert_to_webp: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageSettings {
+    pub compact_json: bool, // Smaller, faster files at the cost of readability
+    pub lenient_status: bool, // Load unknown commission statuses as "pending" instead of as-is
+    pub warn_duplicate_titles: bool, // Warn on save when the client already has a same-titled commission in that status
+    pub partition_history_by_year: bool, // Completed commissions go in history/<year>/<client>/
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatermarkSettings {
+    pub opacity_percent: u8,
+    pub font_size: f32,
+}
+
+impl Default for WatermarkSettings {
+    fn default() -> Self {
+        Self {
+            opacity_percent: 30,
+            font_size: 48.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PricingSettings {
+    pub hourly_rate_cents: i64, // 0 until set; price suggestions need a rate
+}
+
+pub struct ConfigRepository;
+
+impl ConfigRepository {
+    pub fn config_path<R: Runtime>(app_handle: &AppHandle<R>) -> Result<PathBuf, String> {
+        // Config lives next to the executable, outside the Data folder
+        Ok(FileStorage::get_app_dir(app_handle)?.join(CONFIG_FILE_NAME))
+    }
+
+    pub fn load<R: Runtime>(app_handle: &AppHandle<R>) -> Result<AppConfig, String> {
+        let config_file = Self::config_path(app_handle)?;
+        
+        if !config_file.exists() {
+            return Ok(AppConfig::default());
+        }
+        
+        let config_json = fs::read_to_string(&config_file)
+            .map_err(|e| format!("Failed to read config file: {}", e))?;
+        
+        // A corrupt config shouldn't stop the app from starting; validate_config reports it
+        // and reset_config_to_defaults replaces it
+        match serde_json::from_str(&config_json) {
+            Ok(config) => {
+                CORRUPT_CONFIG_WARNED.store(false, Ordering::Relaxed);
+                Ok(config)
+            }
+            Err(e) => {
+                if !CORRUPT_CONFIG_WARNED.swap(true, Ordering::Relaxed) {
+                    eprintln!("Config file {} is invalid ({}); using default settings", config_file.display(), e);
+                }
+                Ok(AppConfig::default())
+            }
+        }
+    }
+
+    pub fn save<R: Runtime>(app_handle: &AppHandle<R>, config: &AppConfig) -> Result<(), String> {
+        let config_file = Self::config_path(app_handle)?;
+        
+        let config_json = serde_json::to_string_pretty(config)
+            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        
+        FileStorage::write_json_file(&config_file, &config_json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestEnv;
+    #[test]
+    fn a_corrupt_config_is_warned_about_once() {
+        let env = TestEnv::new();
+        let config_file = ConfigRepository::config_path(env.app()).unwrap();
+        fs::write(&config_file, "not json").unwrap();
+        
+        ConfigRepository::load(env.app()).unwrap();
+        assert!(CORRUPT_CONFIG_WARNED.load(Ordering::Relaxed));
+        ConfigRepository::load(env.app()).unwrap();
+        assert!(CORRUPT_CONFIG_WARNED.load(Ordering::Relaxed));
+        
+        // Once the file is fixed, a later corruption is reported again
+        ConfigRepository::save(env.app(), &AppConfig::default()).unwrap();
+        ConfigRepository::load(env.app()).unwrap();
+        assert!(!CORRUPT_CONFIG_WARNED.load(Ordering::Relaxed));
+    }
+}
@@ -1,17 +1,40 @@
+use serde::Serialize;
 use std::fs;
-use std::path::PathBuf;
-use tauri::AppHandle;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+use super::config_repository::ConfigRepository;
+
+// Written into exports so imports can recognise app-produced bundles
+pub const EXPORT_MARKER_FILE: &str = "commflow-export.json";
+
+// Record layout version: 1 stored float prices, 2 stores integer cents
+pub const CURRENT_SCHEMA_VERSION: u64 = 2;
+
+// Overrides the data directory for tests and power users; takes precedence over the config
+pub const DATA_DIR_ENV_VAR: &str = "COMMFLOW_DATA_DIR";
 
 pub struct FileStorage;
 
 impl FileStorage {
-    pub fn get_app_data_dir(_app_handle: &AppHandle) -> Result<PathBuf, String> {
+    pub fn get_app_dir<R: Runtime>(_app_handle: &AppHandle<R>) -> Result<PathBuf, String> {
         // Get the directory where the executable is located
         let exe_path = std::env::current_exe().map_err(|e| format!("Failed to get exe path: {}", e))?;
         let exe_dir = exe_path.parent().ok_or("Failed to get exe directory")?;
         
-        // Create Data folder in the same directory as the executable
-        let data_dir = exe_dir.join("Data");
+        Ok(exe_dir.to_path_buf())
+    }
+
+    pub fn get_app_data_dir<R: Runtime>(app_handle: &AppHandle<R>) -> Result<PathBuf, String> {
+        // Use the environment override, the configured location, or a Data folder in the same
+        // directory as the executable
+        let env_dir = std::env::var_os(DATA_DIR_ENV_VAR).filter(|dir| !dir.is_empty());
+        let data_dir = match env_dir {
+            Some(env_dir) => PathBuf::from(env_dir),
+            None => match ConfigRepository::load(app_handle)?.data_directory {
+                Some(custom_dir) => PathBuf::from(custom_dir),
+                None => Self::get_app_dir(app_handle)?.join("Data"),
+            },
+        };
         
         // Create the Data directory if it doesn't exist
         fs::create_dir_all(&data_dir).map_err(|e| format!("Failed to create data directory: {}", e))?;
@@ -19,8 +42,8 @@ impl FileStorage {
         Ok(data_dir)
     }
 
-    pub fn ensure_data_folders(data_dir: &PathBuf) -> Result<(), String> {
-        let folders = ["clients", "pendings", "history"];
+    pub fn ensure_data_folders(data_dir: &Path) -> Result<(), String> {
+        let folders = ["clients", "pendings", "history", "cancelled"];
         
         for folder in folders.iter() {
             let folder_path = data_dir.join(folder);
@@ -30,8 +53,14 @@ impl FileStorage {
         Ok(())
     }
 
-    pub fn read_directory_json_files(dir_path: &PathBuf) -> Result<Vec<String>, String> {
-        let mut json_contents = Vec::new();
+    pub fn read_directory_json_files(dir_path: &Path) -> Result<Vec<String>, String> {
+        let entries = Self::read_directory_json_entries(dir_path)?;
+        Ok(entries.into_iter().map(|(_, content)| content).collect())
+    }
+
+    /// Like read_directory_json_files, but keeps the path each content was read from.
+    pub fn read_directory_json_entries(dir_path: &Path) -> Result<Vec<(PathBuf, String)>, String> {
+        let mut json_entries = Vec::new();
 
         if dir_path.exists() {
             let entries = fs::read_dir(dir_path)
@@ -44,12 +73,25 @@ impl FileStorage {
                 if path.extension().and_then(|s| s.to_str()) == Some("json") {
                     let content = fs::read_to_string(&path)
                         .map_err(|e| format!("Failed to read file: {}", e))?;
-                    json_contents.push(content);
+                    json_entries.push((path, content));
                 }
             }
         }
 
-        Ok(json_contents)
+        Ok(json_entries)
+    }
+
+    /// Serializes a record using the configured JSON style; reads accept either style.
+    pub fn to_json<R: Runtime, T: Serialize>(app_handle: &AppHandle<R>, value: &T) -> Result<String, String> {
+        let compact = ConfigRepository::load(app_handle)?.storage.compact_json;
+        
+        let json = if compact {
+            serde_json::to_string(value)
+        } else {
+            serde_json::to_string_pretty(value)
+        };
+        
+        json.map_err(|e| e.to_string())
     }
 
     pub fn write_json_file(file_path: &PathBuf, json_content: &str) -> Result<(), String> {
@@ -73,11 +115,173 @@ impl FileStorage {
         Ok(())
     }
 
+    /// Quotes a CSV field when needed, and defuses leading characters spreadsheets would
+    /// treat as a formula.
+    pub fn csv_field(value: &str) -> String {
+        let value = if value.starts_with(['=', '+', '-', '@']) {
+            format!("'{}", value)
+        } else {
+            value.to_string()
+        };
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value
+        }
+    }
+
+    pub fn export_marker_json() -> String {
+        serde_json::json!({
+            "app": "CommFlow",
+            "version": env!("CARGO_PKG_VERSION"),
+            "schema_version": CURRENT_SCHEMA_VERSION,
+            "created_at": chrono::Utc::now().to_rfc3339(),
+        }).to_string()
+    }
+
+    pub fn has_export_marker(dir_path: &Path) -> bool {
+        fs::read_to_string(dir_path.join(EXPORT_MARKER_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .is_some_and(|marker| marker.get("app").and_then(|a| a.as_str()) == Some("CommFlow"))
+    }
+
+    /// Lists every file under a directory, recursively, without following symlinks.
+    pub fn list_files_recursive(dir_path: &Path) -> Result<Vec<PathBuf>, String> {
+        let mut files = Vec::new();
+        
+        if !dir_path.exists() {
+            return Ok(files);
+        }
+        
+        let entries = fs::read_dir(dir_path)
+            .map_err(|e| format!("Failed to read directory: {}", e))?;
+        
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let file_type = entry.file_type()
+                .map_err(|e| format!("Failed to read file type: {}", e))?;
+            
+            if file_type.is_dir() {
+                files.extend(Self::list_files_recursive(&entry.path())?);
+            } else if file_type.is_file() {
+                files.push(entry.path());
+            }
+        }
+        
+        Ok(files)
+    }
+
+    /// Picks where a file with the given contents goes under base_dir: relative_path itself,
+    /// or the first free "{stem}_{n}.{ext}" beside it when that name holds something else. A
+    /// name already holding the same contents is returned as is, so the file can be reused.
+    pub fn unused_or_identical_path(base_dir: &Path, relative_path: &str, contents: &[u8]) -> String {
+        let original = Path::new(relative_path);
+        let stem = original.file_stem().unwrap_or_default().to_string_lossy();
+        
+        let mut candidate = relative_path.to_string();
+        let mut suffix = 1;
+        loop {
+            let target = base_dir.join(&candidate);
+            if !target.exists() || fs::read(&target).is_ok_and(|existing| existing == contents) {
+                return candidate;
+            }
+            
+            let file_name = match original.extension() {
+                Some(ext) => format!("{}_{}.{}", stem, suffix, ext.to_string_lossy()),
+                None => format!("{}_{}", stem, suffix),
+            };
+            candidate = original.with_file_name(file_name).to_string_lossy().replace('\\', "/");
+            suffix += 1;
+        }
+    }
+
+    /// Counts files and their total size under a directory, recursively.
+    pub fn directory_stats(dir_path: &Path) -> Result<(usize, u64), String> {
+        let mut file_count = 0;
+        let mut total_bytes = 0;
+        
+        if !dir_path.exists() {
+            return Ok((file_count, total_bytes));
+        }
+        
+        let entries = fs::read_dir(dir_path)
+            .map_err(|e| format!("Failed to read directory: {}", e))?;
+        
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let path = entry.path();
+            
+            if path.is_dir() {
+                let (sub_count, sub_bytes) = Self::directory_stats(&path)?;
+                file_count += sub_count;
+                total_bytes += sub_bytes;
+            } else {
+                let metadata = entry.metadata()
+                    .map_err(|e| format!("Failed to read file metadata: {}", e))?;
+                file_count += 1;
+                total_bytes += metadata.len();
+            }
+        }
+        
+        Ok((file_count, total_bytes))
+    }
+
     pub fn sanitize_filename(name: &str) -> String {
-        name.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_")
+        let sanitized = name.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_");
+
+        // Reserved device names (CON, COM1, ...) are invalid on Windows even with an extension
+        let (stem, extension) = match sanitized.find('.') {
+            Some(index) => sanitized.split_at(index),
+            None => (sanitized.as_str(), ""),
+        };
+
+        if Self::is_reserved_windows_name(stem) {
+            return format!("{}_{}", stem, extension);
+        }
+
+        sanitized
+    }
+
+    fn is_reserved_windows_name(stem: &str) -> bool {
+        const RESERVED_NAMES: [&str; 4] = ["CON", "PRN", "AUX", "NUL"];
+
+        let upper = stem.trim_end().to_uppercase();
+        if RESERVED_NAMES.contains(&upper.as_str()) {
+            return true;
+        }
+
+        // COM1-COM9 and LPT1-LPT9
+        if upper.len() == 4 && (upper.starts_with("COM") || upper.starts_with("LPT")) {
+            return matches!(upper.as_bytes()[3], b'1'..=b'9');
+        }
+
+        false
     }
 
     pub fn sanitize_timestamp(timestamp: &str) -> String {
         timestamp.replace([':', '/', '\\', '*', '?', '"', '<', '>', '|'], "-")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_filename_suffixes_reserved_device_names() {
+        assert_eq!(FileStorage::sanitize_filename("CON"), "CON_");
+        assert_eq!(FileStorage::sanitize_filename("com1.json"), "com1_.json");
+        assert_eq!(FileStorage::sanitize_filename("Console"), "Console");
+    }
+
+    #[test]
+    fn data_dir_env_var_redirects_saves() {
+        let env = crate::test_support::TestEnv::new();
+        assert_eq!(FileStorage::get_app_data_dir(env.app()).unwrap(), env.data_dir());
+
+        let client = crate::test_support::client("alice", "Alice");
+        tauri::async_runtime::block_on(crate::repository::ClientRepository::save(env.app(), &client)).unwrap();
+        assert!(env.data_dir().join("clients").join("alice.json").exists());
+    }
+}
@@ -1,7 +1,11 @@
+pub mod audit_repository;
 pub mod client_repository;
 pub mod commission_repository;
+pub mod config_repository;
 pub mod file_storage;
 
+pub use audit_repository::AuditRepository;
 pub use client_repository::ClientRepository;
 pub use commission_repository::CommissionRepository;
+pub use config_repository::ConfigRepository;
 pub use file_storage::FileStorage;
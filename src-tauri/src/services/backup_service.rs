@@ -0,0 +1,442 @@
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+use zip::write::SimpleFileOptions;
+use crate::repository::{CommissionRepository, FileStorage};
+use crate::repository::commission_repository::FOLDER_STATUSES;
+use crate::repository::file_storage::EXPORT_MARKER_FILE;
+use super::image_service::ImageService;
+use super::validation_service::ValidationService;
+
+// Folders that make up a complete backup of the data directory
+const BACKUP_FOLDERS: [&str; 4] = ["clients", "pendings", "history", "cancelled"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupInfo {
+    pub entry_count: usize,
+    pub file_count: usize,
+    pub missing_folders: Vec<String>,
+    pub has_expected_folders: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreSummary {
+    pub files_restored: usize,
+    pub files_skipped: usize,
+    pub snapshot_path: Option<String>, // Backup of the data as it was before the restore
+}
+
+pub struct BackupService;
+
+impl BackupService {
+    pub async fn create_backup<R: Runtime>(app_handle: AppHandle<R>) -> Result<String, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        FileStorage::ensure_data_folders(&data_dir)?;
+        
+        let backups_dir = data_dir.join("backups");
+        fs::create_dir_all(&backups_dir)
+            .map_err(|e| format!("Failed to create backups directory: {}", e))?;
+        
+        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+        let backup_file = backups_dir.join(format!("backup_{}.zip", timestamp));
+        
+        Self::write_backup(&data_dir, &backup_file)?;
+        
+        Ok(backup_file.to_string_lossy().to_string())
+    }
+
+    fn write_backup(data_dir: &Path, backup_file: &Path) -> Result<(), String> {
+        let file = File::create(backup_file)
+            .map_err(|e| format!("Failed to create backup file: {}", e))?;
+        let mut zip = zip::ZipWriter::new(file);
+        
+        for folder in BACKUP_FOLDERS {
+            Self::add_directory(&mut zip, data_dir, &data_dir.join(folder))?;
+        }
+        
+        zip.start_file(EXPORT_MARKER_FILE, SimpleFileOptions::default())
+            .map_err(|e| format!("Failed to add export marker to backup: {}", e))?;
+        zip.write_all(FileStorage::export_marker_json().as_bytes())
+            .map_err(|e| format!("Failed to write export marker to backup: {}", e))?;
+        
+        zip.finish().map_err(|e| format!("Failed to finish backup: {}", e))?;
+        
+        Ok(())
+    }
+
+    pub async fn verify_backup<R: Runtime>(_app_handle: AppHandle<R>, zip_path: String) -> Result<BackupInfo, String> {
+        let zip_file = PathBuf::from(&zip_path);
+        if !zip_file.is_file() {
+            return Err("Backup file does not exist".to_string());
+        }
+        
+        let file = File::open(&zip_file)
+            .map_err(|e| format!("Failed to open backup file: {}", e))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| format!("Backup is not a valid zip archive (it may be truncated): {}", e))?;
+        
+        let mut file_count = 0;
+        let mut top_level_folders = Vec::new();
+        
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)
+                .map_err(|e| format!("Failed to read backup entry {}: {}", index, e))?;
+            let entry_name = entry.name().to_string();
+            
+            if let Some(folder) = entry_name.split('/').next() {
+                if !top_level_folders.iter().any(|f| f == folder) {
+                    top_level_folders.push(folder.to_string());
+                }
+            }
+            
+            if entry.is_dir() {
+                continue;
+            }
+            
+            // Reading an entry to the end makes the zip reader check its CRC
+            io::copy(&mut entry, &mut io::sink())
+                .map_err(|e| format!("Backup entry {} is corrupt: {}", entry_name, e))?;
+            file_count += 1;
+        }
+        
+        let missing_folders: Vec<String> = BACKUP_FOLDERS.iter()
+            .filter(|folder| !top_level_folders.iter().any(|f| f == *folder))
+            .map(|folder| folder.to_string())
+            .collect();
+        
+        Ok(BackupInfo {
+            entry_count: archive.len(),
+            file_count,
+            has_expected_folders: missing_folders.is_empty(),
+            missing_folders,
+        })
+    }
+
+    /// Extracts a backup into the data directory. `mode` decides what happens to files that
+    /// already exist: "overwrite" replaces them, "skip" keeps them, and "newer_wins" keeps
+    /// whichever client or commission record has the later updated_at. Other files, such as
+    /// images, are never replaced under "newer_wins". Every entry is checked before anything
+    /// is written, so a malicious archive is rejected as a whole.
+    pub async fn restore_backup<R: Runtime>(
+        app_handle: AppHandle<R>,
+        zip_path: String,
+        mode: String,
+        snapshot_first: bool,
+    ) -> Result<RestoreSummary, String> {
+        ValidationService::validate_restore_mode(&mode)?;
+        
+        // Reading every entry also proves the archive isn't truncated or corrupt
+        let info = Self::verify_backup(app_handle.clone(), zip_path.clone()).await?;
+        if !info.has_expected_folders {
+            return Err(format!("Not a CommFlow backup: missing {}", info.missing_folders.join(", ")));
+        }
+        
+        let file = File::open(&zip_path)
+            .map_err(|e| format!("Failed to open backup file: {}", e))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| format!("Backup is not a valid zip archive: {}", e))?;
+        
+        // Allowed entries only: inside the backup folders, no absolute paths or ..
+        let mut targets = Vec::new();
+        for index in 0..archive.len() {
+            let entry = archive.by_index(index)
+                .map_err(|e| format!("Failed to read backup entry {}: {}", index, e))?;
+            if entry.is_dir() || entry.name() == EXPORT_MARKER_FILE {
+                continue;
+            }
+            
+            let relative = entry.enclosed_name()
+                .ok_or_else(|| format!("Backup entry {} points outside the data directory", entry.name()))?;
+            let top_level = relative.components().next()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .unwrap_or_default();
+            if !BACKUP_FOLDERS.contains(&top_level.as_str()) {
+                return Err(format!("Backup entry {} is outside the backup folders", entry.name()));
+            }
+            
+            targets.push((index, relative));
+        }
+        
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        
+        // Named apart from regular backups so it can't replace the archive being restored
+        let snapshot_path = if snapshot_first {
+            let backups_dir = data_dir.join("backups");
+            fs::create_dir_all(&backups_dir)
+                .map_err(|e| format!("Failed to create backups directory: {}", e))?;
+            let snapshot_file = backups_dir.join(format!("pre_restore_{}.zip", chrono::Utc::now().format("%Y%m%d_%H%M%S")));
+            Self::write_backup(&data_dir, &snapshot_file)?;
+            Some(snapshot_file.to_string_lossy().to_string())
+        } else {
+            None
+        };
+        
+        let mut summary = RestoreSummary {
+            files_restored: 0,
+            files_skipped: 0,
+            snapshot_path,
+        };
+        
+        for (index, relative) in targets {
+            let mut entry = archive.by_index(index)
+                .map_err(|e| format!("Failed to read backup entry {}: {}", index, e))?;
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents)
+                .map_err(|e| format!("Failed to read backup entry {}: {}", entry.name(), e))?;
+            
+            let target = data_dir.join(&relative);
+            if target.exists() && !Self::should_replace(&mode, &target, &contents) {
+                summary.files_skipped += 1;
+                continue;
+            }
+            
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create directory: {}", e))?;
+            }
+            fs::write(&target, &contents)
+                .map_err(|e| format!("Failed to restore {}: {}", relative.display(), e))?;
+            summary.files_restored += 1;
+        }
+        
+        println!("Restored {} files from {} ({} kept as they were)", summary.files_restored, zip_path, summary.files_skipped);
+        
+        Ok(summary)
+    }
+
+    fn should_replace(mode: &str, existing: &Path, incoming: &[u8]) -> bool {
+        match mode {
+            "overwrite" => true,
+            "newer_wins" => {
+                let updated_at = |bytes: &[u8]| {
+                    let value: serde_json::Value = serde_json::from_slice(bytes).ok()?;
+                    chrono::DateTime::parse_from_rfc3339(value.get("updated_at")?.as_str()?).ok()
+                };
+                
+                // Without timestamps on both sides there's no telling which is newer
+                match (fs::read(existing).ok().and_then(|bytes| updated_at(&bytes)), updated_at(incoming)) {
+                    (Some(current), Some(restored)) => restored > current,
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Size of each backup in Data/backups, keyed by the time in its file name, oldest first.
+    /// Zips that weren't named by create_backup are ignored.
+    pub async fn analyze_backup_sizes<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<(String, u64)>, String> {
+        let backups_dir = FileStorage::get_app_data_dir(&app_handle)?.join("backups");
+        if !backups_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        
+        let entries = fs::read_dir(&backups_dir)
+            .map_err(|e| format!("Failed to read backups directory: {}", e))?;
+        
+        let mut sizes = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            
+            // Same format create_backup writes
+            let Some(created_at) = file_name.strip_prefix("backup_")
+                .and_then(|rest| rest.strip_suffix(".zip"))
+                .and_then(|timestamp| chrono::NaiveDateTime::parse_from_str(timestamp, "%Y%m%d_%H%M%S").ok())
+            else {
+                continue;
+            };
+            
+            let size = entry.metadata()
+                .map_err(|e| format!("Failed to read backup metadata: {}", e))?
+                .len();
+            sizes.push((created_at, size));
+        }
+        
+        sizes.sort();
+        
+        Ok(sizes.into_iter()
+            .map(|(created_at, size)| (created_at.and_utc().to_rfc3339(), size))
+            .collect())
+    }
+
+    /// Every file belonging to one client: the client record, its commissions in every folder,
+    /// and their images with any originals and thumbnails. Paths are inside the data directory.
+    pub async fn client_files<R: Runtime>(app_handle: &AppHandle<R>, client_id: &str) -> Result<Vec<PathBuf>, String> {
+        let data_dir = FileStorage::get_app_data_dir(app_handle)?;
+        let mut files = vec![data_dir.join("clients").join(format!("{}.json", client_id))];
+        
+        for status in FOLDER_STATUSES {
+            for (path, commission) in CommissionRepository::find_files_by_status(app_handle, status).await? {
+                if commission.client_id != client_id {
+                    continue;
+                }
+                files.push(path);
+                
+                for image_path in commission.images.iter().filter(|p| !p.is_empty() && !p.starts_with("data:")) {
+                    let Some(image_file) = ImageService::resolve_image_path(&data_dir, &commission.client_name, &commission.status, image_path) else {
+                        continue;
+                    };
+                    for derived in ["originals", "thumbs"] {
+                        if let Some(name) = image_file.file_name() {
+                            let derived_file = image_file.with_file_name(derived).join(name);
+                            if derived_file.is_file() {
+                                files.push(derived_file);
+                            }
+                        }
+                    }
+                    files.push(image_file);
+                }
+            }
+        }
+        
+        // Commissions may share an image
+        files.sort();
+        files.dedup();
+        
+        Ok(files)
+    }
+
+    /// Zips the given client's files into the destination folder and checks the archive reads
+    /// back completely before returning its path.
+    pub async fn create_client_bundle<R: Runtime>(app_handle: &AppHandle<R>, client_id: &str, dest_dir: &Path) -> Result<PathBuf, String> {
+        let data_dir = FileStorage::get_app_data_dir(app_handle)?;
+        let files = Self::client_files(app_handle, client_id).await?;
+        
+        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+        let bundle_file = dest_dir.join(format!("client_{}_{}.zip", client_id, timestamp));
+        
+        let file = File::create(&bundle_file)
+            .map_err(|e| format!("Failed to create client bundle: {}", e))?;
+        let mut zip = zip::ZipWriter::new(file);
+        for path in &files {
+            Self::add_file(&mut zip, &data_dir, path)?;
+        }
+        zip.finish().map_err(|e| format!("Failed to finish client bundle: {}", e))?;
+        
+        let info = Self::verify_backup(app_handle.clone(), bundle_file.to_string_lossy().to_string()).await?;
+        if info.file_count != files.len() {
+            return Err(format!("Client bundle holds {} of {} files", info.file_count, files.len()));
+        }
+        
+        Ok(bundle_file)
+    }
+
+    fn add_directory(
+        zip: &mut zip::ZipWriter<File>,
+        base_dir: &Path,
+        dir_path: &Path,
+    ) -> Result<(), String> {
+        let relative_dir = Self::archive_name(base_dir, dir_path)?;
+        zip.add_directory(relative_dir, SimpleFileOptions::default())
+            .map_err(|e| format!("Failed to add directory to backup: {}", e))?;
+        
+        if !dir_path.exists() {
+            return Ok(());
+        }
+        
+        let entries = fs::read_dir(dir_path)
+            .map_err(|e| format!("Failed to read directory: {}", e))?;
+        
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let path = entry.path();
+            
+            if path.is_dir() {
+                Self::add_directory(zip, base_dir, &path)?;
+            } else {
+                Self::add_file(zip, base_dir, &path)?;
+            }
+        }
+        
+        Ok(())
+    }
+
+    fn add_file(zip: &mut zip::ZipWriter<File>, base_dir: &Path, path: &Path) -> Result<(), String> {
+        let contents = fs::read(path)
+            .map_err(|e| format!("Failed to read file for backup: {}", e))?;
+        zip.start_file(Self::archive_name(base_dir, path)?, SimpleFileOptions::default())
+            .map_err(|e| format!("Failed to add file to backup: {}", e))?;
+        zip.write_all(&contents)
+            .map_err(|e| format!("Failed to write file to backup: {}", e))
+    }
+
+    fn archive_name(base_dir: &Path, path: &Path) -> Result<String, String> {
+        let relative = path.strip_prefix(base_dir)
+            .map_err(|_| "Backup path is outside the data directory".to_string())?;
+        
+        // Zip entries always use forward slashes, regardless of platform
+        Ok(relative.components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{commission, TestEnv};
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn verify_backup_accepts_fresh_backup_and_rejects_truncated_one() {
+        let env = TestEnv::new();
+        block_on(CommissionRepository::save(env.app(), &commission("c1", "alice", "pending"))).unwrap();
+        
+        let backup = block_on(BackupService::create_backup(env.app().clone())).unwrap();
+        let info = block_on(BackupService::verify_backup(env.app().clone(), backup.clone())).unwrap();
+        assert!(info.has_expected_folders);
+        assert!(info.file_count >= 2);
+        
+        let bytes = fs::read(&backup).unwrap();
+        let truncated = env.data_dir().join("truncated.zip");
+        fs::write(&truncated, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(block_on(BackupService::verify_backup(env.app().clone(), truncated.to_string_lossy().to_string())).is_err());
+    }
+
+    #[test]
+    fn backup_sizes_are_listed_chronologically() {
+        let env = TestEnv::new();
+        let backups_dir = env.data_dir().join("backups");
+        fs::create_dir_all(&backups_dir).unwrap();
+        fs::write(backups_dir.join("backup_20260301_120000.zip"), vec![0; 300]).unwrap();
+        fs::write(backups_dir.join("backup_20260115_090000.zip"), vec![0; 100]).unwrap();
+        fs::write(backups_dir.join("notes.txt"), "ignored").unwrap();
+        
+        let sizes = block_on(BackupService::analyze_backup_sizes(env.app().clone())).unwrap();
+        assert_eq!(sizes, vec![
+            ("2026-01-15T09:00:00+00:00".to_string(), 100),
+            ("2026-03-01T12:00:00+00:00".to_string(), 300),
+        ]);
+    }
+
+    #[test]
+    fn newer_wins_restore_keeps_later_local_edits() {
+        let env = TestEnv::new();
+        let save = |id: &str, title: &str, updated_at: &str| {
+            let mut c = commission(id, "alice", "pending");
+            c.title = title.to_string();
+            c.updated_at = updated_at.to_string();
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        };
+        save("c1", "Backed up", "2026-02-01T00:00:00Z");
+        save("c2", "Backed up", "2026-02-01T00:00:00Z");
+        save("c3", "Backed up", "2026-02-01T00:00:00Z");
+        let backup = block_on(BackupService::create_backup(env.app().clone())).unwrap();
+        
+        save("c1", "Edited later", "2026-03-01T00:00:00Z");
+        save("c2", "Rolled back", "2026-01-01T00:00:00Z");
+        block_on(CommissionRepository::delete_by_id_and_status(env.app(), "c3", "pending")).unwrap();
+        
+        let summary = block_on(BackupService::restore_backup(env.app().clone(), backup, "newer_wins".to_string(), true)).unwrap();
+        assert_eq!((summary.files_restored, summary.files_skipped), (2, 1));
+        assert!(summary.snapshot_path.is_some_and(|path| Path::new(&path).is_file()));
+        
+        let title = |id: &str| block_on(CommissionRepository::find_by_id(env.app(), id, "pending")).unwrap().unwrap().title;
+        assert_eq!(title("c1"), "Edited later");
+        assert_eq!(title("c2"), "Backed up");
+        assert_eq!(title("c3"), "Backed up");
+    }
+}
@@ -0,0 +1,339 @@
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+use zip::write::SimpleFileOptions;
+use crate::repository::{CommissionRepository, ConfigRepository, FileStorage};
+use crate::repository::commission_repository::FOLDER_STATUSES;
+use super::commission_service::CommissionService;
+use super::image_service::ImageService;
+use super::validation_service::ValidationService;
+
+// Entries of a commission bundle: the record, its images under images/, and an optional signature
+const BUNDLE_COMMISSION_ENTRY: &str = "commission.json";
+const BUNDLE_SIGNATURE_ENTRY: &str = "signature";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleImport {
+    pub commission_id: String,
+    pub images_imported: usize,
+    pub signature: String, // "valid", "invalid", "unsigned", or "unchecked" when no key is configured
+    pub warnings: Vec<String>,
+}
+
+pub struct BundleService;
+
+impl BundleService {
+    /// Zips one commission and its image files into the destination folder. When a signing
+    /// key is configured the bundle also carries an HMAC-SHA256 over its contents.
+    pub async fn export_commission_bundle<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        status: String,
+        dest: String,
+    ) -> Result<String, String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_status(&status)?;
+
+        let dest_dir = PathBuf::from(&dest);
+        if !dest_dir.is_dir() {
+            return Err("Bundle destination must be an existing folder".to_string());
+        }
+
+        let commission = CommissionRepository::find_by_id(&app_handle, &commission_id, &status).await?
+            .ok_or_else(|| format!("Commission {} not found", commission_id))?;
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+
+        let commission_json = serde_json::to_string_pretty(&commission)
+            .map_err(|e| format!("Failed to serialize commission: {}", e))?;
+        let mut entries = vec![(BUNDLE_COMMISSION_ENTRY.to_string(), commission_json.into_bytes())];
+
+        for image_path in commission.images.iter().filter(|p| p.starts_with("images/")) {
+            match ImageService::resolve_image_path(&data_dir, &commission.client_name, &commission.status, image_path) {
+                Some(image_file) => {
+                    let contents = fs::read(&image_file)
+                        .map_err(|e| format!("Failed to read image {}: {}", image_path, e))?;
+                    entries.push((image_path.clone(), contents));
+                }
+                None => eprintln!("Image {} of commission {} is missing; leaving it out of the bundle", image_path, commission.id),
+            }
+        }
+
+        if let Some(key) = ConfigRepository::load(&app_handle)?.bundle_signing_key.filter(|key| !key.is_empty()) {
+            let signature = Self::sign(&key, &entries);
+            entries.push((BUNDLE_SIGNATURE_ENTRY.to_string(), signature.into_bytes()));
+        }
+
+        let bundle_file = dest_dir.join(format!("commission_{}.zip", commission.id));
+        let file = File::create(&bundle_file)
+            .map_err(|e| format!("Failed to create bundle: {}", e))?;
+        let mut zip = zip::ZipWriter::new(file);
+        for (name, contents) in &entries {
+            zip.start_file(name.as_str(), SimpleFileOptions::default())
+                .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
+            zip.write_all(contents)
+                .map_err(|e| format!("Failed to write {} to bundle: {}", name, e))?;
+        }
+        zip.finish().map_err(|e| format!("Failed to finish bundle: {}", e))?;
+
+        Ok(bundle_file.to_string_lossy().to_string())
+    }
+
+    /// Imports a bundle written by export_commission_bundle as a new commission. With a
+    /// signing key configured the signature is checked, and a missing or wrong one is
+    /// reported as a warning rather than refused.
+    pub async fn import_commission_bundle<R: Runtime>(app_handle: AppHandle<R>, zip_path: String) -> Result<BundleImport, String> {
+        let file = File::open(&zip_path)
+            .map_err(|e| format!("Failed to open bundle: {}", e))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| format!("Bundle is not a valid zip archive: {}", e))?;
+
+        let mut entries = Vec::new();
+        let mut signature = None;
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)
+                .map_err(|e| format!("Failed to read bundle entry {}: {}", index, e))?;
+            if entry.is_dir() {
+                continue;
+            }
+
+            let name = entry.name().to_string();
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents)
+                .map_err(|e| format!("Bundle entry {} is corrupt: {}", name, e))?;
+
+            if name == BUNDLE_SIGNATURE_ENTRY {
+                signature = Some(String::from_utf8_lossy(&contents).trim().to_string());
+                continue;
+            }
+            if name != BUNDLE_COMMISSION_ENTRY {
+                // Image entries become paths under the client folder, so they get the same checks
+                if !name.starts_with("images/") || entry.enclosed_name().is_none() {
+                    return Err(format!("Bundle entry {} is not allowed", name));
+                }
+                ValidationService::validate_image_path(&name)?;
+            }
+            entries.push((name, contents));
+        }
+
+        let mut warnings = Vec::new();
+        let signature_status = match (ConfigRepository::load(&app_handle)?.bundle_signing_key.filter(|key| !key.is_empty()), signature) {
+            (None, _) => "unchecked",
+            (Some(_), None) => {
+                warnings.push("Bundle is not signed".to_string());
+                "unsigned"
+            }
+            (Some(key), Some(signature)) => {
+                if Self::verify(&key, &entries, &signature) {
+                    "valid"
+                } else {
+                    warnings.push("Bundle signature does not match: it was modified or signed with another key".to_string());
+                    "invalid"
+                }
+            }
+        };
+
+        let commission_json = entries.iter()
+            .find(|(name, _)| name == BUNDLE_COMMISSION_ENTRY)
+            .map(|(_, contents)| String::from_utf8_lossy(contents).to_string())
+            .ok_or_else(|| "Bundle has no commission.json".to_string())?;
+        let commission = CommissionRepository::parse_commission(&commission_json)?;
+
+        ValidationService::validate_id(&commission.id)?;
+        ValidationService::validate_name(&commission.client_name, "Client name")?;
+        for existing_status in FOLDER_STATUSES {
+            if CommissionRepository::find_by_id(&app_handle, &commission.id, existing_status).await?.is_some() {
+                return Err(format!("Commission {} already exists", commission.id));
+            }
+        }
+
+        // Images always live under pendings, whatever the commission's status
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let client_dir = data_dir.join("pendings").join(FileStorage::sanitize_filename(&commission.client_name));
+        let mut written = Vec::new();
+        let commission_id = commission.id.clone();
+        let imported = match Self::write_images(&client_dir, &entries, &mut written, &mut warnings) {
+            Ok(()) => CommissionService::create_commission(app_handle, commission).await,
+            Err(e) => Err(e),
+        };
+        match imported {
+            Ok(create_warnings) => warnings.extend(create_warnings),
+            Err(e) => {
+                // Nothing would refer to the images without the commission
+                for file in &written {
+                    if let Err(cleanup_error) = fs::remove_file(file) {
+                        eprintln!("Failed to remove {} after a failed import: {}", file.display(), cleanup_error);
+                    }
+                }
+                return Err(e);
+            }
+        }
+
+        Ok(BundleImport {
+            commission_id,
+            images_imported: written.len(),
+            signature: signature_status.to_string(),
+            warnings,
+        })
+    }
+
+    /// Writes the bundle's image entries under the client folder, recording each file written.
+    /// Existing files are kept, with a warning.
+    fn write_images(
+        client_dir: &Path,
+        entries: &[(String, Vec<u8>)],
+        written: &mut Vec<PathBuf>,
+        warnings: &mut Vec<String>,
+    ) -> Result<(), String> {
+        for (name, contents) in entries.iter().filter(|(name, _)| name != BUNDLE_COMMISSION_ENTRY) {
+            let target = client_dir.join(name);
+            if target.exists() {
+                warnings.push(format!("Kept the existing {}", name));
+                continue;
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create images directory: {}", e))?;
+            }
+            fs::write(&target, contents)
+                .map_err(|e| format!("Failed to write image {}: {}", name, e))?;
+            written.push(target);
+        }
+        Ok(())
+    }
+
+    // Entry names are included so contents can't be moved between entries unnoticed
+    fn mac(key: &str, entries: &[(String, Vec<u8>)]) -> Hmac<Sha256> {
+        let mut sorted: Vec<&(String, Vec<u8>)> = entries.iter().collect();
+        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        for (name, contents) in sorted {
+            mac.update(name.as_bytes());
+            mac.update(&[0]);
+            mac.update(&(contents.len() as u64).to_le_bytes());
+            mac.update(contents);
+        }
+        mac
+    }
+
+    fn sign(key: &str, entries: &[(String, Vec<u8>)]) -> String {
+        Self::mac(key, entries)
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    fn verify(key: &str, entries: &[(String, Vec<u8>)], signature: &str) -> bool {
+        let Some(expected) = Self::decode_hex(signature) else {
+            return false;
+        };
+        // Constant-time comparison
+        Self::mac(key, entries).verify_slice(&expected).is_ok()
+    }
+
+    fn decode_hex(value: &str) -> Option<Vec<u8>> {
+        if value.len() % 2 != 0 || !value.is_ascii() {
+            return None;
+        }
+        (0..value.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&value[index..index + 2], 16).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{commission, png, TestEnv};
+    use tauri::async_runtime::block_on;
+
+    fn write_bundle(path: &Path, entries: &[(String, Vec<u8>)]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, contents) in entries {
+            zip.start_file(name.as_str(), SimpleFileOptions::default()).unwrap();
+            zip.write_all(contents).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    fn read_bundle(path: &Path) -> Vec<(String, Vec<u8>)> {
+        let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+        (0..archive.len())
+            .map(|index| {
+                let mut entry = archive.by_index(index).unwrap();
+                let mut contents = Vec::new();
+                entry.read_to_end(&mut contents).unwrap();
+                (entry.name().to_string(), contents)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn signed_bundle_round_trips_and_tampering_is_detected() {
+        let env = TestEnv::new();
+        env.configure(|config| config.bundle_signing_key = Some("secret".to_string()));
+        let image = block_on(ImageService::save_commission_image(
+            env.app().clone(), "c1".to_string(), "Alice".to_string(), png(8, 8), "art.png".to_string(),
+        )).unwrap();
+        let mut work = commission("c1", "alice", "pending");
+        work.client_name = "Alice".to_string();
+        work.images = vec![image.relative_path.clone()];
+        block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        let dest = env.data_dir().join("exports");
+        fs::create_dir_all(&dest).unwrap();
+        let image_file = env.data_dir().join("pendings").join("Alice").join(&image.relative_path);
+        let remove_commission = || {
+            block_on(CommissionRepository::delete_by_id_and_status(env.app(), "c1", "pending")).unwrap();
+            fs::remove_file(&image_file).unwrap();
+        };
+
+        let bundle = block_on(BundleService::export_commission_bundle(
+            env.app().clone(), "c1".to_string(), "pending".to_string(), dest.to_string_lossy().to_string(),
+        )).unwrap();
+        remove_commission();
+        let imported = block_on(BundleService::import_commission_bundle(env.app().clone(), bundle.clone())).unwrap();
+        assert_eq!((imported.signature.as_str(), imported.images_imported), ("valid", 1));
+        assert!(imported.warnings.is_empty());
+        assert!(image_file.is_file());
+
+        let tampered: Vec<(String, Vec<u8>)> = read_bundle(Path::new(&bundle))
+            .into_iter()
+            .map(|(name, contents)| match name.as_str() {
+                BUNDLE_COMMISSION_ENTRY => (name, String::from_utf8(contents).unwrap().replace("Title c1", "Forged").into_bytes()),
+                _ => (name, contents),
+            })
+            .collect();
+        let tampered_bundle = dest.join("tampered.zip");
+        write_bundle(&tampered_bundle, &tampered);
+        remove_commission();
+        let imported = block_on(BundleService::import_commission_bundle(
+            env.app().clone(), tampered_bundle.to_string_lossy().to_string(),
+        )).unwrap();
+        assert_eq!(imported.signature, "invalid");
+        assert_eq!(imported.warnings.len(), 1);
+    }
+
+    #[test]
+    fn failed_import_removes_the_images_it_wrote() {
+        let env = TestEnv::new();
+        let mut invalid = commission("c1", "alice", "pending");
+        invalid.client_name = "Alice".to_string();
+        invalid.payment_status = "Bogus".to_string();
+        let bundle = env.data_dir().join("bundle.zip");
+        write_bundle(&bundle, &[
+            (BUNDLE_COMMISSION_ENTRY.to_string(), serde_json::to_vec(&invalid).unwrap()),
+            ("images/c1_art.png".to_string(), png(8, 8)),
+        ]);
+
+        assert!(block_on(BundleService::import_commission_bundle(env.app().clone(), bundle.to_string_lossy().to_string())).is_err());
+        assert!(!env.data_dir().join("pendings").join("Alice").join("images").join("c1_art.png").exists());
+    }
+}
@@ -1,46 +1,666 @@
-use tauri::AppHandle;
-use crate::repository::ClientRepository;
-use crate::repository::client_repository::Client;
-use super::validation_service::ValidationService;
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hasher};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime};
+use crate::repository::{AuditRepository, ClientRepository, CommissionRepository, FileStorage};
+use crate::repository::commission_repository::FOLDER_STATUSES;
+use crate::models::{Client, ClientInput, CommLogEntry};
+use super::backup_service::BackupService;
+use super::validation_service::{ValidationService, PHONE_CONTACT_KINDS};
+
+// Leaves room for the random suffix within the 64 character id limit
+const MAX_ID_SLUG_LENGTH: usize = 40;
+const MAX_ID_GENERATION_ATTEMPTS: usize = 16;
+// Hex characters kept from the id hash; plenty to spread clients over a color palette
+const AVATAR_SEED_LENGTH: usize = 8;
+// vCard lines longer than this many octets are folded onto continuation lines
+const VCARD_LINE_LIMIT: usize = 75;
 
 pub struct ClientService;
 
 impl ClientService {
-    pub async fn create_client(
-        app_handle: AppHandle,
-        client: Client,
+    /// Saves a client from the frontend, keeping what is stored for anything it doesn't send.
+    pub async fn save_client<R: Runtime>(app_handle: AppHandle<R>, input: ClientInput) -> Result<(), String> {
+        let existing = ClientRepository::find_by_id(&app_handle, &input.id).await?;
+        Self::create_client(app_handle, input.into_client(existing)).await
+    }
+
+    pub async fn create_client<R: Runtime>(
+        app_handle: AppHandle<R>,
+        mut client: Client,
     ) -> Result<(), String> {
         // Validate all client fields
         ValidationService::validate_id(&client.id)?;
         ValidationService::validate_name(&client.name, "Client name")?;
         ValidationService::validate_email(&client.email)?;
         ValidationService::validate_contact(&client.contact)?;
+        for contact in &mut client.contacts {
+            ValidationService::validate_contact_kind(&contact.kind)?;
+            contact.value = ValidationService::normalize_contact(&contact.kind, &contact.value)?;
+            ValidationService::validate_contact(&contact.value)?;
+        }
         
         // Additional timestamp validation
         if client.created_at.is_empty() || client.updated_at.is_empty() {
             return Err("Timestamps cannot be empty".to_string());
         }
         
-        ClientRepository::save(&app_handle, &client).await
+        ClientRepository::save(&app_handle, &client).await?;
+        AuditRepository::record(&app_handle, "save", "client", &client.id);
+        
+        Ok(())
+    }
+
+    /// The value a structured contact would be saved as, for previewing in the UI.
+    pub async fn normalize_contact(kind: String, value: String) -> Result<String, String> {
+        ValidationService::validate_contact_kind(&kind)?;
+        ValidationService::normalize_contact(&kind, &value)
+    }
+
+    /// Like save_client, but mints an id from the name when the client has none.
+    /// Returns the id the client was saved under.
+    pub async fn create_client_auto_id<R: Runtime>(
+        app_handle: AppHandle<R>,
+        mut client: ClientInput,
+    ) -> Result<String, String> {
+        if client.id.is_empty() {
+            ValidationService::validate_name(&client.name, "Client name")?;
+            client.id = Self::generate_client_id(&app_handle, &client.name).await?;
+        }
+        
+        let client_id = client.id.clone();
+        Self::save_client(app_handle, client).await?;
+        
+        Ok(client_id)
+    }
+
+    async fn generate_client_id<R: Runtime>(app_handle: &AppHandle<R>, name: &str) -> Result<String, String> {
+        // Lowercase ASCII slug; everything else collapses into single underscores
+        let mut slug = String::new();
+        for c in name.chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('_') {
+                slug.push('_');
+            }
+        }
+        slug.truncate(MAX_ID_SLUG_LENGTH);
+        let slug = slug.trim_end_matches('_');
+        let slug = if slug.is_empty() { "client" } else { slug };
+        
+        for _ in 0..MAX_ID_GENERATION_ATTEMPTS {
+            // RandomState is randomly seeded, which is enough for a collision-avoiding suffix
+            let suffix = RandomState::new().build_hasher().finish() as u32;
+            let candidate = format!("{}_{:08x}", slug, suffix);
+            
+            ValidationService::validate_id(&candidate)?;
+            if !ClientRepository::exists(app_handle, &candidate).await? {
+                return Ok(candidate);
+            }
+        }
+        
+        Err("Failed to generate a unique client id".to_string())
     }
 
-    pub async fn get_client_by_id(
-        app_handle: AppHandle,
+    pub async fn get_client_by_id<R: Runtime>(
+        app_handle: AppHandle<R>,
         client_id: String,
     ) -> Result<Option<Client>, String> {
         ValidationService::validate_id(&client_id)?;
         ClientRepository::find_by_id(&app_handle, &client_id).await
     }
 
-    pub async fn get_all_clients(app_handle: AppHandle) -> Result<Vec<Client>, String> {
+    /// A short hash of the client id, so the UI can give each client the same avatar color
+    /// every time. Saved on the client record the first time it is computed.
+    pub async fn get_client_avatar_seed<R: Runtime>(app_handle: AppHandle<R>, client_id: String) -> Result<String, String> {
+        ValidationService::validate_id(&client_id)?;
+        
+        let mut client = ClientRepository::find_by_id(&app_handle, &client_id).await?
+            .ok_or_else(|| format!("Client {} not found", client_id))?;
+        if let Some(seed) = &client.avatar_seed {
+            return Ok(seed.clone());
+        }
+        
+        let seed: String = Sha256::digest(client.id.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()[..AVATAR_SEED_LENGTH]
+            .to_string();
+        
+        client.avatar_seed = Some(seed.clone());
+        ClientRepository::save(&app_handle, &client).await?;
+        
+        Ok(seed)
+    }
+
+    /// Appends a dated note about contact with the client, e.g. "emailed for refs".
+    pub async fn add_client_communication<R: Runtime>(
+        app_handle: AppHandle<R>,
+        client_id: String,
+        channel: String,
+        note: String,
+    ) -> Result<CommLogEntry, String> {
+        ValidationService::validate_id(&client_id)?;
+        let channel = channel.trim().to_lowercase();
+        ValidationService::validate_contact_kind(&channel)?;
+        ValidationService::validate_communication_note(&note)?;
+        
+        let mut client = ClientRepository::find_by_id(&app_handle, &client_id).await?
+            .ok_or_else(|| format!("Client {} not found", client_id))?;
+        
+        let now = chrono::Utc::now().to_rfc3339();
+        let entry = CommLogEntry {
+            timestamp: now.clone(),
+            channel,
+            note: note.trim().to_string(),
+        };
+        client.communications.push(entry.clone());
+        client.updated_at = now;
+        
+        ClientRepository::save(&app_handle, &client).await?;
+        AuditRepository::record(&app_handle, "save", "client", &client_id);
+        
+        Ok(entry)
+    }
+
+    pub async fn get_client_communications<R: Runtime>(app_handle: AppHandle<R>, client_id: String) -> Result<Vec<CommLogEntry>, String> {
+        ValidationService::validate_id(&client_id)?;
+        
+        let client = ClientRepository::find_by_id(&app_handle, &client_id).await?
+            .ok_or_else(|| format!("Client {} not found", client_id))?;
+        
+        Ok(client.communications)
+    }
+
+    pub async fn get_all_clients<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<Client>, String> {
         ClientRepository::find_all(&app_handle).await
     }
 
-    pub async fn delete_client(
-        app_handle: AppHandle,
+    /// Case-insensitive match against name, email and contact fields.
+    pub async fn search_clients<R: Runtime>(app_handle: AppHandle<R>, query: String) -> Result<Vec<Client>, String> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        let clients = ClientRepository::find_all(&app_handle).await?;
+        
+        Ok(clients.into_iter()
+            .filter(|client| {
+                client.name.to_lowercase().contains(&query)
+                    || client.email.to_lowercase().contains(&query)
+                    || client.contact.to_lowercase().contains(&query)
+                    || client.contacts.iter().any(|c| c.value.to_lowercase().contains(&query))
+            })
+            .collect())
+    }
+
+    /// Groups clients that share an email (trimmed, case-insensitive). Clients without an email
+    /// are never grouped.
+    pub async fn find_duplicate_clients<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<Vec<Client>>, String> {
+        let clients = ClientRepository::find_all(&app_handle).await?;
+        
+        let mut by_email: HashMap<String, Vec<Client>> = HashMap::new();
+        for client in clients {
+            let email = client.email.trim().to_lowercase();
+            if !email.is_empty() {
+                by_email.entry(email).or_default().push(client);
+            }
+        }
+        
+        let mut groups: Vec<(String, Vec<Client>)> = by_email.into_iter()
+            .filter(|(_, group)| group.len() > 1)
+            .collect();
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
+        
+        Ok(groups.into_iter()
+            .map(|(_, mut group)| {
+                group.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+                group
+            })
+            .collect())
+    }
+
+    pub async fn is_client_id_available<R: Runtime>(
+        app_handle: AppHandle<R>,
+        client_id: String,
+    ) -> Result<bool, String> {
+        ValidationService::validate_id(&client_id)?;
+        let exists = ClientRepository::exists(&app_handle, &client_id).await?;
+        Ok(!exists)
+    }
+
+    pub async fn get_contact_type_breakdown<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<(String, usize)>, String> {
+        let clients = ClientRepository::find_all(&app_handle).await?;
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        
+        for client in &clients {
+            // Each client counts once per distinct kind, however many contacts of that kind it has
+            let mut kinds: HashSet<&str> = client.contacts.iter().map(|c| c.kind.as_str()).collect();
+            if !client.email.is_empty() {
+                kinds.insert("email");
+            }
+            
+            for kind in kinds {
+                *counts.entry(kind.to_string()).or_insert(0) += 1;
+            }
+        }
+        
+        let mut breakdown: Vec<(String, usize)> = counts.into_iter().collect();
+        breakdown.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        
+        Ok(breakdown)
+    }
+
+    /// Clients with at least min_count commissions in any status, cancelled included, most
+    /// commissions first. Commissions of clients that no longer exist are not counted.
+    pub async fn get_repeat_clients<R: Runtime>(app_handle: AppHandle<R>, min_count: usize) -> Result<Vec<(Client, usize)>, String> {
+        // One scan of each folder instead of one per client
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for status in FOLDER_STATUSES {
+            for commission in CommissionRepository::find_by_status(&app_handle, status).await? {
+                *counts.entry(commission.client_id).or_insert(0) += 1;
+            }
+        }
+        
+        let mut repeat_clients: Vec<(Client, usize)> = ClientRepository::find_all(&app_handle).await?
+            .into_iter()
+            .filter_map(|client| {
+                let count = counts.get(&client.id).copied().unwrap_or(0);
+                (count >= min_count).then_some((client, count))
+            })
+            .collect();
+        repeat_clients.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.name.cmp(&b.0.name)));
+        
+        Ok(repeat_clients)
+    }
+
+    pub async fn delete_client<R: Runtime>(
+        app_handle: AppHandle<R>,
         client_id: String,
     ) -> Result<(), String> {
         ValidationService::validate_id(&client_id)?;
-        ClientRepository::delete(&app_handle, &client_id).await
+        ClientRepository::delete(&app_handle, &client_id).await?;
+        AuditRepository::record(&app_handle, "delete", "client", &client_id);
+        
+        Ok(())
+    }
+
+    /// Bundles everything stored for a client into a zip in the destination folder, then
+    /// deletes all of it. Nothing is deleted unless the bundle was written and read back
+    /// intact. Returns the bundle path.
+    pub async fn export_and_delete_client<R: Runtime>(
+        app_handle: AppHandle<R>,
+        client_id: String,
+        dest: String,
+    ) -> Result<String, String> {
+        ValidationService::validate_id(&client_id)?;
+        
+        let dest_dir = PathBuf::from(&dest);
+        if !dest_dir.is_dir() {
+            return Err("Bundle destination must be an existing folder".to_string());
+        }
+        if !ClientRepository::exists(&app_handle, &client_id).await? {
+            return Err(format!("Client {} not found", client_id));
+        }
+        
+        let bundle_file = BackupService::create_client_bundle(&app_handle, &client_id, &dest_dir).await?;
+        
+        let mut commission_ids = HashSet::new();
+        for status in FOLDER_STATUSES {
+            commission_ids.extend(CommissionRepository::find_by_status(&app_handle, status).await?
+                .into_iter()
+                .filter(|commission| commission.client_id == client_id)
+                .map(|commission| commission.id));
+        }
+        
+        // Same file list the bundle was built from, so only what was exported gets removed
+        let files = BackupService::client_files(&app_handle, &client_id).await?;
+        for file in &files {
+            FileStorage::delete_file(file)?;
+        }
+        // The audit log would otherwise still hold copies of the erased records
+        AuditRepository::remove_snapshots(&app_handle, "commission", &commission_ids)?;
+        
+        // Remove the folders that are now empty, stopping at the top-level data folders
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        for file in &files {
+            let mut dir = file.parent();
+            while let Some(current) = dir.filter(|d| d.starts_with(&data_dir) && d.parent() != Some(data_dir.as_path())) {
+                if fs::remove_dir(current).is_err() {
+                    break;
+                }
+                dir = current.parent();
+            }
+        }
+        
+        println!("Exported and deleted client {} ({} files) to {}", client_id, files.len(), bundle_file.display());
+        
+        Ok(bundle_file.to_string_lossy().to_string())
+    }
+
+    /// Writes every client as a vCard 3.0 into one .vcf file in the destination folder and
+    /// returns the file path. Clients without contact details still get a card with their name.
+    pub async fn export_clients_vcard<R: Runtime>(app_handle: AppHandle<R>, dest: String) -> Result<String, String> {
+        let dest_dir = PathBuf::from(&dest);
+        if !dest_dir.is_dir() {
+            return Err("vCard destination must be an existing folder".to_string());
+        }
+        
+        let mut clients = ClientRepository::find_all(&app_handle).await?;
+        clients.sort_by_key(|client| client.name.to_lowercase());
+        
+        let vcf: String = clients.iter().map(Self::vcard).collect();
+        
+        let vcf_file = dest_dir.join(format!("clients_{}.vcf", chrono::Utc::now().format("%Y-%m-%d")));
+        fs::write(&vcf_file, vcf)
+            .map_err(|e| format!("Failed to write vCard file: {}", e))?;
+        
+        println!("Exported {} clients to {}", clients.len(), vcf_file.display());
+        
+        Ok(vcf_file.to_string_lossy().to_string())
+    }
+
+    fn vcard(client: &Client) -> String {
+        let mut emails: Vec<&str> = Vec::new();
+        let mut phones: Vec<String> = Vec::new();
+        let mut lines = vec![
+            "BEGIN:VCARD".to_string(),
+            "VERSION:3.0".to_string(),
+            format!("UID:{}", Self::vcard_escape(&client.id)),
+            format!("FN:{}", Self::vcard_escape(&client.name)),
+            format!("N:;{};;;", Self::vcard_escape(&client.name)),
+        ];
+        
+        if !client.email.is_empty() {
+            emails.push(&client.email);
+        }
+        // The free-text contact field is only a phone number if it reads as one
+        if let Ok(phone) = ValidationService::normalize_contact("phone", &client.contact) {
+            phones.push(phone);
+        } else if !client.contact.trim().is_empty() {
+            lines.push(format!("NOTE:{}", Self::vcard_escape(client.contact.trim())));
+        }
+        
+        for contact in &client.contacts {
+            if contact.kind == "email" {
+                if !emails.contains(&contact.value.as_str()) {
+                    emails.push(&contact.value);
+                }
+            } else if PHONE_CONTACT_KINDS.contains(&contact.kind.as_str()) {
+                if !phones.contains(&contact.value) {
+                    phones.push(contact.value.clone());
+                }
+            } else {
+                // Kinds are validated as lowercase letters, digits, - and _, so safe as a parameter
+                lines.push(format!("X-SOCIALPROFILE;TYPE={}:{}", contact.kind, Self::vcard_escape(&contact.value)));
+            }
+        }
+        
+        lines.extend(emails.iter().map(|email| format!("EMAIL;TYPE=INTERNET:{}", Self::vcard_escape(email))));
+        lines.extend(phones.iter().map(|phone| format!("TEL;TYPE=CELL:{}", Self::vcard_escape(phone))));
+        lines.push("END:VCARD".to_string());
+        
+        lines.iter()
+            .map(|line| format!("{}\r\n", Self::vcard_fold(line)))
+            .collect()
+    }
+
+    // Backslash first, so the escapes added after it aren't escaped again
+    fn vcard_escape(value: &str) -> String {
+        value.replace('\\', "\\\\")
+            .replace(',', "\\,")
+            .replace(';', "\\;")
+            .replace("\r\n", "\\n")
+            .replace(['\r', '\n'], "\\n")
+    }
+
+    // Continuation lines start with a space; splits fall on character boundaries
+    fn vcard_fold(line: &str) -> String {
+        let mut folded = String::new();
+        let mut line_octets = 0;
+        for c in line.chars() {
+            if line_octets + c.len_utf8() > VCARD_LINE_LIMIT {
+                folded.push_str("\r\n ");
+                line_octets = 1;
+            }
+            folded.push(c);
+            line_octets += c.len_utf8();
+        }
+        folded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::client::ClientContact;
+    use crate::services::{CommissionService, ImageService};
+    use crate::test_support::{client, commission, png, TestEnv};
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn deleting_a_nonexistent_client_reports_not_found() {
+        let env = TestEnv::new();
+        let err = block_on(ClientService::delete_client(env.app().clone(), "ghost".to_string())).unwrap_err();
+        assert!(err.contains("not found"), "{}", err);
+    }
+
+    #[test]
+    fn client_id_is_available_until_taken() {
+        let env = TestEnv::new();
+        assert!(block_on(ClientService::is_client_id_available(env.app().clone(), "alice".to_string())).unwrap());
+        
+        block_on(ClientRepository::save(env.app(), &client("alice", "Alice"))).unwrap();
+        assert!(!block_on(ClientService::is_client_id_available(env.app().clone(), "alice".to_string())).unwrap());
+    }
+
+    #[test]
+    fn contact_breakdown_counts_each_kind_once_per_client() {
+        let env = TestEnv::new();
+        let contact = |kind: &str, value: &str| ClientContact { kind: kind.to_string(), value: value.to_string() };
+        let mut alice = client("alice", "Alice");
+        alice.contacts = vec![contact("discord", "alice#1"), contact("discord", "alice#2"), contact("phone", "+15550100")];
+        let mut bob = client("bob", "Bob");
+        bob.contacts = vec![contact("discord", "bob#1")];
+        block_on(ClientRepository::save(env.app(), &alice)).unwrap();
+        block_on(ClientRepository::save(env.app(), &bob)).unwrap();
+        
+        let breakdown = block_on(ClientService::get_contact_type_breakdown(env.app().clone())).unwrap();
+        assert_eq!(breakdown, vec![("discord".to_string(), 2), ("phone".to_string(), 1)]);
+    }
+
+    fn frontend_save(id: &str, name: &str, extra: serde_json::Value) -> ClientInput {
+        let mut input = serde_json::to_value(client(id, name)).unwrap();
+        let object = input.as_object_mut().unwrap();
+        for key in ["contacts", "avatar_seed", "communications"] {
+            object.remove(key);
+        }
+        object.extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(input).unwrap()
+    }
+
+    #[test]
+    fn resaving_from_the_frontend_keeps_contacts_and_avatar_seed() {
+        let env = TestEnv::new();
+        let mut stored = client("alice", "Alice");
+        stored.contacts = vec![ClientContact { kind: "discord".to_string(), value: "alice#1".to_string() }];
+        block_on(ClientService::create_client(env.app().clone(), stored)).unwrap();
+        let seed = block_on(ClientService::get_client_avatar_seed(env.app().clone(), "alice".to_string())).unwrap();
+        
+        block_on(ClientService::save_client(env.app().clone(), frontend_save("alice", "Alice B", serde_json::json!({})))).unwrap();
+        
+        let saved = block_on(ClientRepository::find_by_id(env.app(), "alice")).unwrap().unwrap();
+        assert_eq!(saved.name, "Alice B");
+        assert_eq!(saved.contacts.len(), 1);
+        assert_eq!(saved.avatar_seed, Some(seed.clone()));
+        
+        // Sending an empty list removes every contact
+        let cleared = frontend_save("alice", "Alice B", serde_json::json!({ "contacts": [] }));
+        block_on(ClientService::save_client(env.app().clone(), cleared)).unwrap();
+        let saved = block_on(ClientRepository::find_by_id(env.app(), "alice")).unwrap().unwrap();
+        assert!(saved.contacts.is_empty());
+        assert_eq!(saved.avatar_seed, Some(seed));
+    }
+
+    #[test]
+    fn duplicate_clients_are_grouped_by_normalized_email() {
+        let env = TestEnv::new();
+        let mut first = client("alice", "Alice");
+        first.email = "alice@example.com".to_string();
+        let mut second = client("alice2", "Alice (old)");
+        second.email = " Alice@Example.com ".to_string();
+        let mut unique = client("bob", "Bob");
+        unique.email = "bob@example.com".to_string();
+        for c in [&first, &second, &unique] {
+            block_on(ClientRepository::save(env.app(), c)).unwrap();
+        }
+        
+        let groups = block_on(ClientService::find_duplicate_clients(env.app().clone())).unwrap();
+        assert_eq!(groups.len(), 1);
+        let mut ids: Vec<&str> = groups[0].iter().map(|c| c.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["alice", "alice2"]);
+    }
+
+    #[test]
+    fn empty_id_gets_a_valid_unique_id() {
+        let env = TestEnv::new();
+        let first = block_on(ClientService::create_client_auto_id(env.app().clone(), frontend_save("", "Alice Smith", serde_json::json!({})))).unwrap();
+        let second = block_on(ClientService::create_client_auto_id(env.app().clone(), frontend_save("", "Alice Smith", serde_json::json!({})))).unwrap();
+        
+        ValidationService::validate_id(&first).unwrap();
+        ValidationService::validate_id(&second).unwrap();
+        assert_ne!(first, second);
+        assert!(block_on(ClientRepository::exists(env.app(), &first)).unwrap());
+        
+        let kept = block_on(ClientService::create_client_auto_id(env.app().clone(), frontend_save("given", "Bob", serde_json::json!({})))).unwrap();
+        assert_eq!(kept, "given");
+    }
+
+    #[test]
+    fn export_and_delete_erases_only_after_writing_the_bundle() {
+        let env = TestEnv::new();
+        block_on(ClientRepository::save(env.app(), &client("alice", "Alice"))).unwrap();
+        let saved = block_on(ImageService::save_commission_image(
+            env.app().clone(), "c1".to_string(), "Alice".to_string(), png(8, 8), "art.png".to_string(),
+        )).unwrap();
+        let mut work = commission("c1", "alice", "pending");
+        work.client_name = "Alice".to_string();
+        work.images = vec![saved.relative_path];
+        block_on(CommissionService::create_commission(env.app().clone(), work)).unwrap();
+        let dest = env.data_dir().join("exports");
+        fs::create_dir_all(&dest).unwrap();
+        
+        // Without a bundle nothing is deleted
+        let missing_dest = env.data_dir().join("missing").to_string_lossy().to_string();
+        assert!(block_on(ClientService::export_and_delete_client(env.app().clone(), "alice".to_string(), missing_dest)).is_err());
+        assert!(block_on(ClientRepository::exists(env.app(), "alice")).unwrap());
+        
+        let bundle = block_on(ClientService::export_and_delete_client(
+            env.app().clone(), "alice".to_string(), dest.to_string_lossy().to_string(),
+        )).unwrap();
+        
+        let info = block_on(BackupService::verify_backup(env.app().clone(), bundle)).unwrap();
+        assert!(info.file_count >= 3);
+        assert!(!block_on(ClientRepository::exists(env.app(), "alice")).unwrap());
+        assert!(!env.data_dir().join("pendings").join("Alice").exists());
+        let audit = AuditRepository::read_lines(env.app()).unwrap();
+        assert!(!audit.is_empty());
+        assert!(audit.iter().all(|line| !line.contains("snapshot")));
+    }
+
+    #[test]
+    fn avatar_seed_is_stable_and_stored() {
+        let env = TestEnv::new();
+        block_on(ClientRepository::save(env.app(), &client("alice", "Alice"))).unwrap();
+        
+        let first = block_on(ClientService::get_client_avatar_seed(env.app().clone(), "alice".to_string())).unwrap();
+        let second = block_on(ClientService::get_client_avatar_seed(env.app().clone(), "alice".to_string())).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.len(), AVATAR_SEED_LENGTH);
+        
+        let stored = block_on(ClientRepository::find_by_id(env.app(), "alice")).unwrap().unwrap();
+        assert_eq!(stored.avatar_seed, Some(first));
+    }
+
+    #[test]
+    fn communications_are_read_back_in_the_order_added() {
+        let env = TestEnv::new();
+        block_on(ClientRepository::save(env.app(), &client("alice", "Alice"))).unwrap();
+        let add = |channel: &str, note: &str| {
+            block_on(ClientService::add_client_communication(env.app().clone(), "alice".to_string(), channel.to_string(), note.to_string()))
+        };
+        
+        add("email", "emailed for refs").unwrap();
+        add("Discord", "sent sketch").unwrap();
+        assert!(add("carrier pigeon", "sent a bird").is_err());
+        
+        let log = block_on(ClientService::get_client_communications(env.app().clone(), "alice".to_string())).unwrap();
+        let entries: Vec<(&str, &str)> = log.iter().map(|entry| (entry.channel.as_str(), entry.note.as_str())).collect();
+        assert_eq!(entries, vec![("email", "emailed for refs"), ("discord", "sent sketch")]);
+    }
+
+    #[test]
+    fn vcard_export_parses_back_to_one_card_per_client() {
+        let env = TestEnv::new();
+        let mut alice = client("alice", "Smith, Alice; Jr.");
+        alice.email = "alice@example.com".to_string();
+        alice.contact = "+1 555 0100".to_string();
+        block_on(ClientRepository::save(env.app(), &alice)).unwrap();
+        block_on(ClientRepository::save(env.app(), &client("bob", &"Bob ".repeat(30)))).unwrap();
+        
+        let path = block_on(ClientService::export_clients_vcard(env.app().clone(), env.data_dir().to_string_lossy().to_string())).unwrap();
+        let vcf = fs::read_to_string(path).unwrap();
+        
+        // Unfold continuation lines, then collect each card's properties
+        let unfolded = vcf.replace("\r\n ", "");
+        let mut cards: Vec<Vec<(String, String)>> = Vec::new();
+        for line in unfolded.split("\r\n").filter(|line| !line.is_empty()) {
+            let (name, value) = line.split_once(':').expect("property line");
+            match name {
+                "BEGIN" => cards.push(Vec::new()),
+                "END" => {}
+                _ => cards.last_mut().expect("inside a card").push((name.to_string(), value.to_string())),
+            }
+        }
+        let unescape = |value: &str| value.replace("\\,", ",").replace("\\;", ";").replace("\\\\", "\\");
+        let names: Vec<String> = cards.iter()
+            .map(|card| card.iter().find(|(name, _)| name == "FN").map(|(_, value)| unescape(value)).expect("FN"))
+            .collect();
+        
+        assert_eq!(names, vec!["Bob ".repeat(30), "Smith, Alice; Jr.".to_string()]);
+        assert!(vcf.lines().all(|line| line.len() <= VCARD_LINE_LIMIT + 1));
+        assert!(cards[1].iter().any(|(name, value)| name == "EMAIL;TYPE=INTERNET" && value == "alice@example.com"));
+        assert!(cards[1].iter().any(|(name, _)| name == "TEL;TYPE=CELL"));
+        assert!(cards[0].iter().all(|(name, _)| !name.starts_with("EMAIL") && !name.starts_with("TEL")));
+    }
+
+    #[test]
+    fn repeat_clients_need_min_count_commissions() {
+        let env = TestEnv::new();
+        for c in [client("alice", "Alice"), client("bob", "Bob"), client("carol", "Carol")] {
+            block_on(ClientRepository::save(env.app(), &c)).unwrap();
+        }
+        for work in [
+            commission("a1", "alice", "pending"),
+            commission("a2", "alice", "completed"),
+            commission("a3", "alice", "completed"),
+            commission("b1", "bob", "completed"),
+            commission("ghost1", "ghost", "pending"),
+            commission("ghost2", "ghost", "completed"),
+        ] {
+            block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        }
+        
+        let repeat = block_on(ClientService::get_repeat_clients(env.app().clone(), 2)).unwrap();
+        let counts: Vec<(&str, usize)> = repeat.iter().map(|(c, count)| (c.id.as_str(), *count)).collect();
+        assert_eq!(counts, vec![("alice", 3)]);
+        
+        let everyone = block_on(ClientService::get_repeat_clients(env.app().clone(), 0)).unwrap();
+        let counts: Vec<(&str, usize)> = everyone.iter().map(|(c, count)| (c.id.as_str(), *count)).collect();
+        assert_eq!(counts, vec![("alice", 3), ("bob", 1), ("carol", 0)]);
     }
 }
@@ -1,29 +1,177 @@
-use tauri::AppHandle;
-use crate::repository::CommissionRepository;
-use crate::repository::commission_repository::Commission;
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tauri::{AppHandle, Runtime};
+use crate::repository::{AuditRepository, ClientRepository, CommissionRepository, ConfigRepository, FileStorage};
+use crate::models::{Commission, CommissionEvent, CommissionInput};
+use crate::repository::audit_repository::AuditEntry;
+use crate::repository::commission_repository::{ScanCache, FOLDER_STATUSES};
+use super::image_service::ImageService;
 use super::validation_service::ValidationService;
 
+// Completed and cancelled work shown on the board; older commissions still count in the totals
+const BOARD_COMPLETED_LIMIT: usize = 20;
+
+// Assumed days per commission when there is no completed history to learn from
+const DEFAULT_TURNAROUND_DAYS: f64 = 7.0;
+
+// Not a valid tag, so it can't collide with a real one
+const UNTAGGED_BUCKET: &str = "(untagged)";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommissionBreakdown {
+    pub base_cents: i64,
+    pub tax_cents: i64,
+    pub fee_cents: i64,
+    pub total_cents: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnnotatedCommission {
+    #[serde(flatten)]
+    pub commission: Commission,
+    pub client_exists: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkResult {
+    pub updated: Vec<String>,
+    pub unchanged: Vec<String>, // Found, but nothing needed doing
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PriceStats {
+    pub count: usize, // 0 when nothing matched; the other fields are then 0 too
+    pub min_cents: i64,
+    pub max_cents: i64,
+    pub mean_cents: i64,
+    pub median_cents: i64, // Even counts average the two middle prices
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RefundPolicy {
+    pub kind: String, // "full_refund", "keep_deposit", "percentage_kept"
+    #[serde(default)]
+    pub kept_percent: u8, // For "percentage_kept": share of the price kept as a cancellation fee
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardColumn {
+    pub commissions: Vec<Commission>,
+    pub count: usize, // Whole column, including completed work past the board limit
+    pub total_cents: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Board {
+    pub pending: BoardColumn,
+    pub in_progress: BoardColumn,
+    pub completed: BoardColumn,
+    pub cancelled: BoardColumn,
+}
+
 pub struct CommissionService;
 
 impl CommissionService {
-    pub async fn create_commission(
-        app_handle: AppHandle,
+    /// Saves a commission from the frontend, keeping the stored value of every field it left out.
+    pub async fn save_commission<R: Runtime>(
+        app_handle: AppHandle<R>,
+        input: CommissionInput,
+    ) -> Result<Vec<String>, String> {
+        let existing = Self::find_stored(&app_handle, &input.id).await?;
+        let commission = input.into_commission(existing.as_ref());
+        Self::store_commission(app_handle, commission, existing).await
+    }
+
+    /// Validates and saves a commission as given. Returns warnings about things that were saved anyway.
+    pub async fn create_commission<R: Runtime>(
+        app_handle: AppHandle<R>,
         commission: Commission,
-    ) -> Result<(), String> {
+    ) -> Result<Vec<String>, String> {
+        let existing = Self::find_stored(&app_handle, &commission.id).await?;
+        Self::store_commission(app_handle, commission, existing).await
+    }
+
+    async fn store_commission<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission: Commission,
+        existing: Option<Commission>,
+    ) -> Result<Vec<String>, String> {
         println!("=== COMMISSION_SERVICE::CREATE START ===");
         println!("Commission ID: {}", commission.id);
         println!("Commission Title: {}", commission.title);
         println!("Commission Images: {:?}", commission.images);
         
+        Self::validate_fields(&commission)?;
+        
+        // Validate image paths - filter out empty paths first and handle data URLs
+        let valid_images: Vec<String> = commission.images.iter()
+            .filter(|path| !path.is_empty())
+            .cloned()
+            .collect();
+        
+        println!("Validating commission with {} non-empty images: {:?}", valid_images.len(), valid_images);
+        
+        for image_path in &valid_images {
+            ValidationService::validate_image_path(image_path)?;
+        }
+        
+        println!("All image paths validated successfully");
+        
+        // Create a new commission with filtered images
+        let mut validated_commission = commission;
+        validated_commission.images = valid_images;
+        
+        // A cover whose image was removed would point at nothing
+        if validated_commission.cover_image.as_ref().is_some_and(|cover| !validated_commission.images.contains(cover)) {
+            validated_commission.cover_image = None;
+        }
+        
+        let mut warnings = Vec::new();
+        if ConfigRepository::load(&app_handle)?.storage.warn_duplicate_titles {
+            if let Some(duplicate) = Self::find_duplicate_title(&app_handle, &validated_commission).await? {
+                warnings.push(format!(
+                    "{} already has a {} commission titled \"{}\" ({})",
+                    validated_commission.client_name, validated_commission.status, duplicate.title, duplicate.id
+                ));
+            }
+        }
+        
+        Self::record_save_events(&app_handle, &mut validated_commission, existing)?;
+        
+        CommissionRepository::save(&app_handle, &validated_commission).await?;
+        AuditRepository::record_snapshot(&app_handle, "save", "commission", &validated_commission.id, &validated_commission);
+        
+        println!("=== COMMISSION_SERVICE::CREATE SUCCESS ===");
+        Ok(warnings)
+    }
+
+    /// Checks everything on a commission except its image paths.
+    fn validate_fields(commission: &Commission) -> Result<(), String> {
         // Validate all commission fields
-        ValidationService::validate_id(&commission.id)?;
+        ValidationService::validate_commission_id(&commission.id)?;
         ValidationService::validate_id(&commission.client_id)?;
         ValidationService::validate_name(&commission.client_name, "Client name")?;
         ValidationService::validate_name(&commission.title, "Commission title")?;
         ValidationService::validate_description(&commission.description)?;
         ValidationService::validate_price_cents(commission.price_cents)?;
+        ValidationService::validate_paid_cents(commission.paid_cents, commission.price_cents)?;
+        ValidationService::validate_deposit_cents(commission.deposit_cents, commission.price_cents)?;
+        ValidationService::validate_tax_and_fee(commission.tax_cents, commission.fee_cents, commission.price_cents)?;
+        ValidationService::validate_currency(&commission.currency)?;
+        for tag in &commission.tags {
+            ValidationService::validate_tag(tag)?;
+        }
         ValidationService::validate_payment_status(&commission.payment_status)?;
         ValidationService::validate_status(&commission.status)?;
+        ValidationService::validate_priority(&commission.priority)?;
+        if let Some(due_date) = &commission.due_date {
+            ValidationService::validate_due_date(due_date)?;
+        }
+        if let Some(hours) = commission.estimated_hours {
+            ValidationService::validate_estimated_hours(hours)?;
+        }
         
         println!("Basic field validation passed");
         
@@ -35,40 +183,136 @@ impl CommissionService {
         
         println!("Timestamp validation passed");
         
-        // Validate image paths - filter out empty paths first and handle data URLs
-        let valid_images: Vec<String> = commission.images.iter()
-            .filter(|path| !path.is_empty())
-            .cloned()
-            .collect();
+        Ok(())
+    }
+
+    /// Another commission of the same client and status whose title matches, ignoring case
+    /// and surrounding whitespace.
+    async fn find_duplicate_title<R: Runtime>(app_handle: &AppHandle<R>, commission: &Commission) -> Result<Option<Commission>, String> {
+        let title = commission.title.trim().to_lowercase();
+        let siblings = CommissionRepository::find_by_status(app_handle, &commission.status).await?;
         
-        println!("Validating commission with {} non-empty images: {:?}", valid_images.len(), valid_images);
+        Ok(siblings.into_iter().find(|c| {
+            c.id != commission.id
+                && c.client_id == commission.client_id
+                && c.status == commission.status
+                && c.title.trim().to_lowercase() == title
+        }))
+    }
+
+    /// Archived commissions are left out unless include_archived is set.
+    pub async fn get_commissions_by_status<R: Runtime>(
+        app_handle: AppHandle<R>,
+        status: String,
+        include_archived: bool,
+    ) -> Result<Vec<Commission>, String> {
+        ValidationService::validate_status(&status)?;
+        let commissions = CommissionRepository::find_by_status(&app_handle, &status).await?;
         
-        for image_path in &valid_images {
-            ValidationService::validate_image_path(image_path)?;
-        }
+        Ok(commissions.into_iter()
+            .filter(|commission| include_archived || !commission.archived)
+            .collect())
+    }
+
+    /// Same as get_commissions_by_status, High priority first, then oldest first within a priority.
+    pub async fn get_commissions_prioritized<R: Runtime>(
+        app_handle: AppHandle<R>,
+        status: String,
+    ) -> Result<Vec<Commission>, String> {
+        let mut commissions = Self::get_commissions_by_status(app_handle, status, false).await?;
         
-        println!("All image paths validated successfully");
+        // Unknown priorities, e.g. from hand-edited files, rank like Normal
+        let rank = |priority: &str| match priority {
+            "High" => 0,
+            "Low" => 2,
+            _ => 1,
+        };
+        commissions.sort_by(|a, b| {
+            rank(&a.priority).cmp(&rank(&b.priority))
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
         
-        // Create a new commission with filtered images
-        let mut validated_commission = commission;
-        validated_commission.images = valid_images;
+        Ok(commissions)
+    }
+
+    /// All three board columns from one read of the data folder, archived work left out.
+    /// Open columns are oldest first; completed and cancelled show the most recent first.
+    pub async fn get_board<R: Runtime>(app_handle: AppHandle<R>) -> Result<Board, String> {
+        // pendings holds both open columns
+        let (mut pending, mut in_progress): (Vec<Commission>, Vec<Commission>) =
+            Self::get_commissions_by_status(app_handle.clone(), "pending".to_string(), false).await?
+                .into_iter()
+                .filter(|c| c.status == "pending" || c.status == "in-progress")
+                .partition(|c| c.status == "pending");
+        let mut completed = Self::get_commissions_by_status(app_handle.clone(), "completed".to_string(), false).await?;
+        let mut cancelled = Self::get_commissions_by_status(app_handle, "cancelled".to_string(), false).await?;
         
-        CommissionRepository::save(&app_handle, &validated_commission).await?;
+        pending.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        in_progress.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        // updated_at doubles as the completion or cancellation time
+        completed.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        cancelled.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
         
-        println!("=== COMMISSION_SERVICE::CREATE SUCCESS ===");
-        Ok(())
+        Ok(Board {
+            pending: Self::board_column(pending, usize::MAX),
+            in_progress: Self::board_column(in_progress, usize::MAX),
+            completed: Self::board_column(completed, BOARD_COMPLETED_LIMIT),
+            cancelled: Self::board_column(cancelled, BOARD_COMPLETED_LIMIT),
+        })
+    }
+
+    fn board_column(mut commissions: Vec<Commission>, limit: usize) -> BoardColumn {
+        let count = commissions.len();
+        let total_cents = commissions.iter().map(|c| c.price_cents).sum();
+        commissions.truncate(limit);
+        
+        BoardColumn { commissions, count, total_cents }
     }
 
-    pub async fn get_commissions_by_status(
-        app_handle: AppHandle,
+    /// Same as get_commissions_by_status, with each entry flagged when its client no longer exists.
+    pub async fn get_commissions_annotated<R: Runtime>(
+        app_handle: AppHandle<R>,
         status: String,
+    ) -> Result<Vec<AnnotatedCommission>, String> {
+        let commissions = Self::get_commissions_by_status(app_handle.clone(), status, false).await?;
+        
+        let client_ids: HashSet<String> = ClientRepository::find_all(&app_handle).await?
+            .into_iter()
+            .map(|client| client.id)
+            .collect();
+        
+        Ok(commissions.into_iter()
+            .map(|commission| AnnotatedCommission {
+                client_exists: client_ids.contains(&commission.client_id),
+                commission,
+            })
+            .collect())
+    }
+
+    /// Every commission of one client, whatever its status, newest first.
+    pub async fn get_client_commissions<R: Runtime>(
+        app_handle: AppHandle<R>,
+        client_id: String,
     ) -> Result<Vec<Commission>, String> {
-        ValidationService::validate_status(&status)?;
-        CommissionRepository::find_by_status(&app_handle, &status).await
+        ValidationService::validate_id(&client_id)?;
+        
+        // pendings also holds in-progress commissions
+        let mut commissions = Vec::new();
+        for status in FOLDER_STATUSES {
+            let found = CommissionRepository::find_by_status(&app_handle, status).await?;
+            commissions.extend(found.into_iter().filter(|c| c.client_id == client_id));
+        }
+        
+        // Unparseable timestamps sort last
+        commissions.sort_by_key(|c| {
+            std::cmp::Reverse(chrono::DateTime::parse_from_rfc3339(&c.created_at).ok())
+        });
+        
+        Ok(commissions)
     }
 
-    pub async fn move_commission(
-        app_handle: AppHandle,
+    pub async fn move_commission<R: Runtime>(
+        app_handle: AppHandle<R>,
         commission_id: String,
         from_status: String,
         to_status: String,
@@ -79,17 +323,1875 @@ impl CommissionService {
         
         println!("Moving commission {} from {} to {}", commission_id, from_status, to_status);
         
-        CommissionRepository::move_commission(&app_handle, &commission_id, &from_status, &to_status).await
+        CommissionRepository::move_commission(&app_handle, &commission_id, &from_status, &to_status).await?;
+        AuditRepository::record(&app_handle, "move", "commission", &commission_id);
+        
+        Ok(())
+    }
+
+    /// Flips a commission between pending and in-progress. Does nothing if it is already
+    /// in the requested state; completed commissions are left alone.
+    pub async fn set_commission_started<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        started: bool,
+    ) -> Result<(), String> {
+        ValidationService::validate_id(&commission_id)?;
+        
+        // pendings holds both states, so one lookup finds it either way
+        let commission = CommissionRepository::find_by_id(&app_handle, &commission_id, "pending").await?
+            .ok_or_else(|| format!("Commission {} not found in pending or in-progress", commission_id))?;
+        
+        let target_status = if started { "in-progress" } else { "pending" };
+        if commission.status == target_status {
+            return Ok(());
+        }
+        if commission.status == "completed" {
+            return Err(format!("Commission {} is already completed", commission_id));
+        }
+        
+        CommissionRepository::move_commission(&app_handle, &commission_id, &commission.status, target_status).await
+    }
+
+    /// Archives or unarchives a completed commission. updated_at is left alone: for completed
+    /// work it doubles as the completion time.
+    pub async fn set_commission_archived<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        archived: bool,
+    ) -> Result<(), String> {
+        ValidationService::validate_id(&commission_id)?;
+        
+        let mut commission = CommissionRepository::find_by_id(&app_handle, &commission_id, "completed").await?
+            .ok_or_else(|| format!("Completed commission {} not found", commission_id))?;
+        
+        if commission.archived == archived {
+            return Ok(());
+        }
+        
+        commission.archived = archived;
+        CommissionRepository::save(&app_handle, &commission).await?;
+        AuditRepository::record_snapshot(&app_handle, "save", "commission", &commission_id, &commission);
+        
+        Ok(())
+    }
+
+    /// Restores a commission to the last snapshot saved at or before the given time, keeping
+    /// its event history. The revert is logged with its own snapshot, so it can be undone the
+    /// same way.
+    pub async fn revert_commission<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        status: String,
+        to_timestamp: String,
+    ) -> Result<Commission, String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_status(&status)?;
+        let to_time = chrono::DateTime::parse_from_rfc3339(&to_timestamp)
+            .map_err(|_| "Revert time must be an RFC3339 timestamp".to_string())?;
+        
+        let (current_file, current) = CommissionRepository::find_files_by_status(&app_handle, &status).await?
+            .into_iter()
+            .find(|(_, c)| c.id == commission_id)
+            .ok_or_else(|| format!("Commission {} not found", commission_id))?;
+        
+        let snapshot = AuditRepository::read_lines(&app_handle)?
+            .iter()
+            .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+            .filter(|entry| entry.entity_type == "commission" && entry.entity_id == commission_id)
+            .filter_map(|entry| {
+                let timestamp = chrono::DateTime::parse_from_rfc3339(&entry.timestamp).ok()?;
+                Some((timestamp, entry.snapshot?))
+            })
+            .filter(|(timestamp, _)| *timestamp <= to_time)
+            .max_by_key(|(timestamp, _)| *timestamp)
+            .map(|(_, snapshot)| snapshot)
+            .ok_or_else(|| format!("No saved snapshot of commission {} at or before {}", commission_id, to_timestamp))?;
+        
+        let mut restored = CommissionRepository::parse_commission(&snapshot)?;
+        // Snapshots leave out inline images, so the ones the commission has now are kept
+        restored.images.extend(current.images.into_iter().filter(|image| image.starts_with("data:")));
+        if restored.cover_image.is_none() {
+            restored.cover_image = current.cover_image.filter(|cover| cover.starts_with("data:"));
+        }
+        restored.events = current.events;
+        restored.events.push(CommissionEvent::new("reverted", Some(to_timestamp.clone())));
+        // A snapshot's stored hash may predate the save it records; the save below rewrites it
+        restored.integrity_warning = None;
+        
+        // The snapshot may have another status or client, which puts it in another file
+        let restored_file = CommissionRepository::save(&app_handle, &restored).await?;
+        if restored_file != current_file {
+            FileStorage::delete_file(&current_file)?;
+        }
+        AuditRepository::record_snapshot(&app_handle, "revert", "commission", &commission_id, &restored);
+        
+        println!("Reverted commission {} to its state at {}", commission_id, to_timestamp);
+        
+        Ok(restored)
+    }
+
+    pub async fn delete_commission<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        status: String,
+    ) -> Result<(), String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_status(&status)?;
+        
+        CommissionRepository::delete_by_id_and_status(&app_handle, &commission_id, &status).await?;
+        AuditRepository::record(&app_handle, "delete", "commission", &commission_id);
+        
+        Ok(())
+    }
+
+    /// Replaces one commission with one piece per title. Amounts are split evenly, with the
+    /// remainder going to the first piece. Returns the ids of the new commissions.
+    pub async fn split_commission<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        status: String,
+        titles: Vec<String>,
+        keep_original: bool,
+    ) -> Result<Vec<String>, String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_status(&status)?;
+        
+        if titles.len() < 2 {
+            return Err("A commission must be split into at least two pieces".to_string());
+        }
+        for title in &titles {
+            ValidationService::validate_name(title, "Commission title")?;
+        }
+        
+        let source = CommissionRepository::find_by_id(&app_handle, &commission_id, &status).await?
+            .ok_or_else(|| format!("Commission {} not found", commission_id))?;
+        
+        let pieces = titles.len();
+        let prices = Self::split_evenly(source.price_cents, pieces);
+        let paid = Self::split_evenly(source.paid_cents, pieces);
+        let deposits = Self::split_evenly(source.deposit_cents, pieces);
+        let taxes = Self::split_evenly(source.tax_cents, pieces);
+        let fees = Self::split_evenly(source.fee_cents, pieces);
+        
+        // Check every new id up front so a collision can't leave a half-finished split
+        let mut new_ids = Vec::with_capacity(pieces);
+        for index in 1..=pieces {
+            let new_id = format!("{}_{}", source.id, index);
+            ValidationService::validate_id(&new_id)?;
+            for existing_status in FOLDER_STATUSES {
+                if CommissionRepository::find_by_id(&app_handle, &new_id, existing_status).await?.is_some() {
+                    return Err(format!("Commission {} already exists", new_id));
+                }
+            }
+            new_ids.push(new_id);
+        }
+        
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut new_pieces = Vec::with_capacity(pieces);
+        for (index, title) in titles.into_iter().enumerate() {
+            let mut piece = source.clone();
+            piece.id = new_ids[index].clone();
+            piece.title = title;
+            piece.price_cents = prices[index];
+            piece.paid_cents = paid[index];
+            piece.deposit_cents = deposits[index];
+            piece.tax_cents = taxes[index];
+            piece.fee_cents = fees[index];
+            piece.payment_status = Self::payment_status_from_amounts(piece.paid_cents, piece.price_cents).to_string();
+            piece.updated_at = now.clone();
+            
+            Self::validate_fields(&piece)?;
+            new_pieces.push(piece);
+        }
+        
+        for (index, piece) in new_pieces.into_iter().enumerate() {
+            if let Err(e) = Self::create_commission(app_handle.clone(), piece).await {
+                // Don't leave part of the split behind
+                for created_id in &new_ids[..index] {
+                    if let Err(cleanup_error) = CommissionRepository::delete_by_id_and_status(&app_handle, created_id, &source.status).await {
+                        eprintln!("Failed to remove split piece {}: {}", created_id, cleanup_error);
+                    }
+                }
+                return Err(e);
+            }
+        }
+        
+        if !keep_original {
+            CommissionRepository::delete_by_id_and_status(&app_handle, &source.id, &source.status).await?;
+        }
+        
+        Ok(new_ids)
+    }
+
+    fn split_evenly(total_cents: i64, pieces: usize) -> Vec<i64> {
+        let pieces_i64 = pieces as i64;
+        let share = total_cents / pieces_i64;
+        let remainder = total_cents % pieces_i64;
+        
+        (0..pieces)
+            .map(|index| if index == 0 { share + remainder } else { share })
+            .collect()
+    }
+
+    pub async fn update_commission_title<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        status: String,
+        new_title: String,
+    ) -> Result<(), String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_status(&status)?;
+        ValidationService::validate_name(&new_title, "Commission title")?;
+        
+        // Patch only the title so concurrent changes to other fields aren't overwritten
+        let mut commission = CommissionRepository::find_by_id(&app_handle, &commission_id, &status).await?
+            .ok_or_else(|| format!("Commission {} not found", commission_id))?;
+        
+        commission.title = new_title;
+        commission.updated_at = chrono::Utc::now().to_rfc3339();
+        
+        CommissionRepository::save(&app_handle, &commission).await?;
+        AuditRepository::record_snapshot(&app_handle, "save", "commission", &commission_id, &commission);
+        
+        Ok(())
+    }
+
+    /// Gives a commission a new id: the record, its file name and any images named after the
+    /// old id are renamed. The old id itself is not validated so non-conforming ids can be fixed.
+    pub async fn change_commission_id<R: Runtime>(
+        app_handle: AppHandle<R>,
+        status: String,
+        old_id: String,
+        new_id: String,
+    ) -> Result<(), String> {
+        ValidationService::validate_status(&status)?;
+        ValidationService::validate_commission_id(&new_id)?;
+        if old_id.is_empty() {
+            return Err("ID cannot be empty".to_string());
+        }
+        
+        // Either folder would make the new id ambiguous once the commission moves
+        for existing_status in FOLDER_STATUSES {
+            if CommissionRepository::find_by_id(&app_handle, &new_id, existing_status).await?.is_some() {
+                return Err(format!("Commission {} already exists", new_id));
+            }
+        }
+        
+        let (old_file, mut commission) = CommissionRepository::find_files_by_status(&app_handle, &status).await?
+            .into_iter()
+            .find(|(_, c)| c.id == old_id)
+            .ok_or_else(|| format!("Commission {} not found", old_id))?;
+        
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let images = ImageService::rekey_commission_images(&data_dir, &commission.client_name, &commission.images, &old_id, &new_id)?;
+        
+        if let Some(cover) = &commission.cover_image {
+            if let Some(index) = commission.images.iter().position(|image| image == cover) {
+                commission.cover_image = Some(images[index].clone());
+            }
+        }
+        commission.images = images;
+        commission.id = new_id;
+        commission.updated_at = chrono::Utc::now().to_rfc3339();
+        commission.events.push(CommissionEvent::new(
+            "id_changed",
+            Some(format!("{} -> {}", old_id, commission.id)),
+        ));
+        
+        if CommissionRepository::save(&app_handle, &commission).await? != old_file {
+            FileStorage::delete_file(&old_file)?;
+        }
+        AuditRepository::record_snapshot(&app_handle, "save", "commission", &commission.id, &commission);
+        
+        Ok(())
     }
 
-    pub async fn delete_commission(
-        app_handle: AppHandle,
+    /// Sets the cover to one of the commission's own images, or clears it with None.
+    pub async fn set_commission_cover<R: Runtime>(
+        app_handle: AppHandle<R>,
         commission_id: String,
         status: String,
+        relative_path: Option<String>,
     ) -> Result<(), String> {
         ValidationService::validate_id(&commission_id)?;
         ValidationService::validate_status(&status)?;
         
-        CommissionRepository::delete_by_id_and_status(&app_handle, &commission_id, &status).await
+        let mut commission = CommissionRepository::find_by_id(&app_handle, &commission_id, &status).await?
+            .ok_or_else(|| format!("Commission {} not found", commission_id))?;
+        
+        if let Some(path) = &relative_path {
+            ValidationService::validate_image_path(path)?;
+            if !commission.images.contains(path) {
+                return Err(format!("Image {} does not belong to commission {}", path, commission_id));
+            }
+        }
+        
+        commission.cover_image = relative_path;
+        commission.updated_at = chrono::Utc::now().to_rfc3339();
+        
+        CommissionRepository::save(&app_handle, &commission).await?;
+        AuditRepository::record_snapshot(&app_handle, "save", "commission", &commission_id, &commission);
+        
+        Ok(())
+    }
+
+    /// Replaces a tag on every commission that has it, without duplicating the new tag where
+    /// it is already present. Returns how many commissions changed.
+    pub async fn rename_tag<R: Runtime>(app_handle: AppHandle<R>, old_tag: String, new_tag: String) -> Result<usize, String> {
+        ValidationService::reject_control_and_nul(&old_tag, "Tag")?;
+        ValidationService::validate_tag(&new_tag)?;
+        if old_tag == new_tag {
+            return Ok(0);
+        }
+        
+        let mut renamed = 0;
+        for status in FOLDER_STATUSES {
+            for mut commission in CommissionRepository::find_by_status(&app_handle, status).await? {
+                let Some(position) = commission.tags.iter().position(|tag| *tag == old_tag) else {
+                    continue;
+                };
+                
+                if commission.tags.contains(&new_tag) {
+                    commission.tags.remove(position);
+                } else {
+                    commission.tags[position] = new_tag.clone();
+                }
+                
+                // updated_at is left alone: for completed work it doubles as the completion time
+                CommissionRepository::save(&app_handle, &commission).await?;
+                AuditRepository::record_snapshot(&app_handle, "save", "commission", &commission.id, &commission);
+                renamed += 1;
+            }
+        }
+        
+        println!("Renamed tag {} to {} on {} commissions", old_tag, new_tag, renamed);
+        
+        Ok(renamed)
+    }
+
+    /// Adds a tag to each of the given commissions in one status folder, reading the folder once.
+    pub async fn add_tag_bulk<R: Runtime>(
+        app_handle: AppHandle<R>,
+        ids: Vec<String>,
+        status: String,
+        tag: String,
+    ) -> Result<BulkResult, String> {
+        ValidationService::validate_status(&status)?;
+        ValidationService::validate_tag(&tag)?;
+        for id in &ids {
+            ValidationService::validate_id(id)?;
+        }
+        
+        let mut commissions = CommissionRepository::find_by_status(&app_handle, &status).await?;
+        let mut result = BulkResult {
+            updated: Vec::new(),
+            unchanged: Vec::new(),
+            missing: Vec::new(),
+        };
+        
+        for id in ids {
+            let Some(commission) = commissions.iter_mut().find(|c| c.id == id) else {
+                result.missing.push(id);
+                continue;
+            };
+            
+            if commission.tags.contains(&tag) {
+                result.unchanged.push(id);
+                continue;
+            }
+            
+            commission.tags.push(tag.clone());
+            CommissionRepository::save(&app_handle, commission).await?;
+            AuditRepository::record_snapshot(&app_handle, "save", "commission", &id, commission);
+            result.updated.push(id);
+        }
+        
+        Ok(result)
+    }
+
+    pub async fn complete_all_for_client<R: Runtime>(
+        app_handle: AppHandle<R>,
+        client_id: String,
+    ) -> Result<usize, String> {
+        ValidationService::validate_id(&client_id)?;
+        
+        // Every move rescans pendings; the cache keeps that to one read per file
+        let mut cache = ScanCache::default();
+        let commissions = CommissionRepository::find_files_by_status_cached(&app_handle, "pending", &mut cache).await?;
+        let mut completed = 0;
+        
+        for (_, commission) in commissions.iter().filter(|(_, c)| c.client_id == client_id && c.status != "completed") {
+            CommissionRepository::move_commission_cached(&app_handle, &commission.id, &commission.status, "completed", &mut cache).await?;
+            completed += 1;
+        }
+        
+        println!("Completed {} commissions for client {} ({} files read)", completed, client_id, cache.files_read());
+        
+        Ok(completed)
+    }
+
+    pub async fn reconcile_payment_status<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+        let mut changed = 0;
+        
+        for status in FOLDER_STATUSES {
+            let commissions = CommissionRepository::find_by_status(&app_handle, status).await?;
+            
+            for mut commission in commissions {
+                let expected = Self::payment_status_from_amounts(commission.paid_cents, commission.price_cents);
+                if commission.payment_status == expected {
+                    continue;
+                }
+                
+                println!("Reconciling payment status of {}: {} -> {}", commission.id, commission.payment_status, expected);
+                
+                commission.payment_status = expected.to_string();
+                // A completed commission's updated_at is its completion time, which reports go by
+                if commission.status != "completed" {
+                    commission.updated_at = chrono::Utc::now().to_rfc3339();
+                }
+                commission.events.push(CommissionEvent::new("payment_updated", Some(expected.to_string())));
+                CommissionRepository::save(&app_handle, &commission).await?;
+                AuditRepository::record_snapshot(&app_handle, "reconcile", "commission", &commission.id, &commission);
+                changed += 1;
+            }
+        }
+        
+        Ok(changed)
+    }
+
+    /// Highest-priced commissions first; equal prices keep the older commission first.
+    pub async fn get_top_commissions<R: Runtime>(
+        app_handle: AppHandle<R>,
+        limit: usize,
+        status: Option<String>,
+    ) -> Result<Vec<Commission>, String> {
+        let statuses = match &status {
+            Some(status) => {
+                ValidationService::validate_status(status)?;
+                vec![status.as_str()]
+            }
+            None => FOLDER_STATUSES.to_vec(),
+        };
+        
+        let mut commissions = Vec::new();
+        for status in statuses {
+            commissions.extend(CommissionRepository::find_by_status(&app_handle, status).await?);
+        }
+        
+        commissions.sort_by(|a, b| {
+            b.price_cents.cmp(&a.price_cents)
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+        commissions.truncate(limit);
+        
+        Ok(commissions)
+    }
+
+    pub async fn get_price_statistics<R: Runtime>(
+        app_handle: AppHandle<R>,
+        status: Option<String>,
+    ) -> Result<PriceStats, String> {
+        let statuses = match &status {
+            Some(status) => {
+                ValidationService::validate_status(status)?;
+                vec![status.as_str()]
+            }
+            None => FOLDER_STATUSES.to_vec(),
+        };
+        
+        let mut prices = Vec::new();
+        for status in statuses {
+            prices.extend(CommissionRepository::find_by_status(&app_handle, status).await?
+                .iter()
+                .map(|c| c.price_cents));
+        }
+        
+        Ok(Self::price_stats(prices))
+    }
+
+    fn price_stats(mut prices: Vec<i64>) -> PriceStats {
+        if prices.is_empty() {
+            return PriceStats::default();
+        }
+        prices.sort_unstable();
+        
+        let count = prices.len();
+        let total: i128 = prices.iter().map(|&p| i128::from(p)).sum();
+        let middle = count / 2;
+        let median_cents = if count % 2 == 0 {
+            ((i128::from(prices[middle - 1]) + i128::from(prices[middle])) as f64 / 2.0).round() as i64
+        } else {
+            prices[middle]
+        };
+        
+        PriceStats {
+            count,
+            min_cents: prices[0],
+            max_cents: prices[count - 1],
+            mean_cents: (total as f64 / count as f64).round() as i64,
+            median_cents,
+        }
+    }
+
+    pub async fn get_commissions_due_within<R: Runtime>(
+        app_handle: AppHandle<R>,
+        days: u32,
+    ) -> Result<Vec<Commission>, String> {
+        let now = chrono::Utc::now();
+        let window_end = now + chrono::Duration::days(i64::from(days));
+        
+        let commissions = CommissionRepository::find_by_status(&app_handle, "pending").await?;
+        
+        let mut due: Vec<(chrono::DateTime<chrono::Utc>, Commission)> = commissions
+            .into_iter()
+            .filter(|c| c.status != "completed")
+            .filter_map(|c| {
+                let due_date = chrono::DateTime::parse_from_rfc3339(c.due_date.as_deref()?).ok()?;
+                Some((due_date.with_timezone(&chrono::Utc), c))
+            })
+            .filter(|(due_date, _)| *due_date >= now && *due_date <= window_end)
+            .collect();
+        
+        due.sort_by_key(|(due_date, _)| *due_date);
+        
+        Ok(due.into_iter().map(|(_, c)| c).collect())
+    }
+
+    /// Open commissions of the given status that were never given a due date, oldest first.
+    pub async fn find_commissions_without_due_date<R: Runtime>(
+        app_handle: AppHandle<R>,
+        status: String,
+    ) -> Result<Vec<Commission>, String> {
+        ValidationService::validate_status(&status)?;
+        if status == "completed" {
+            return Err("Completed commissions don't need a due date".to_string());
+        }
+        
+        // pendings also holds in-progress commissions
+        let mut unscheduled: Vec<Commission> = CommissionRepository::find_by_status(&app_handle, &status).await?
+            .into_iter()
+            .filter(|c| c.status == status && c.due_date.is_none())
+            .collect();
+        
+        unscheduled.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        
+        Ok(unscheduled)
+    }
+
+    /// In-progress commissions not updated for more than the given number of days, oldest first.
+    pub async fn find_stale_in_progress<R: Runtime>(
+        app_handle: AppHandle<R>,
+        older_than_days: u32,
+    ) -> Result<Vec<Commission>, String> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(older_than_days));
+        
+        // pendings also holds in-progress commissions
+        let commissions = CommissionRepository::find_by_status(&app_handle, "in-progress").await?;
+        
+        let mut stale: Vec<(chrono::DateTime<chrono::Utc>, Commission)> = commissions
+            .into_iter()
+            .filter(|c| c.status == "in-progress")
+            .filter_map(|c| {
+                let updated_at = chrono::DateTime::parse_from_rfc3339(&c.updated_at).ok()?;
+                Some((updated_at.with_timezone(&chrono::Utc), c))
+            })
+            .filter(|(updated_at, _)| *updated_at < cutoff)
+            .collect();
+        
+        stale.sort_by_key(|(updated_at, _)| *updated_at);
+        
+        Ok(stale.into_iter().map(|(_, c)| c).collect())
+    }
+
+    pub async fn get_commissions_modified_since<R: Runtime>(
+        app_handle: AppHandle<R>,
+        since: String,
+    ) -> Result<Vec<Commission>, String> {
+        let since = chrono::DateTime::parse_from_rfc3339(&since)
+            .map_err(|_| "Since must be an RFC3339 timestamp".to_string())?
+            .with_timezone(&chrono::Utc);
+        
+        let mut modified: Vec<(chrono::DateTime<chrono::Utc>, Commission)> = Vec::new();
+        for status in FOLDER_STATUSES {
+            let commissions = CommissionRepository::find_by_status(&app_handle, status).await?;
+            modified.extend(commissions.into_iter()
+                .filter_map(|c| {
+                    let updated_at = chrono::DateTime::parse_from_rfc3339(&c.updated_at).ok()?;
+                    Some((updated_at.with_timezone(&chrono::Utc), c))
+                })
+                .filter(|(updated_at, _)| *updated_at > since));
+        }
+        
+        modified.sort_by_key(|(updated_at, _)| *updated_at);
+        
+        Ok(modified.into_iter().map(|(_, c)| c).collect())
+    }
+
+    /// Sums completed commission prices in the target currency. `rates` gives how many units of
+    /// the target currency one unit of each other currency is worth. Commissions in a currency
+    /// without a rate are left out and logged.
+    pub async fn get_revenue_in_currency<R: Runtime>(
+        app_handle: AppHandle<R>,
+        target_currency: String,
+        rates: HashMap<String, f64>,
+    ) -> Result<i64, String> {
+        ValidationService::validate_currency(&target_currency)?;
+        if let Some((currency, rate)) = rates.iter().find(|(_, rate)| !rate.is_finite() || **rate <= 0.0) {
+            return Err(format!("Invalid rate {} for {}", rate, currency));
+        }
+        
+        let commissions = CommissionRepository::find_by_status(&app_handle, "completed").await?;
+        let mut total = 0.0;
+        let mut skipped = Vec::new();
+        
+        for commission in &commissions {
+            let rate = if commission.currency == target_currency {
+                Some(1.0)
+            } else {
+                rates.get(&commission.currency).copied()
+            };
+            
+            match rate {
+                Some(rate) => total += commission.price_cents as f64 * rate,
+                None => skipped.push(format!("{} ({})", commission.id, commission.currency)),
+            }
+        }
+        
+        if !skipped.is_empty() {
+            println!("Revenue in {} skipped commissions with no rate: {}", target_currency, skipped.join(", "));
+        }
+        
+        // Rounded once at the end so per-commission rounding doesn't add up
+        Ok(total.round() as i64)
+    }
+
+    /// What to pay back if the commission is cancelled now. Whatever the policy keeps is taken
+    /// from the amount paid so far, so the refund is never negative.
+    pub async fn compute_refund<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        status: String,
+        policy: RefundPolicy,
+    ) -> Result<i64, String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_status(&status)?;
+        ValidationService::validate_refund_policy(&policy.kind, policy.kept_percent)?;
+        
+        let commission = CommissionRepository::find_by_id(&app_handle, &commission_id, &status).await?
+            .ok_or_else(|| format!("Commission {} not found", commission_id))?;
+        
+        let kept_cents = match policy.kind.as_str() {
+            "keep_deposit" => commission.deposit_cents,
+            "percentage_kept" => {
+                (commission.price_cents as f64 * f64::from(policy.kept_percent) / 100.0).round() as i64
+            }
+            _ => 0,
+        };
+        
+        Ok((commission.paid_cents - kept_cents).max(0))
+    }
+
+    /// Share of the value of pending and in-progress work that has already been paid, from 0.0
+    /// up; overpayments can push it past 1.0. 0.0 when nothing is outstanding.
+    pub async fn get_deposit_coverage<R: Runtime>(app_handle: AppHandle<R>) -> Result<f64, String> {
+        let outstanding: Vec<Commission> = CommissionRepository::find_by_status(&app_handle, "pending").await?
+            .into_iter()
+            .filter(|c| c.status != "completed")
+            .collect();
+        
+        let total_cents: i64 = outstanding.iter().map(|c| c.price_cents).sum();
+        if total_cents <= 0 {
+            return Ok(0.0);
+        }
+        
+        let paid_cents: i64 = outstanding.iter().map(|c| c.paid_cents).sum();
+        Ok(paid_cents as f64 / total_cents as f64)
+    }
+
+    /// Sums price_cents of completed commissions per tag, most revenue first. A commission
+    /// counts in full toward every tag it carries, so the buckets overlap.
+    pub async fn get_revenue_by_tag<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<(String, i64)>, String> {
+        let completed = CommissionRepository::find_by_status(&app_handle, "completed").await?;
+        
+        let mut revenue: HashMap<String, i64> = HashMap::new();
+        for commission in &completed {
+            let tags: HashSet<&str> = commission.tags.iter().map(String::as_str).collect();
+            if tags.is_empty() {
+                *revenue.entry(UNTAGGED_BUCKET.to_string()).or_insert(0) += commission.price_cents;
+            }
+            for tag in tags {
+                *revenue.entry(tag.to_string()).or_insert(0) += commission.price_cents;
+            }
+        }
+        
+        let mut revenue: Vec<(String, i64)> = revenue.into_iter().collect();
+        revenue.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        
+        Ok(revenue)
+    }
+
+    /// Counts completed commissions per ISO week ("2026-W05") or month ("2026-02") of their
+    /// updated_at, oldest bucket first.
+    pub async fn get_completion_cadence<R: Runtime>(
+        app_handle: AppHandle<R>,
+        granularity: String,
+    ) -> Result<Vec<(String, usize)>, String> {
+        ValidationService::validate_cadence_granularity(&granularity)?;
+        
+        let completed = CommissionRepository::find_by_status(&app_handle, "completed").await?;
+        
+        // Zero-padded labels sort chronologically
+        let mut buckets: BTreeMap<String, usize> = BTreeMap::new();
+        for commission in &completed {
+            let Ok(completed_at) = chrono::DateTime::parse_from_rfc3339(&commission.updated_at) else {
+                continue;
+            };
+            let completed_at = completed_at.with_timezone(&chrono::Utc);
+            
+            let label = if granularity == "week" {
+                completed_at.format("%G-W%V").to_string()
+            } else {
+                completed_at.format("%Y-%m").to_string()
+            };
+            *buckets.entry(label).or_insert(0) += 1;
+        }
+        
+        Ok(buckets.into_iter().collect())
+    }
+
+    /// Commissions created per UTC day ("2026-02-14") of the given year, across every folder.
+    /// Days without commissions are left out.
+    pub async fn get_activity_calendar<R: Runtime>(app_handle: AppHandle<R>, year: i32) -> Result<HashMap<String, usize>, String> {
+        let mut calendar = HashMap::new();
+        for status in FOLDER_STATUSES {
+            for commission in CommissionRepository::find_by_status(&app_handle, status).await? {
+                let Ok(created_at) = chrono::DateTime::parse_from_rfc3339(&commission.created_at) else {
+                    continue;
+                };
+                let created_on = created_at.with_timezone(&chrono::Utc).date_naive();
+                if created_on.year() != year {
+                    continue;
+                }
+                
+                *calendar.entry(created_on.format("%Y-%m-%d").to_string()).or_insert(0) += 1;
+            }
+        }
+        
+        Ok(calendar)
+    }
+
+    pub async fn get_commission_timeline<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        status: String,
+    ) -> Result<Vec<CommissionEvent>, String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_status(&status)?;
+        
+        let commission = CommissionRepository::find_by_id(&app_handle, &commission_id, &status).await?
+            .ok_or_else(|| format!("Commission {} not found", commission_id))?;
+        
+        Ok(commission.events)
+    }
+
+    pub async fn get_commission_by_reference<R: Runtime>(
+        app_handle: AppHandle<R>,
+        reference_number: u64,
+    ) -> Result<Option<Commission>, String> {
+        for status in FOLDER_STATUSES {
+            let found = CommissionRepository::find_by_status(&app_handle, status).await?
+                .into_iter()
+                .find(|c| c.reference_number == Some(reference_number));
+            if found.is_some() {
+                return Ok(found);
+            }
+        }
+        
+        Ok(None)
+    }
+
+    /// Numbers commissions saved before reference numbers existed, oldest first, so they follow
+    /// the order the work came in. Returns how many were numbered.
+    pub async fn backfill_reference_numbers<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+        let mut unnumbered = Vec::new();
+        for status in FOLDER_STATUSES {
+            unnumbered.extend(CommissionRepository::find_by_status(&app_handle, status).await?
+                .into_iter()
+                .filter(|c| c.reference_number.is_none()));
+        }
+        unnumbered.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        
+        for commission in &mut unnumbered {
+            commission.reference_number = Some(CommissionRepository::next_reference_number(&app_handle)?);
+            // updated_at is left alone: for completed work it doubles as the completion time
+            CommissionRepository::save(&app_handle, commission).await?;
+        }
+        
+        println!("Assigned reference numbers to {} commissions", unnumbered.len());
+        
+        Ok(unnumbered.len())
+    }
+
+    /// Case-insensitive match against title and description, across active and completed commissions.
+    pub async fn search_commissions<R: Runtime>(app_handle: AppHandle<R>, query: String) -> Result<Vec<Commission>, String> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        let mut matches = Vec::new();
+        for status in FOLDER_STATUSES {
+            let commissions = CommissionRepository::find_by_status(&app_handle, status).await?;
+            matches.extend(commissions.into_iter().filter(|commission| {
+                commission.title.to_lowercase().contains(&query)
+                    || commission.description.to_lowercase().contains(&query)
+            }));
+        }
+        
+        Ok(matches)
+    }
+
+    pub async fn get_commission_breakdown<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        status: String,
+    ) -> Result<CommissionBreakdown, String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_status(&status)?;
+        
+        let commission = CommissionRepository::find_by_id(&app_handle, &commission_id, &status).await?
+            .ok_or_else(|| format!("Commission {} not found", commission_id))?;
+        
+        Ok(Self::breakdown_for(&commission))
+    }
+
+    /// price_cents is the client-facing total; the base is whatever tax and fees leave over.
+    pub fn breakdown_for(commission: &Commission) -> CommissionBreakdown {
+        CommissionBreakdown {
+            base_cents: commission.price_cents - commission.tax_cents - commission.fee_cents,
+            tax_cents: commission.tax_cents,
+            fee_cents: commission.fee_cents,
+            total_cents: commission.price_cents,
+        }
+    }
+
+    /// The stored copy of a commission, whichever folder it's in.
+    async fn find_stored<R: Runtime>(app_handle: &AppHandle<R>, commission_id: &str) -> Result<Option<Commission>, String> {
+        for status in FOLDER_STATUSES {
+            if let Some(existing) = CommissionRepository::find_by_id(app_handle, commission_id, status).await? {
+                return Ok(Some(existing));
+            }
+        }
+        
+        Ok(None)
+    }
+
+    /// Carries the stored history and reference number over to the incoming commission and
+    /// appends events for what changed. New commissions get the next reference number.
+    fn record_save_events<R: Runtime>(app_handle: &AppHandle<R>, commission: &mut Commission, existing: Option<Commission>) -> Result<(), String> {
+        // The frontend doesn't send events back, so the stored copy is the source of truth
+        let Some(existing) = existing else {
+            commission.events = vec![CommissionEvent::new("created", None)];
+            commission.reference_number = Some(CommissionRepository::next_reference_number(app_handle)?);
+            return Ok(());
+        };
+        
+        commission.events = existing.events;
+        commission.reference_number = existing.reference_number;
+        
+        if existing.status != commission.status {
+            commission.events.push(CommissionEvent::new(
+                "status_changed",
+                Some(format!("{} -> {}", existing.status, commission.status)),
+            ));
+        }
+        
+        if existing.payment_status != commission.payment_status || existing.paid_cents != commission.paid_cents {
+            commission.events.push(CommissionEvent::new(
+                "payment_updated",
+                Some(commission.payment_status.clone()),
+            ));
+        }
+        
+        for image in commission.images.iter().filter(|image| !existing.images.contains(image)) {
+            // Inline data URLs are too large to copy into the timeline
+            let detail = if image.starts_with("data:") { None } else { Some(image.clone()) };
+            commission.events.push(CommissionEvent::new("image_added", detail));
+        }
+        
+        Ok(())
+    }
+
+    pub async fn rebuild_commission_client_names<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+        let clients = ClientRepository::find_all(&app_handle).await?;
+        let client_names: HashMap<String, String> = clients.into_iter()
+            .map(|client| (client.id, client.name))
+            .collect();
+        
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut updated = 0;
+        
+        for status in FOLDER_STATUSES {
+            for (old_file, mut commission) in CommissionRepository::find_files_by_status(&app_handle, status).await? {
+                let Some(client_name) = client_names.get(&commission.client_id) else {
+                    eprintln!("Skipping commission {}: unknown client {}", commission.id, commission.client_id);
+                    continue;
+                };
+                
+                if commission.client_name == *client_name {
+                    continue;
+                }
+                
+                let old_client_name = std::mem::replace(&mut commission.client_name, client_name.clone());
+                commission.updated_at = chrono::Utc::now().to_rfc3339();
+                
+                // The client folder follows the name, so the file may need to move
+                if CommissionRepository::save(&app_handle, &commission).await? != old_file {
+                    FileStorage::delete_file(&old_file)?;
+                }
+                ImageService::relocate_commission_images(&data_dir, &commission.images, &old_client_name, client_name)?;
+                
+                updated += 1;
+            }
+        }
+        
+        Ok(updated)
+    }
+
+    /// Price for the given hours at the configured hourly rate, rounded to the cent.
+    pub async fn suggest_price<R: Runtime>(app_handle: AppHandle<R>, hours: f64) -> Result<i64, String> {
+        ValidationService::validate_estimated_hours(hours)?;
+        
+        let hourly_rate_cents = ConfigRepository::load(&app_handle)?.pricing.hourly_rate_cents;
+        if hourly_rate_cents <= 0 {
+            return Err("Set an hourly rate before asking for a price".to_string());
+        }
+        
+        let price_cents = (hours * hourly_rate_cents as f64).round() as i64;
+        ValidationService::validate_price_cents(price_cents)?;
+        
+        Ok(price_cents)
+    }
+
+    pub async fn suggest_due_date<R: Runtime>(app_handle: AppHandle<R>, estimated_days: u32) -> Result<String, String> {
+        let outstanding = CommissionRepository::find_by_status(&app_handle, "pending").await?
+            .iter()
+            .filter(|c| c.status != "completed")
+            .count();
+        
+        let turnaround_days = Self::average_turnaround_days(&app_handle).await?
+            .unwrap_or(DEFAULT_TURNAROUND_DAYS);
+        
+        // Work starts once the current queue is cleared
+        let queue_days = outstanding as f64 * turnaround_days;
+        let total_hours = ((queue_days + f64::from(estimated_days)) * 24.0).round() as i64;
+        
+        let suggested = chrono::Utc::now() + chrono::Duration::hours(total_hours);
+        Ok(suggested.to_rfc3339())
+    }
+
+    /// When the current queue would be cleared at the average turnaround seen in history.
+    /// Unlike suggest_due_date there is no fallback: without history there is no estimate.
+    pub async fn estimate_queue_clear_date<R: Runtime>(app_handle: AppHandle<R>) -> Result<String, String> {
+        let outstanding = CommissionRepository::find_by_status(&app_handle, "pending").await?
+            .iter()
+            .filter(|c| c.status != "completed")
+            .count();
+        
+        let turnaround_days = Self::average_turnaround_days(&app_handle).await?
+            .ok_or("Insufficient data: no completed commissions to estimate turnaround from")?;
+        
+        let total_hours = (outstanding as f64 * turnaround_days * 24.0).round() as i64;
+        
+        let clear_date = chrono::Utc::now() + chrono::Duration::hours(total_hours);
+        Ok(clear_date.to_rfc3339())
+    }
+
+    /// Average days between creation and completion over the history folder, if any is measurable.
+    async fn average_turnaround_days<R: Runtime>(app_handle: &AppHandle<R>) -> Result<Option<f64>, String> {
+        let completed = CommissionRepository::find_by_status(app_handle, "completed").await?;
+        
+        let durations: Vec<f64> = completed.iter()
+            .filter_map(|c| {
+                let created = chrono::DateTime::parse_from_rfc3339(&c.created_at).ok()?;
+                let finished = chrono::DateTime::parse_from_rfc3339(&c.updated_at).ok()?;
+                let days = (finished - created).num_seconds() as f64 / 86_400.0;
+                (days >= 0.0).then_some(days)
+            })
+            .collect();
+        
+        if durations.is_empty() {
+            return Ok(None);
+        }
+        
+        Ok(Some(durations.iter().sum::<f64>() / durations.len() as f64))
+    }
+
+    fn payment_status_from_amounts(paid_cents: i64, price_cents: i64) -> &'static str {
+        if paid_cents <= 0 {
+            "Not Paid"
+        } else if paid_cents >= price_cents {
+            "Fully Paid"
+        } else {
+            "Half Paid"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::commission_repository::REFERENCE_COUNTER_FILE;
+    use crate::test_support::{client, commission, TestEnv};
+    use tauri::async_runtime::block_on;
+
+    #[test]
+    fn reconcile_payment_status_fixes_disagreeing_status() {
+        let env = TestEnv::new();
+        let mut partly_paid = commission("c1", "alice", "pending");
+        partly_paid.paid_cents = 400;
+        let mut unpaid = commission("c2", "alice", "pending");
+        unpaid.payment_status = "Fully Paid".to_string();
+        let mut settled = commission("c3", "alice", "pending");
+        settled.paid_cents = 1000;
+        settled.payment_status = "Fully Paid".to_string();
+        for c in [&partly_paid, &unpaid, &settled] {
+            block_on(CommissionRepository::save(env.app(), c)).unwrap();
+        }
+        
+        assert_eq!(block_on(CommissionService::reconcile_payment_status(env.app().clone())).unwrap(), 2);
+        
+        let reconciled = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        assert_eq!(reconciled.payment_status, "Half Paid");
+        let reconciled = block_on(CommissionRepository::find_by_id(env.app(), "c2", "pending")).unwrap().unwrap();
+        assert_eq!(reconciled.payment_status, "Not Paid");
+        
+        let audit = AuditRepository::read_lines(env.app()).unwrap();
+        assert_eq!(audit.iter().filter(|line| line.contains("\"reconcile\"")).count(), 2);
+    }
+
+    fn frontend_save(id: &str, extra: serde_json::Value) -> CommissionInput {
+        let mut input = serde_json::to_value(commission(id, "alice", "pending")).unwrap();
+        let object = input.as_object_mut().unwrap();
+        for key in ["paid_cents", "deposit_cents", "tax_cents", "fee_cents", "currency", "priority", "tags",
+            "due_date", "estimated_hours", "archived", "cover_image", "reference_number", "events", "content_hash"] {
+            object.remove(key);
+        }
+        object.extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(input).unwrap()
+    }
+
+    #[test]
+    fn save_commission_keeps_fields_the_frontend_omits() {
+        let env = TestEnv::new();
+        let mut stored = commission("c1", "alice", "pending");
+        stored.paid_cents = 500;
+        stored.tags = vec!["sketch".to_string()];
+        stored.priority = "High".to_string();
+        stored.due_date = Some("2026-03-01T00:00:00Z".to_string());
+        block_on(CommissionService::create_commission(env.app().clone(), stored)).unwrap();
+        
+        let from_ui = frontend_save("c1", serde_json::json!({ "title": "Renamed" }));
+        block_on(CommissionService::save_commission(env.app().clone(), from_ui)).unwrap();
+        
+        let saved = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        assert_eq!(saved.title, "Renamed");
+        assert_eq!(saved.paid_cents, 500);
+        assert_eq!(saved.tags, vec!["sketch".to_string()]);
+        assert_eq!(saved.priority, "High");
+        assert_eq!(saved.due_date.as_deref(), Some("2026-03-01T00:00:00Z"));
+        assert_eq!(saved.reference_number, Some(1));
+    }
+
+    #[test]
+    fn save_commission_clears_fields_that_are_sent_empty() {
+        let env = TestEnv::new();
+        let mut stored = commission("c1", "alice", "pending");
+        stored.paid_cents = 500;
+        stored.tags = vec!["sketch".to_string()];
+        stored.priority = "High".to_string();
+        stored.due_date = Some("2026-03-01T00:00:00Z".to_string());
+        stored.archived = true;
+        block_on(CommissionService::create_commission(env.app().clone(), stored)).unwrap();
+        
+        let from_ui = frontend_save("c1", serde_json::json!({
+            "paid_cents": 0, "tags": [], "priority": "Normal", "due_date": null, "archived": false,
+        }));
+        block_on(CommissionService::save_commission(env.app().clone(), from_ui)).unwrap();
+        
+        let saved = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        assert_eq!(saved.paid_cents, 0);
+        assert!(saved.tags.is_empty());
+        assert_eq!(saved.priority, "Normal");
+        assert_eq!(saved.due_date, None);
+        assert!(!saved.archived);
+        
+        // Internal callers pass the whole commission, so its defaults are saved as they are
+        let mut stored = saved;
+        stored.tags = vec!["sketch".to_string()];
+        block_on(CommissionService::create_commission(env.app().clone(), stored)).unwrap();
+        block_on(CommissionService::create_commission(env.app().clone(), commission("c1", "alice", "pending"))).unwrap();
+        let saved = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        assert!(saved.tags.is_empty());
+    }
+
+    #[test]
+    fn due_within_keeps_only_dates_inside_the_window() {
+        let env = TestEnv::new();
+        let now = chrono::Utc::now();
+        for (id, days) in [("later", 3), ("soon", 1), ("far", 30), ("past", -2)] {
+            let mut c = commission(id, "alice", "pending");
+            c.due_date = Some((now + chrono::Duration::days(days)).to_rfc3339());
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+        block_on(CommissionRepository::save(env.app(), &commission("undated", "alice", "pending"))).unwrap();
+        
+        let due = block_on(CommissionService::get_commissions_due_within(env.app().clone(), 7)).unwrap();
+        let ids: Vec<&str> = due.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["soon", "later"]);
+    }
+
+    #[test]
+    fn complete_all_for_client_moves_both_pending_commissions() {
+        let env = TestEnv::new();
+        block_on(CommissionRepository::save(env.app(), &commission("c1", "alice", "pending"))).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c2", "alice", "in-progress"))).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c3", "bob", "pending"))).unwrap();
+        
+        assert_eq!(block_on(CommissionService::complete_all_for_client(env.app().clone(), "alice".to_string())).unwrap(), 2);
+        
+        let completed = block_on(CommissionRepository::find_by_status(env.app(), "completed")).unwrap();
+        let mut ids: Vec<&str> = completed.iter().map(|c| c.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["c1", "c2"]);
+        assert_eq!(block_on(CommissionRepository::find_by_status(env.app(), "pending")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn creating_then_moving_records_two_events() {
+        let env = TestEnv::new();
+        block_on(CommissionService::create_commission(env.app().clone(), commission("c1", "alice", "pending"))).unwrap();
+        block_on(CommissionService::move_commission(
+            env.app().clone(), "c1".to_string(), "pending".to_string(), "completed".to_string(),
+        )).unwrap();
+        
+        let timeline = block_on(CommissionService::get_commission_timeline(
+            env.app().clone(), "c1".to_string(), "completed".to_string(),
+        )).unwrap();
+        let kinds: Vec<&str> = timeline.iter().map(|event| event.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["created", "status_changed"]);
+    }
+
+    #[test]
+    fn update_title_leaves_other_fields_alone() {
+        let env = TestEnv::new();
+        let mut original = commission("c1", "alice", "pending");
+        original.description = "Full body".to_string();
+        original.tags = vec!["ref".to_string()];
+        block_on(CommissionRepository::save(env.app(), &original)).unwrap();
+        
+        block_on(CommissionService::update_commission_title(
+            env.app().clone(), "c1".to_string(), "pending".to_string(), "New title".to_string(),
+        )).unwrap();
+        
+        let updated = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        assert_eq!(updated.title, "New title");
+        assert_eq!(updated.description, original.description);
+        assert_eq!(updated.tags, original.tags);
+        assert_eq!(updated.price_cents, original.price_cents);
+        assert_eq!(updated.created_at, original.created_at);
+        assert_ne!(updated.updated_at, original.updated_at);
+    }
+
+    #[test]
+    fn larger_queue_suggests_a_later_due_date() {
+        let env = TestEnv::new();
+        let due_date = || {
+            let suggested = block_on(CommissionService::suggest_due_date(env.app().clone(), 3)).unwrap();
+            chrono::DateTime::parse_from_rfc3339(&suggested).unwrap()
+        };
+        
+        let empty_queue = due_date();
+        block_on(CommissionRepository::save(env.app(), &commission("c1", "alice", "pending"))).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c2", "bob", "in-progress"))).unwrap();
+        let busy_queue = due_date();
+        
+        assert!(busy_queue - empty_queue > chrono::Duration::days(13));
+    }
+
+    #[test]
+    fn rebuild_client_names_renames_and_moves_commissions() {
+        let env = TestEnv::new();
+        block_on(ClientRepository::save(env.app(), &client("alice", "Alice Renamed"))).unwrap();
+        let stale = commission("c1", "alice", "pending");
+        let old_file = CommissionRepository::file_path(env.data_dir(), &stale, false);
+        block_on(CommissionRepository::save(env.app(), &stale)).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c2", "ghost", "pending"))).unwrap();
+        
+        assert_eq!(block_on(CommissionService::rebuild_commission_client_names(env.app().clone())).unwrap(), 1);
+        
+        let renamed = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        assert_eq!(renamed.client_name, "Alice Renamed");
+        assert!(!old_file.exists());
+        assert!(CommissionRepository::file_path(env.data_dir(), &renamed, false).exists());
+    }
+
+    #[test]
+    fn breakdown_splits_the_price_into_base_tax_and_fee() {
+        let env = TestEnv::new();
+        let mut taxed = commission("c1", "alice", "pending");
+        taxed.price_cents = 12000;
+        taxed.tax_cents = 1500;
+        taxed.fee_cents = 500;
+        block_on(CommissionRepository::save(env.app(), &taxed)).unwrap();
+        
+        let breakdown = block_on(CommissionService::get_commission_breakdown(
+            env.app().clone(), "c1".to_string(), "pending".to_string(),
+        )).unwrap();
+        assert_eq!(breakdown.base_cents, 10000);
+        assert_eq!(breakdown.tax_cents, 1500);
+        assert_eq!(breakdown.fee_cents, 500);
+        assert_eq!(breakdown.total_cents, 12000);
+    }
+
+    #[test]
+    fn annotated_listing_flags_orphaned_commissions() {
+        let env = TestEnv::new();
+        block_on(ClientRepository::save(env.app(), &client("alice", "Alice"))).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c1", "alice", "pending"))).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c2", "ghost", "pending"))).unwrap();
+        
+        let mut annotated = block_on(CommissionService::get_commissions_annotated(env.app().clone(), "pending".to_string())).unwrap();
+        annotated.sort_by(|a, b| a.commission.id.cmp(&b.commission.id));
+        let flags: Vec<(&str, bool)> = annotated.iter().map(|a| (a.commission.id.as_str(), a.client_exists)).collect();
+        assert_eq!(flags, vec![("c1", true), ("c2", false)]);
+    }
+
+    #[test]
+    fn split_shares_price_and_deposit_between_pieces() {
+        let env = TestEnv::new();
+        let mut source = commission("c1", "alice", "pending");
+        source.price_cents = 1001;
+        source.deposit_cents = 301;
+        block_on(CommissionRepository::save(env.app(), &source)).unwrap();
+        
+        let ids = block_on(CommissionService::split_commission(
+            env.app().clone(), "c1".to_string(), "pending".to_string(),
+            vec!["Sketch".to_string(), "Lineart".to_string()], false,
+        )).unwrap();
+        assert_eq!(ids, vec!["c1_1".to_string(), "c1_2".to_string()]);
+        
+        let pieces: Vec<Commission> = ids.iter()
+            .map(|id| block_on(CommissionRepository::find_by_id(env.app(), id, "pending")).unwrap().unwrap())
+            .collect();
+        assert_eq!(pieces.iter().map(|p| p.price_cents).sum::<i64>(), 1001);
+        assert_eq!(pieces.iter().map(|p| p.deposit_cents).collect::<Vec<_>>(), vec![151, 150]);
+        assert!(block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().is_none());
+    }
+
+    #[test]
+    fn split_writes_nothing_when_a_piece_is_invalid() {
+        let env = TestEnv::new();
+        let mut source = commission("c1", "alice", "pending");
+        source.currency = "dollars".to_string();
+        block_on(CommissionRepository::save(env.app(), &source)).unwrap();
+        
+        let result = block_on(CommissionService::split_commission(
+            env.app().clone(), "c1".to_string(), "pending".to_string(),
+            vec!["Sketch".to_string(), "Lineart".to_string()], false,
+        ));
+        assert!(result.is_err());
+        let remaining = block_on(CommissionRepository::find_by_status(env.app(), "pending")).unwrap();
+        assert_eq!(remaining.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), vec!["c1"]);
+    }
+
+    #[test]
+    fn modified_since_returns_later_updates_in_order() {
+        let env = TestEnv::new();
+        for (id, updated_at) in [("late", "2026-03-05T00:00:00Z"), ("early", "2026-01-15T00:00:00Z"), ("mid", "2026-02-10T00:00:00Z")] {
+            let mut c = commission(id, "alice", "pending");
+            c.updated_at = updated_at.to_string();
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+        
+        let modified = block_on(CommissionService::get_commissions_modified_since(
+            env.app().clone(), "2026-02-01T00:00:00Z".to_string(),
+        )).unwrap();
+        assert_eq!(modified.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), vec!["mid", "late"]);
+    }
+
+    #[test]
+    fn cover_can_be_set_and_cleared() {
+        let env = TestEnv::new();
+        let mut illustrated = commission("c1", "alice", "pending");
+        illustrated.images = vec!["images/c1_a.png".to_string(), "images/c1_b.png".to_string()];
+        block_on(CommissionRepository::save(env.app(), &illustrated)).unwrap();
+        let cover = |path: Option<&str>| block_on(CommissionService::set_commission_cover(
+            env.app().clone(), "c1".to_string(), "pending".to_string(), path.map(str::to_string),
+        ));
+        let stored_cover = || block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap().cover_image;
+        
+        cover(Some("images/c1_b.png")).unwrap();
+        assert_eq!(stored_cover(), Some("images/c1_b.png".to_string()));
+        assert!(cover(Some("images/other.png")).is_err());
+        
+        cover(None).unwrap();
+        assert_eq!(stored_cover(), None);
+    }
+
+    #[test]
+    fn client_commissions_span_statuses_newest_first() {
+        let env = TestEnv::new();
+        let mut older = commission("c1", "alice", "completed");
+        older.created_at = "2026-01-01T00:00:00Z".to_string();
+        let mut newer = commission("c2", "alice", "pending");
+        newer.created_at = "2026-02-01T00:00:00Z".to_string();
+        for c in [&older, &newer, &commission("c3", "bob", "pending")] {
+            block_on(CommissionRepository::save(env.app(), c)).unwrap();
+        }
+        
+        let found = block_on(CommissionService::get_client_commissions(env.app().clone(), "alice".to_string())).unwrap();
+        assert_eq!(found.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), vec!["c2", "c1"]);
+    }
+
+    #[test]
+    fn cadence_buckets_completions_by_week() {
+        let env = TestEnv::new();
+        for (id, completed_at) in [("c1", "2026-01-06T10:00:00Z"), ("c2", "2026-01-08T10:00:00Z"), ("c3", "2026-01-14T10:00:00Z")] {
+            let mut done = commission(id, "alice", "completed");
+            done.updated_at = completed_at.to_string();
+            block_on(CommissionRepository::save(env.app(), &done)).unwrap();
+        }
+        
+        let weekly = block_on(CommissionService::get_completion_cadence(env.app().clone(), "week".to_string())).unwrap();
+        assert_eq!(weekly, vec![("2026-W02".to_string(), 2), ("2026-W03".to_string(), 1)]);
+        assert!(block_on(CommissionService::get_completion_cadence(env.app().clone(), "day".to_string())).is_err());
+    }
+
+    #[test]
+    fn change_id_renames_the_file_and_its_images() {
+        let env = TestEnv::new();
+        let saved = block_on(ImageService::save_commission_image(
+            env.app().clone(), "old".to_string(), "Name alice".to_string(), crate::test_support::png(8, 8), "art.png".to_string(),
+        )).unwrap();
+        let mut c = commission("old", "alice", "pending");
+        c.images = vec![saved.relative_path.clone()];
+        c.cover_image = Some(saved.relative_path.clone());
+        block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        
+        block_on(CommissionService::change_commission_id(
+            env.app().clone(), "pending".to_string(), "old".to_string(), "new".to_string(),
+        )).unwrap();
+        
+        assert!(block_on(CommissionRepository::find_by_id(env.app(), "old", "pending")).unwrap().is_none());
+        let rekeyed = block_on(CommissionRepository::find_by_id(env.app(), "new", "pending")).unwrap().unwrap();
+        assert_eq!(rekeyed.images, vec!["images/new_art.png".to_string()]);
+        assert_eq!(rekeyed.cover_image, Some("images/new_art.png".to_string()));
+        
+        let client_dir = env.data_dir().join("pendings").join("Name alice");
+        assert!(client_dir.join("images").join("new_art.png").is_file());
+        assert!(!client_dir.join(&saved.relative_path).exists());
+        let files = block_on(CommissionRepository::find_files_by_status(env.app(), "pending")).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(!files[0].0.file_name().unwrap().to_string_lossy().contains("old"));
+    }
+
+    #[test]
+    fn top_commissions_are_the_highest_priced() {
+        let env = TestEnv::new();
+        for (id, status, price, created_at) in [
+            ("small", "pending", 500, "2026-01-01T00:00:00Z"),
+            ("big", "completed", 9000, "2026-01-03T00:00:00Z"),
+            ("tie_late", "pending", 4000, "2026-01-05T00:00:00Z"),
+            ("tie_early", "pending", 4000, "2026-01-02T00:00:00Z"),
+        ] {
+            let mut c = commission(id, "alice", status);
+            c.price_cents = price;
+            c.created_at = created_at.to_string();
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+        
+        let top = block_on(CommissionService::get_top_commissions(env.app().clone(), 2, None)).unwrap();
+        let ids: Vec<&str> = top.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["big", "tie_early"]);
+        
+        let pending = block_on(CommissionService::get_top_commissions(env.app().clone(), 2, Some("pending".to_string()))).unwrap();
+        let ids: Vec<&str> = pending.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["tie_early", "tie_late"]);
+    }
+
+    #[test]
+    fn started_flag_toggles_between_pending_and_in_progress() {
+        let env = TestEnv::new();
+        block_on(CommissionRepository::save(env.app(), &commission("c1", "alice", "pending"))).unwrap();
+        let status = || block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap().status;
+        
+        block_on(CommissionService::set_commission_started(env.app().clone(), "c1".to_string(), true)).unwrap();
+        assert_eq!(status(), "in-progress");
+        block_on(CommissionService::set_commission_started(env.app().clone(), "c1".to_string(), true)).unwrap();
+        assert_eq!(status(), "in-progress");
+        
+        block_on(CommissionService::set_commission_started(env.app().clone(), "c1".to_string(), false)).unwrap();
+        assert_eq!(status(), "pending");
+        assert_eq!(block_on(CommissionRepository::find_by_status(env.app(), "pending")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn duplicate_title_for_the_same_client_warns() {
+        let env = TestEnv::new();
+        env.configure(|config| config.storage.warn_duplicate_titles = true);
+        let mut portrait = commission("c1", "alice", "pending");
+        portrait.title = "Portrait".to_string();
+        assert!(block_on(CommissionService::create_commission(env.app().clone(), portrait.clone())).unwrap().is_empty());
+        
+        let mut same_client = portrait.clone();
+        same_client.id = "c2".to_string();
+        let warnings = block_on(CommissionService::create_commission(env.app().clone(), same_client)).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("c1"));
+        
+        let mut other_client = commission("c3", "bob", "pending");
+        other_client.title = "Portrait".to_string();
+        assert!(block_on(CommissionService::create_commission(env.app().clone(), other_client)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn revenue_converts_other_currencies_with_the_given_rate() {
+        let env = TestEnv::new();
+        for (id, currency, price) in [("usd", "USD", 1000), ("eur", "EUR", 2000), ("gbp", "GBP", 5000)] {
+            let mut c = commission(id, "alice", "completed");
+            c.currency = currency.to_string();
+            c.price_cents = price;
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+        
+        let rates = HashMap::from([("EUR".to_string(), 1.1)]);
+        let total = block_on(CommissionService::get_revenue_in_currency(env.app().clone(), "USD".to_string(), rates)).unwrap();
+        assert_eq!(total, 1000 + 2200);
+    }
+
+    #[test]
+    fn rename_tag_replaces_it_on_every_commission() {
+        let env = TestEnv::new();
+        for (id, status, tags) in [("c1", "pending", vec!["fullbody"]), ("c2", "completed", vec!["fullbody", "full-body"]), ("c3", "pending", vec!["bust"])] {
+            let mut c = commission(id, "alice", status);
+            c.tags = tags.into_iter().map(String::from).collect();
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+        
+        let renamed = block_on(CommissionService::rename_tag(env.app().clone(), "fullbody".to_string(), "full-body".to_string())).unwrap();
+        assert_eq!(renamed, 2);
+        
+        let tags = |id: &str, status: &str| block_on(CommissionRepository::find_by_id(env.app(), id, status)).unwrap().unwrap().tags;
+        assert_eq!(tags("c1", "pending"), vec!["full-body".to_string()]);
+        assert_eq!(tags("c2", "completed"), vec!["full-body".to_string()]);
+        assert_eq!(tags("c3", "pending"), vec!["bust".to_string()]);
+    }
+
+    #[test]
+    fn stale_in_progress_lists_only_old_work() {
+        let env = TestEnv::new();
+        let now = chrono::Utc::now();
+        for (id, status, days_ago) in [("recent", "in-progress", 1), ("stale", "in-progress", 30), ("older", "in-progress", 60), ("idle", "pending", 90)] {
+            let mut c = commission(id, "alice", status);
+            c.updated_at = (now - chrono::Duration::days(days_ago)).to_rfc3339();
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+        
+        let stale = block_on(CommissionService::find_stale_in_progress(env.app().clone(), 14)).unwrap();
+        let ids: Vec<&str> = stale.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["older", "stale"]);
+    }
+
+    #[test]
+    fn bulk_tag_reports_updated_unchanged_and_missing() {
+        let env = TestEnv::new();
+        block_on(CommissionRepository::save(env.app(), &commission("c1", "alice", "pending"))).unwrap();
+        let mut tagged = commission("c2", "alice", "pending");
+        tagged.tags = vec!["rush".to_string()];
+        block_on(CommissionRepository::save(env.app(), &tagged)).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c3", "alice", "pending"))).unwrap();
+        
+        let ids = vec!["c1".to_string(), "c2".to_string(), "ghost".to_string()];
+        let result = block_on(CommissionService::add_tag_bulk(env.app().clone(), ids, "pending".to_string(), "rush".to_string())).unwrap();
+        assert_eq!(result.updated, vec!["c1".to_string()]);
+        assert_eq!(result.unchanged, vec!["c2".to_string()]);
+        assert_eq!(result.missing, vec!["ghost".to_string()]);
+        
+        let tags = |id: &str| block_on(CommissionRepository::find_by_id(env.app(), id, "pending")).unwrap().unwrap().tags;
+        assert_eq!(tags("c1"), vec!["rush".to_string()]);
+        assert_eq!(tags("c2"), vec!["rush".to_string()]);
+        assert!(tags("c3").is_empty());
+    }
+
+    #[test]
+    fn revenue_by_tag_counts_each_tag_a_commission_carries() {
+        let env = TestEnv::new();
+        for (id, status, price, tags) in [
+            ("c1", "completed", 1000, vec!["portrait", "color"]),
+            ("c2", "completed", 3000, vec!["color"]),
+            ("c3", "completed", 500, vec![]),
+            ("c4", "pending", 9000, vec!["portrait"]),
+        ] {
+            let mut c = commission(id, "alice", status);
+            c.price_cents = price;
+            c.tags = tags.into_iter().map(String::from).collect();
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+        
+        let revenue = block_on(CommissionService::get_revenue_by_tag(env.app().clone())).unwrap();
+        assert_eq!(revenue, vec![
+            ("color".to_string(), 4000),
+            ("portrait".to_string(), 1000),
+            (UNTAGGED_BUCKET.to_string(), 500),
+        ]);
+    }
+
+    #[test]
+    fn activity_calendar_counts_creations_per_day() {
+        let env = TestEnv::new();
+        for (id, status, created_at) in [
+            ("c1", "pending", "2026-02-14T09:00:00Z"),
+            ("c2", "completed", "2026-02-14T18:00:00Z"),
+            ("c3", "pending", "2026-05-01T12:00:00Z"),
+            ("c4", "pending", "2025-12-31T12:00:00Z"),
+            ("c5", "pending", "not a date"),
+        ] {
+            let mut c = commission(id, "alice", status);
+            c.created_at = created_at.to_string();
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+        
+        let calendar = block_on(CommissionService::get_activity_calendar(env.app().clone(), 2026)).unwrap();
+        assert_eq!(calendar, HashMap::from([("2026-02-14".to_string(), 2), ("2026-05-01".to_string(), 1)]));
+    }
+
+    #[test]
+    fn high_priority_sorts_ahead_of_older_normal_work() {
+        let env = TestEnv::new();
+        for (id, priority, created_at) in [
+            ("old_normal", "Normal", "2026-01-01T00:00:00Z"),
+            ("new_high", "High", "2026-03-01T00:00:00Z"),
+            ("oldest_low", "Low", "2025-12-01T00:00:00Z"),
+        ] {
+            let mut c = commission(id, "alice", "pending");
+            c.priority = priority.to_string();
+            c.created_at = created_at.to_string();
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+        
+        let queue = block_on(CommissionService::get_commissions_prioritized(env.app().clone(), "pending".to_string())).unwrap();
+        let ids: Vec<&str> = queue.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["new_high", "old_normal", "oldest_low"]);
+    }
+
+    #[test]
+    fn more_outstanding_work_pushes_the_clear_date_later() {
+        let env = TestEnv::new();
+        assert!(block_on(CommissionService::estimate_queue_clear_date(env.app().clone())).unwrap_err().contains("Insufficient data"));
+        
+        let mut done = commission("done", "alice", "completed");
+        done.created_at = "2026-01-01T00:00:00Z".to_string();
+        done.updated_at = "2026-01-05T00:00:00Z".to_string();
+        block_on(CommissionRepository::save(env.app(), &done)).unwrap();
+        let clear_date = || {
+            let date = block_on(CommissionService::estimate_queue_clear_date(env.app().clone())).unwrap();
+            chrono::DateTime::parse_from_rfc3339(&date).unwrap()
+        };
+        
+        block_on(CommissionRepository::save(env.app(), &commission("c1", "alice", "pending"))).unwrap();
+        let one_queued = clear_date();
+        block_on(CommissionRepository::save(env.app(), &commission("c2", "alice", "in-progress"))).unwrap();
+        let two_queued = clear_date();
+        
+        assert!(two_queued > one_queued);
+        assert!((two_queued - one_queued).num_hours() >= 4 * 24 - 1);
+    }
+
+    #[test]
+    fn archived_work_is_listed_only_when_asked_for() {
+        let env = TestEnv::new();
+        block_on(CommissionRepository::save(env.app(), &commission("c1", "alice", "completed"))).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c2", "alice", "completed"))).unwrap();
+        block_on(CommissionService::set_commission_archived(env.app().clone(), "c1".to_string(), true)).unwrap();
+        
+        let list = |include_archived: bool| {
+            let commissions = block_on(CommissionService::get_commissions_by_status(
+                env.app().clone(), "completed".to_string(), include_archived,
+            )).unwrap();
+            let mut ids: Vec<String> = commissions.into_iter().map(|c| c.id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(list(false), vec!["c2".to_string()]);
+        assert_eq!(list(true), vec!["c1".to_string(), "c2".to_string()]);
+    }
+
+    #[test]
+    fn deposit_coverage_is_paid_over_outstanding_value() {
+        let env = TestEnv::new();
+        assert_eq!(block_on(CommissionService::get_deposit_coverage(env.app().clone())).unwrap(), 0.0);
+        
+        for (id, status, price, paid) in [
+            ("deposit", "pending", 1000, 500),
+            ("unpaid", "in-progress", 3000, 0),
+            ("done", "completed", 5000, 5000),
+        ] {
+            let mut c = commission(id, "alice", status);
+            c.price_cents = price;
+            c.paid_cents = paid;
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+        
+        let coverage = block_on(CommissionService::get_deposit_coverage(env.app().clone())).unwrap();
+        assert!((coverage - 0.125).abs() < 1e-9);
+    }
+
+    #[test]
+    fn price_is_suggested_from_hours_and_the_configured_rate() {
+        let env = TestEnv::new();
+        let suggest = |hours: f64| block_on(CommissionService::suggest_price(env.app().clone(), hours));
+        assert!(suggest(2.0).is_err());
+        
+        env.configure(|config| config.pricing.hourly_rate_cents = 2500);
+        assert_eq!(suggest(2.5).unwrap(), 6250);
+        assert_eq!(suggest(0.01).unwrap(), 25);
+        assert!(suggest(-1.0).is_err());
+        assert!(suggest(f64::NAN).is_err());
+        
+        // Over the price ceiling
+        env.configure(|config| config.pricing.hourly_rate_cents = 99_999_999_999);
+        assert!(suggest(2.0).is_err());
+        
+        let mut estimated = commission("c1", "alice", "pending");
+        estimated.estimated_hours = Some(2.5);
+        block_on(CommissionService::create_commission(env.app().clone(), estimated)).unwrap();
+        block_on(CommissionService::save_commission(env.app().clone(), frontend_save("c1", serde_json::json!({})))).unwrap();
+        let saved = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        assert_eq!(saved.estimated_hours, Some(2.5));
+    }
+
+    #[test]
+    fn only_unscheduled_work_of_the_status_is_listed() {
+        let env = TestEnv::new();
+        let mut scheduled = commission("scheduled", "alice", "pending");
+        scheduled.due_date = Some("2026-03-01T00:00:00Z".to_string());
+        for work in [
+            scheduled,
+            commission("unscheduled", "alice", "pending"),
+            commission("started", "alice", "in-progress"),
+            commission("done", "alice", "completed"),
+        ] {
+            block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        }
+        let unscheduled = |status: &str| {
+            block_on(CommissionService::find_commissions_without_due_date(env.app().clone(), status.to_string()))
+                .map(|found| found.into_iter().map(|c| c.id).collect::<Vec<_>>())
+        };
+        
+        assert_eq!(unscheduled("pending").unwrap(), vec!["unscheduled"]);
+        assert_eq!(unscheduled("in-progress").unwrap(), vec!["started"]);
+        assert!(unscheduled("completed").is_err());
+    }
+
+    #[test]
+    fn price_statistics_take_the_median_of_odd_and_even_counts() {
+        let env = TestEnv::new();
+        let stats = |status: Option<&str>| {
+            block_on(CommissionService::get_price_statistics(env.app().clone(), status.map(str::to_string))).unwrap()
+        };
+        let empty = stats(None);
+        assert_eq!((empty.count, empty.min_cents, empty.max_cents, empty.mean_cents, empty.median_cents), (0, 0, 0, 0, 0));
+        
+        for (id, status, price) in [("p1", "pending", 1000), ("p2", "pending", 9000), ("p3", "pending", 2000), ("d1", "completed", 4001)] {
+            let mut work = commission(id, "alice", status);
+            work.price_cents = price;
+            block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        }
+        
+        let odd = stats(Some("pending"));
+        assert_eq!((odd.count, odd.min_cents, odd.max_cents, odd.mean_cents, odd.median_cents), (3, 1000, 9000, 4000, 2000));
+        
+        // Even counts average the middle two, rounding half a cent up
+        let even = stats(None);
+        assert_eq!((even.count, even.min_cents, even.max_cents, even.median_cents), (4, 1000, 9000, 3001));
+    }
+
+    #[test]
+    fn revert_restores_the_snapshot_from_before_later_saves() {
+        let env = TestEnv::new();
+        let before_any = chrono::Utc::now().to_rfc3339();
+        let inline = "data:image/png;base64,AAAA".to_string();
+        let mut first = commission("c1", "alice", "pending");
+        first.images = vec![inline.clone()];
+        block_on(CommissionService::create_commission(env.app().clone(), first)).unwrap();
+        let after_first = chrono::Utc::now().to_rfc3339();
+        
+        let revert = |to: &str| {
+            block_on(CommissionService::revert_commission(env.app().clone(), "c1".to_string(), "pending".to_string(), to.to_string()))
+        };
+        assert!(revert(&before_any).unwrap_err().contains("No saved snapshot"));
+        
+        let mut second = commission("c1", "alice", "pending");
+        second.title = "Second".to_string();
+        second.images = vec![inline.clone()];
+        block_on(CommissionService::create_commission(env.app().clone(), second)).unwrap();
+        block_on(CommissionService::update_commission_title(env.app().clone(), "c1".to_string(), "pending".to_string(), "Third".to_string())).unwrap();
+        
+        let events_before = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap().events;
+        let restored = revert(&after_first).unwrap();
+        assert_eq!(restored.title, "Title c1");
+        assert_eq!(restored.events.len(), events_before.len() + 1);
+        assert_eq!(restored.events.last().map(|event| event.kind.as_str()), Some("reverted"));
+        assert_eq!(restored.images, vec![inline]);
+        assert!(restored.integrity_warning.is_none());
+        let saved = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        assert_eq!(saved.title, "Title c1");
+        
+        // Inline images stay out of the log
+        let audit = AuditRepository::read_lines(env.app()).unwrap();
+        assert!(audit.iter().all(|line| !line.contains("data:image")));
+        assert_eq!(audit.iter().filter(|line| line.contains("\"revert\"")).count(), 1);
+    }
+
+    #[test]
+    fn bulk_and_single_field_edits_are_snapshotted() {
+        let env = TestEnv::new();
+        let mut tagged = commission("c1", "alice", "pending");
+        tagged.tags = vec!["sketch".to_string()];
+        block_on(CommissionRepository::save(env.app(), &tagged)).unwrap();
+        
+        block_on(CommissionService::rename_tag(env.app().clone(), "sketch".to_string(), "lineart".to_string())).unwrap();
+        block_on(CommissionService::add_tag_bulk(env.app().clone(), vec!["c1".to_string()], "pending".to_string(), "rush".to_string())).unwrap();
+        block_on(CommissionService::set_commission_cover(env.app().clone(), "c1".to_string(), "pending".to_string(), None)).unwrap();
+        block_on(CommissionService::change_commission_id(env.app().clone(), "pending".to_string(), "c1".to_string(), "c2".to_string())).unwrap();
+        
+        let snapshots: Vec<(String, Vec<String>)> = AuditRepository::read_lines(env.app()).unwrap()
+            .iter()
+            .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+            .filter_map(|entry| {
+                let snapshot = CommissionRepository::parse_commission(&entry.snapshot?).ok()?;
+                Some((entry.entity_id, snapshot.tags))
+            })
+            .collect();
+        let lineart = vec!["lineart".to_string()];
+        let rush = vec!["lineart".to_string(), "rush".to_string()];
+        assert_eq!(snapshots, vec![
+            ("c1".to_string(), lineart),
+            ("c1".to_string(), rush.clone()),
+            ("c1".to_string(), rush.clone()),
+            ("c2".to_string(), rush),
+        ]);
+    }
+
+    #[test]
+    fn board_puts_each_commission_in_its_column() {
+        let env = TestEnv::new();
+        let mut newer = commission("p-new", "alice", "pending");
+        newer.created_at = "2026-02-01T00:00:00Z".to_string();
+        let mut archived = commission("d-archived", "alice", "completed");
+        archived.archived = true;
+        let mut works = vec![
+            newer,
+            commission("p-old", "alice", "pending"),
+            commission("started", "alice", "in-progress"),
+            commission("dropped", "alice", "cancelled"),
+            archived,
+        ];
+        for day in 1..=BOARD_COMPLETED_LIMIT + 1 {
+            let mut done = commission(&format!("d{}", day), "alice", "completed");
+            done.updated_at = format!("2026-03-{:02}T00:00:00Z", day);
+            works.push(done);
+        }
+        for work in works {
+            block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        }
+        
+        let board = block_on(CommissionService::get_board(env.app().clone())).unwrap();
+        let ids = |column: &BoardColumn| column.commissions.iter().map(|c| c.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&board.pending), vec!["p-old", "p-new"]);
+        assert_eq!(ids(&board.in_progress), vec!["started"]);
+        assert_eq!(ids(&board.cancelled), vec!["dropped"]);
+        assert_eq!(board.pending.total_cents, 2000);
+        
+        // The column is capped, but its totals cover all of it
+        let completed = ids(&board.completed);
+        assert_eq!(completed.len(), BOARD_COMPLETED_LIMIT);
+        assert_eq!(completed[0], format!("d{}", BOARD_COMPLETED_LIMIT + 1));
+        assert!(!completed.contains(&"d1".to_string()));
+        assert_eq!(board.completed.count, BOARD_COMPLETED_LIMIT + 1);
+        assert_eq!(board.completed.total_cents, 1000 * (BOARD_COMPLETED_LIMIT as i64 + 1));
+    }
+
+    #[test]
+    fn refund_follows_each_policy_for_partly_paid_work() {
+        let env = TestEnv::new();
+        let mut work = commission("c1", "alice", "in-progress");
+        work.price_cents = 10_000;
+        work.deposit_cents = 2_000;
+        work.paid_cents = 5_000;
+        block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        let refund = |kind: &str, kept_percent: u8| {
+            let policy = RefundPolicy { kind: kind.to_string(), kept_percent };
+            block_on(CommissionService::compute_refund(env.app().clone(), "c1".to_string(), "in-progress".to_string(), policy))
+        };
+        
+        assert_eq!(refund("full_refund", 0).unwrap(), 5_000);
+        assert_eq!(refund("keep_deposit", 0).unwrap(), 3_000);
+        assert_eq!(refund("percentage_kept", 30).unwrap(), 2_000);
+        // Keeping more than was paid refunds nothing rather than asking for more
+        assert_eq!(refund("percentage_kept", 60).unwrap(), 0);
+        assert!(refund("percentage_kept", 101).is_err());
+        assert!(refund("store_credit", 0).is_err());
+    }
+
+    #[test]
+    fn cancelled_work_moves_to_its_folder_and_stops_counting_as_outstanding() {
+        let env = TestEnv::new();
+        let mut part_paid = commission("c1", "alice", "pending");
+        part_paid.paid_cents = 500;
+        block_on(CommissionRepository::save(env.app(), &part_paid)).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c2", "alice", "pending"))).unwrap();
+        assert_eq!(block_on(CommissionService::get_deposit_coverage(env.app().clone())).unwrap(), 0.25);
+        
+        block_on(CommissionService::move_commission(env.app().clone(), "c2".to_string(), "pending".to_string(), "cancelled".to_string())).unwrap();
+        
+        let cancelled = block_on(CommissionRepository::find_files_by_status(env.app(), "cancelled")).unwrap();
+        assert_eq!(cancelled.len(), 1);
+        assert!(cancelled[0].0.starts_with(env.data_dir().join("cancelled")));
+        assert_eq!(block_on(CommissionService::get_deposit_coverage(env.app().clone())).unwrap(), 0.5);
+        let board = block_on(CommissionService::get_board(env.app().clone())).unwrap();
+        assert_eq!((board.pending.count, board.cancelled.count), (1, 1));
+    }
+
+    #[test]
+    fn reference_numbers_are_sequential_and_never_reused() {
+        let env = TestEnv::new();
+        let create = |id: &str| block_on(CommissionService::create_commission(env.app().clone(), commission(id, "alice", "pending"))).unwrap();
+        let reference = |id: &str| block_on(CommissionRepository::find_by_id(env.app(), id, "pending")).unwrap().unwrap().reference_number;
+        create("c1");
+        create("c2");
+        create("c1");
+        assert_eq!((reference("c1"), reference("c2")), (Some(1), Some(2)));
+        
+        let found = block_on(CommissionService::get_commission_by_reference(env.app().clone(), 2)).unwrap();
+        assert_eq!(found.map(|c| c.id), Some("c2".to_string()));
+        assert!(block_on(CommissionService::get_commission_by_reference(env.app().clone(), 9)).unwrap().is_none());
+        
+        // Legacy records wait for the backfill
+        block_on(CommissionRepository::save(env.app(), &commission("legacy", "alice", "pending"))).unwrap();
+        assert_eq!(reference("legacy"), None);
+        assert_eq!(block_on(CommissionService::backfill_reference_numbers(env.app().clone())).unwrap(), 1);
+        assert_eq!(reference("legacy"), Some(3));
+        
+        block_on(CommissionService::delete_commission(env.app().clone(), "legacy".to_string(), "pending".to_string())).unwrap();
+        create("c4");
+        assert_eq!(reference("c4"), Some(4));
+        
+        // A lost counter picks up after the highest number still on disk
+        std::fs::remove_file(env.data_dir().join(REFERENCE_COUNTER_FILE)).unwrap();
+        create("c5");
+        assert_eq!(reference("c5"), Some(5));
     }
 }
@@ -0,0 +1,568 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Runtime};
+use crate::repository::{AuditRepository, CommissionRepository, ConfigRepository, FileStorage};
+use crate::repository::audit_repository::AuditEntry;
+use crate::repository::commission_repository::{COMMISSION_FOLDERS, REFERENCE_COUNTER_FILE};
+use crate::repository::file_storage::DATA_DIR_ENV_VAR;
+use super::backup_service::BackupService;
+use super::validation_service::{ValidationLimits, ValidationService, ALLOWED_IMAGE_EXTENSIONS};
+
+// Leftovers younger than this may still belong to a running write
+const STALE_TEMP_FILE_AGE: Duration = Duration::from_secs(60 * 60);
+
+// Typed back by the user before reset_all_data deletes anything
+const RESET_CONFIRMATION: &str = "DELETE ALL MY DATA";
+
+// Typed back by the user before delete_completed_in_range deletes anything
+const PURGE_CONFIRMATION: &str = "DELETE COMPLETED COMMISSIONS";
+
+// Keeps the debug tree payload small however big the data directory is
+const MAX_TREE_ENTRIES: usize = 2000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppInfo {
+    pub version: String,
+    pub data_directory: String,
+    pub data_directory_source: String, // "env", "config" or "default"
+    pub active_profile: Option<String>, // Always None: there is a single profile for now
+    pub limits: ValidationLimits,
+    pub image_formats: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TreeNode {
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64, // Bytes for files; 0 for folders
+    pub children: Vec<TreeNode>,
+    pub truncated: bool, // Children were left out because of the depth or entry limit
+}
+
+pub struct DataService;
+
+impl DataService {
+    /// Everything the frontend needs at startup, in one call.
+    pub async fn get_app_info<R: Runtime>(app_handle: AppHandle<R>) -> Result<AppInfo, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        
+        // Same precedence as get_app_data_dir
+        let data_directory_source = if std::env::var_os(DATA_DIR_ENV_VAR).is_some_and(|dir| !dir.is_empty()) {
+            "env"
+        } else if ConfigRepository::load(&app_handle)?.data_directory.is_some() {
+            "config"
+        } else {
+            "default"
+        };
+        
+        Ok(AppInfo {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            data_directory: data_dir.to_string_lossy().to_string(),
+            data_directory_source: data_directory_source.to_string(),
+            active_profile: None,
+            limits: ValidationService::limits(),
+            image_formats: ALLOWED_IMAGE_EXTENSIONS.iter().map(|ext| ext.to_string()).collect(),
+        })
+    }
+
+    /// Writes the audit log to a CSV in the destination folder and returns the file path.
+    /// Lines that can't be parsed are skipped with a warning.
+    pub async fn export_audit_csv<R: Runtime>(app_handle: AppHandle<R>, dest: String) -> Result<String, String> {
+        let dest_dir = PathBuf::from(&dest);
+        if !dest_dir.is_dir() {
+            return Err("CSV destination must be an existing folder".to_string());
+        }
+        
+        let mut csv = String::from("timestamp,action,entity_type,entity_id\n");
+        let mut exported = 0;
+        for (index, line) in AuditRepository::read_lines(&app_handle)?.iter().enumerate() {
+            let entry: AuditEntry = match serde_json::from_str(line) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    eprintln!("Skipping malformed audit line {}: {}", index + 1, e);
+                    continue;
+                }
+            };
+            
+            let row = [&entry.timestamp, &entry.action, &entry.entity_type, &entry.entity_id]
+                .map(|value| FileStorage::csv_field(value));
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+            exported += 1;
+        }
+        
+        let csv_file = dest_dir.join(format!("audit_log_{}.csv", chrono::Utc::now().format("%Y-%m-%d")));
+        fs::write(&csv_file, csv)
+            .map_err(|e| format!("Failed to write CSV: {}", e))?;
+        
+        println!("Exported {} audit entries to {}", exported, csv_file.display());
+        
+        Ok(csv_file.to_string_lossy().to_string())
+    }
+
+    pub async fn relocate_data_directory<R: Runtime>(
+        app_handle: AppHandle<R>,
+        new_path: String,
+        remove_old: bool,
+    ) -> Result<(), String> {
+        // The saved location would be ignored while the override is set
+        if std::env::var_os(DATA_DIR_ENV_VAR).is_some_and(|dir| !dir.is_empty()) {
+            return Err(format!("Data directory is set by {}; unset it before relocating", DATA_DIR_ENV_VAR));
+        }
+        
+        let new_dir = Self::validate_new_data_path(&new_path)?;
+        let old_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        
+        if new_dir.starts_with(&old_dir) || old_dir.starts_with(&new_dir) {
+            return Err("New data directory cannot be inside the current one (or contain it)".to_string());
+        }
+        
+        // Refuse to merge into an existing folder with content
+        if new_dir.exists() {
+            let is_empty = fs::read_dir(&new_dir)
+                .map_err(|e| format!("Failed to read new data directory: {}", e))?
+                .next()
+                .is_none();
+            if !is_empty {
+                return Err("New data directory must be empty".to_string());
+            }
+        }
+        
+        fs::create_dir_all(&new_dir)
+            .map_err(|e| format!("Failed to create new data directory: {}", e))?;
+        
+        println!("Relocating data from {:?} to {:?}", old_dir, new_dir);
+        
+        let options = fs_extra::dir::CopyOptions::new().content_only(true);
+        if let Err(e) = fs_extra::dir::copy(&old_dir, &new_dir, &options) {
+            let _ = fs::remove_dir_all(&new_dir);
+            return Err(format!("Failed to copy data directory: {}", e));
+        }
+        
+        // Verify the copy before touching the configuration or the source
+        let source_stats = FileStorage::directory_stats(&old_dir)?;
+        let copy_stats = FileStorage::directory_stats(&new_dir)?;
+        if source_stats != copy_stats {
+            let _ = fs::remove_dir_all(&new_dir);
+            return Err(format!(
+                "Copy verification failed ({} files / {} bytes copied, expected {} files / {} bytes)",
+                copy_stats.0, copy_stats.1, source_stats.0, source_stats.1
+            ));
+        }
+        
+        let mut config = ConfigRepository::load(&app_handle)?;
+        config.data_directory = Some(new_dir.to_string_lossy().to_string());
+        ConfigRepository::save(&app_handle, &config)?;
+        
+        if remove_old {
+            fs::remove_dir_all(&old_dir)
+                .map_err(|e| format!("Data relocated, but failed to remove old data directory: {}", e))?;
+        }
+        
+        Ok(())
+    }
+
+    /// Describes the data directory as nested folders and files, down to max_depth levels.
+    /// Only metadata is read, symlinks are listed but never followed, and at most
+    /// MAX_TREE_ENTRIES entries are returned in total.
+    pub async fn get_data_tree<R: Runtime>(app_handle: AppHandle<R>, max_depth: usize) -> Result<TreeNode, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut remaining = MAX_TREE_ENTRIES;
+        
+        let mut root = Self::tree_node(&data_dir, max_depth, &mut remaining)?;
+        root.name = "Data".to_string();
+        
+        Ok(root)
+    }
+
+    fn tree_node(path: &Path, depth_left: usize, remaining: &mut usize) -> Result<TreeNode, String> {
+        let metadata = fs::symlink_metadata(path)
+            .map_err(|e| format!("Failed to read metadata for {}: {}", path.display(), e))?;
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        
+        if !metadata.is_dir() {
+            return Ok(TreeNode {
+                name,
+                is_dir: false,
+                size: if metadata.is_file() { metadata.len() } else { 0 },
+                children: Vec::new(),
+                truncated: false,
+            });
+        }
+        
+        let mut node = TreeNode {
+            name,
+            is_dir: true,
+            size: 0,
+            children: Vec::new(),
+            truncated: false,
+        };
+        
+        let mut entries: Vec<PathBuf> = fs::read_dir(path)
+            .map_err(|e| format!("Failed to read directory: {}", e))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        if entries.is_empty() {
+            return Ok(node);
+        }
+        if depth_left == 0 {
+            node.truncated = true;
+            return Ok(node);
+        }
+        entries.sort();
+        
+        for entry in entries {
+            if *remaining == 0 {
+                node.truncated = true;
+                break;
+            }
+            *remaining -= 1;
+            node.children.push(Self::tree_node(&entry, depth_left - 1, remaining)?);
+        }
+        
+        Ok(node)
+    }
+
+    /// Turns on the year-partitioned history layout and moves every completed commission into
+    /// history/<year>/<client>/. Safe to run again; returns how many files were moved.
+    pub async fn partition_history_by_year<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+        // Saved first, so anything written while this runs already lands in the new layout
+        let mut config = ConfigRepository::load(&app_handle)?;
+        config.storage.partition_history_by_year = true;
+        ConfigRepository::save(&app_handle, &config)?;
+        
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut moved = 0;
+        
+        for (path, commission) in CommissionRepository::find_files_by_status(&app_handle, "completed").await? {
+            if commission.status != "completed" {
+                continue;
+            }
+            
+            let target = CommissionRepository::file_path(&data_dir, &commission, true);
+            if target == path {
+                continue;
+            }
+            
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create history directory: {}", e))?;
+            }
+            fs::rename(&path, &target)
+                .map_err(|e| format!("Failed to move {}: {}", path.display(), e))?;
+            moved += 1;
+            
+            // Only succeeds once the old client folder is empty
+            if let Some(old_client_dir) = path.parent() {
+                fs::remove_dir(old_client_dir).ok();
+            }
+        }
+        
+        println!("Partitioned history by year: moved {} commissions", moved);
+        
+        Ok(moved)
+    }
+
+    pub async fn cleanup_temp_files<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let now = SystemTime::now();
+        let mut removed = 0;
+        
+        for path in FileStorage::list_files_recursive(&data_dir)? {
+            let extension = path.extension().and_then(|s| s.to_str()).unwrap_or_default();
+            if extension != "tmp" && extension != "lock" {
+                continue;
+            }
+            
+            let is_stale = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age >= STALE_TEMP_FILE_AGE);
+            
+            if is_stale {
+                println!("Removing stale temp file {:?}", path);
+                FileStorage::delete_file(&path)?;
+                removed += 1;
+            }
+        }
+        
+        Ok(removed)
+    }
+
+    /// Deletes every client and commission after taking a backup. Nothing is touched unless
+    /// the confirmation matches RESET_CONFIRMATION exactly; backups and config are kept.
+    pub async fn reset_all_data<R: Runtime>(app_handle: AppHandle<R>, confirmation: String) -> Result<(), String> {
+        if confirmation != RESET_CONFIRMATION {
+            return Err(format!("Type \"{}\" to confirm deleting all data", RESET_CONFIRMATION));
+        }
+        
+        let backup_file = BackupService::create_backup(app_handle.clone()).await?;
+        println!("Backed up data to {} before resetting", backup_file);
+        
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        for folder in std::iter::once("clients").chain(COMMISSION_FOLDERS) {
+            let folder_path = data_dir.join(folder);
+            if folder_path.exists() {
+                fs::remove_dir_all(&folder_path)
+                    .map_err(|e| format!("Failed to delete {} folder: {}", folder, e))?;
+            }
+        }
+        // The log holds snapshots of the deleted records, and a fresh start numbers from 1 again
+        FileStorage::delete_file(&AuditRepository::log_path(&data_dir))?;
+        FileStorage::delete_file(&data_dir.join(REFERENCE_COUNTER_FILE))?;
+        FileStorage::ensure_data_folders(&data_dir)?;
+        
+        Ok(())
+    }
+
+    /// Permanently deletes completed commissions whose updated_at (the completion time) falls
+    /// within start..=end, and clears their snapshots from the audit log. No backup is taken:
+    /// this is for getting rid of records. Their image files are left for find_orphaned_images.
+    pub async fn delete_completed_in_range<R: Runtime>(
+        app_handle: AppHandle<R>,
+        start: String,
+        end: String,
+        confirmation: String,
+    ) -> Result<usize, String> {
+        let parse = |value: &str, name: &str| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .map(|t| t.with_timezone(&chrono::Utc))
+                .map_err(|_| format!("{} must be an RFC3339 timestamp", name))
+        };
+        let start = parse(&start, "Start")?;
+        let end = parse(&end, "End")?;
+        if end < start {
+            return Err("End must not be before start".to_string());
+        }
+        if confirmation != PURGE_CONFIRMATION {
+            return Err(format!("Type \"{}\" to confirm deleting these commissions", PURGE_CONFIRMATION));
+        }
+        
+        let mut deleted = HashSet::new();
+        for (path, commission) in CommissionRepository::find_files_by_status(&app_handle, "completed").await? {
+            let Ok(completed_at) = chrono::DateTime::parse_from_rfc3339(&commission.updated_at) else {
+                continue;
+            };
+            let completed_at = completed_at.with_timezone(&chrono::Utc);
+            if completed_at < start || completed_at > end {
+                continue;
+            }
+            
+            FileStorage::delete_file(&path)?;
+            AuditRepository::record(&app_handle, "delete", "commission", &commission.id);
+            deleted.insert(commission.id);
+        }
+        
+        AuditRepository::remove_snapshots(&app_handle, "commission", &deleted)?;
+        println!("Deleted {} completed commissions", deleted.len());
+        
+        Ok(deleted.len())
+    }
+
+    fn validate_new_data_path(new_path: &str) -> Result<PathBuf, String> {
+        if new_path.is_empty() {
+            return Err("Data directory path cannot be empty".to_string());
+        }
+        
+        if new_path.contains("..") || new_path.contains('\0') {
+            return Err("Invalid data directory path - path traversal detected".to_string());
+        }
+        
+        let new_dir = PathBuf::from(new_path);
+        if !new_dir.is_absolute() {
+            return Err("Data directory path must be absolute".to_string());
+        }
+        
+        if new_dir.exists() && !new_dir.is_dir() {
+            return Err("Data directory path must be a directory".to_string());
+        }
+        
+        Ok(new_dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{commission, TestEnv};
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn relocate_copies_data_to_the_new_directory() {
+        let env = TestEnv::new();
+        block_on(CommissionRepository::save(env.app(), &commission("c1", "alice", "pending"))).unwrap();
+        // Relocation refuses to run under the env override, so point the config at the data instead
+        env.configure(|config| config.data_directory = Some(env.data_dir().to_string_lossy().to_string()));
+        std::env::remove_var(DATA_DIR_ENV_VAR);
+        
+        let new_dir = env.data_dir().with_extension("moved");
+        let _ = fs::remove_dir_all(&new_dir);
+        let result = block_on(DataService::relocate_data_directory(
+            env.app().clone(), new_dir.to_string_lossy().to_string(), false,
+        ));
+        let copied = CommissionRepository::file_path(&new_dir, &commission("c1", "alice", "pending"), false).exists();
+        let configured = ConfigRepository::load(env.app()).unwrap().data_directory;
+        let _ = fs::remove_dir_all(&new_dir);
+        
+        result.unwrap();
+        assert!(copied);
+        assert_eq!(configured, Some(new_dir.to_string_lossy().to_string()));
+        assert!(env.data_dir().join("pendings").exists());
+    }
+
+    #[test]
+    fn cleanup_removes_stale_temp_files() {
+        let env = TestEnv::new();
+        let pendings = env.data_dir().join("pendings");
+        fs::create_dir_all(&pendings).unwrap();
+        let stale = pendings.join("c1.json.tmp");
+        let fresh = pendings.join("c2.json.tmp");
+        fs::write(&stale, "{").unwrap();
+        fs::write(&fresh, "{").unwrap();
+        let old = SystemTime::now() - STALE_TEMP_FILE_AGE - Duration::from_secs(60);
+        fs::File::options().write(true).open(&stale).unwrap().set_modified(old).unwrap();
+        
+        assert_eq!(block_on(DataService::cleanup_temp_files(env.app().clone())).unwrap(), 1);
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+    }
+
+    #[test]
+    fn app_info_reports_version_data_dir_and_limits() {
+        let env = TestEnv::new();
+        let info = block_on(DataService::get_app_info(env.app().clone())).unwrap();
+        
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(PathBuf::from(&info.data_directory), env.data_dir());
+        assert_eq!(info.data_directory_source, "env");
+        assert!(info.limits.max_id_length > 0);
+        assert!(info.image_formats.contains(&"png".to_string()));
+    }
+
+    #[test]
+    fn audit_csv_skips_malformed_lines() {
+        let env = TestEnv::new();
+        AuditRepository::append(env.app(), "save", "client", "alice", None).unwrap();
+        let log_file = AuditRepository::log_path(env.data_dir());
+        fs::write(&log_file, fs::read_to_string(&log_file).unwrap() + "not json\n").unwrap();
+        AuditRepository::append(env.app(), "delete", "commission", "c1", Some("{}".to_string())).unwrap();
+        
+        let path = block_on(DataService::export_audit_csv(env.app().clone(), env.data_dir().to_string_lossy().to_string())).unwrap();
+        
+        let csv = fs::read_to_string(path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "timestamp,action,entity_type,entity_id");
+        assert!(lines[1].ends_with(",save,client,alice"));
+        assert!(lines[2].ends_with(",delete,commission,c1"));
+    }
+
+    #[test]
+    fn data_tree_reflects_the_directory_up_to_the_depth() {
+        let env = TestEnv::new();
+        let client_dir = env.data_dir().join("pendings").join("Alice");
+        fs::create_dir_all(client_dir.join("images")).unwrap();
+        fs::write(client_dir.join("c1.json"), "{}").unwrap();
+        fs::write(client_dir.join("images").join("c1_a.png"), [0; 10]).unwrap();
+        
+        let tree = block_on(DataService::get_data_tree(env.app().clone(), 3)).unwrap();
+        assert_eq!(tree.name, "Data");
+        let pendings = tree.children.iter().find(|node| node.name == "pendings").unwrap();
+        let alice = &pendings.children[0];
+        assert_eq!(alice.name, "Alice");
+        let names: Vec<&str> = alice.children.iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(names, vec!["c1.json", "images"]);
+        assert_eq!((alice.children[0].is_dir, alice.children[0].size), (false, 2));
+        
+        // images is at depth 3, so its contents are left out
+        assert!(alice.children[1].is_dir && alice.children[1].truncated);
+        assert!(alice.children[1].children.is_empty());
+    }
+
+    #[test]
+    fn partitioning_moves_history_into_year_folders() {
+        let env = TestEnv::new();
+        for (id, updated_at) in [("old", "2025-06-01T00:00:00Z"), ("new", "2026-02-01T00:00:00Z")] {
+            let mut c = commission(id, "alice", "completed");
+            c.updated_at = updated_at.to_string();
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+        
+        assert_eq!(block_on(DataService::partition_history_by_year(env.app().clone())).unwrap(), 2);
+        
+        let history = env.data_dir().join("history");
+        let files = block_on(CommissionRepository::find_files_by_status(env.app(), "completed")).unwrap();
+        assert_eq!(files.len(), 2);
+        for (path, commission) in &files {
+            let year = &commission.updated_at[..4];
+            assert!(path.starts_with(history.join(year)), "{} not under {}", path.display(), year);
+        }
+        let flat_file = CommissionRepository::file_path(env.data_dir(), &files[0].1, false);
+        assert!(!flat_file.parent().unwrap().exists());
+        assert_eq!(block_on(DataService::partition_history_by_year(env.app().clone())).unwrap(), 0);
+    }
+
+    #[test]
+    fn reset_needs_the_exact_phrase_and_backs_up_first() {
+        let env = TestEnv::new();
+        let mut work = commission("c1", "alice", "pending");
+        work.reference_number = Some(CommissionRepository::next_reference_number(env.app()).unwrap());
+        block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        AuditRepository::record_snapshot(env.app(), "save", "commission", "c1", &work);
+        
+        assert!(block_on(DataService::reset_all_data(env.app().clone(), "delete all my data".to_string())).is_err());
+        assert!(block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().is_some());
+        
+        block_on(DataService::reset_all_data(env.app().clone(), RESET_CONFIRMATION.to_string())).unwrap();
+        
+        assert!(block_on(CommissionRepository::find_by_status(env.app(), "pending")).unwrap().is_empty());
+        assert!(AuditRepository::read_lines(env.app()).unwrap().is_empty());
+        assert_eq!(CommissionRepository::next_reference_number(env.app()).unwrap(), 1);
+        
+        let backups: Vec<PathBuf> = fs::read_dir(env.data_dir().join("backups")).unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(backups.len(), 1);
+        let info = block_on(BackupService::verify_backup(env.app().clone(), backups[0].to_string_lossy().to_string())).unwrap();
+        assert!(info.file_count >= 1);
+    }
+
+    #[test]
+    fn purge_deletes_only_completed_work_inside_the_range() {
+        let env = TestEnv::new();
+        let completed_on = |id: &str, date: &str| {
+            let mut done = commission(id, "alice", "completed");
+            done.updated_at = format!("{}T12:00:00Z", date);
+            done
+        };
+        for work in [
+            completed_on("before", "2025-12-31"),
+            completed_on("first", "2026-01-01"),
+            completed_on("last", "2026-01-31"),
+            completed_on("after", "2026-02-01"),
+            commission("open", "alice", "pending"),
+        ] {
+            block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        }
+        let purge = |start: &str, end: &str, confirmation: &str| {
+            block_on(DataService::delete_completed_in_range(env.app().clone(), start.to_string(), end.to_string(), confirmation.to_string()))
+        };
+        
+        assert!(purge("2026-01-01T00:00:00Z", "2026-02-01T00:00:00Z", "yes").is_err());
+        assert!(purge("2026-02-01T00:00:00Z", "2026-01-01T00:00:00Z", PURGE_CONFIRMATION).is_err());
+        assert!(purge("January", "2026-02-01T00:00:00Z", PURGE_CONFIRMATION).is_err());
+        assert_eq!(purge("2026-01-01T00:00:00Z", "2026-02-01T00:00:00Z", PURGE_CONFIRMATION).unwrap(), 2);
+        
+        let mut remaining: Vec<String> = block_on(CommissionRepository::find_by_status(env.app(), "completed")).unwrap()
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        remaining.sort();
+        assert_eq!(remaining, vec!["after", "before"]);
+        assert!(block_on(CommissionRepository::find_by_id(env.app(), "open", "pending")).unwrap().is_some());
+    }
+}
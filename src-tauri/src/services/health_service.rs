@@ -0,0 +1,847 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+use crate::repository::{AuditRepository, ClientRepository, CommissionRepository, FileStorage};
+use crate::models::{Client, Commission};
+use crate::repository::commission_repository::COMMISSION_FOLDERS;
+use super::image_service::ImageService;
+use super::validation_service::ValidationService;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthIssue {
+    pub severity: String, // "error", "warning", "info"
+    pub category: String, // "unparseable", "missing_image", "id_mismatch", "misfiled", "orphaned_image"
+    pub path: String,     // Relative to the data directory
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub files_checked: usize,
+    pub healthy: bool, // No errors or warnings; info-level findings don't count
+    pub issues: Vec<HealthIssue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EnumIssue {
+    pub path: String, // Relative to the data directory
+    pub commission_id: String,
+    pub field: String, // "status", "payment_status" or "priority"
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimestampIssue {
+    pub path: String, // Relative to the data directory
+    pub commission_id: String,
+    pub kind: String, // "updated_before_created", "created_in_future", "updated_in_future"
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderAudit {
+    pub path: String, // Relative to the data directory
+    pub commission_files: usize,
+    pub client_id: Option<String>, // Client record whose name maps to this folder
+    pub orphaned: bool, // No client record maps to this folder
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftIssue {
+    pub path: String, // Relative to the data directory
+    pub commission_id: String,
+    pub client_name: String,
+    pub folder_name: String, // Client folder the file is in
+    pub expected_folder: String, // Sanitized client_name
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OversizedRecord {
+    pub path: String, // Relative to the data directory
+    pub commission_id: Option<String>, // None when the file doesn't parse
+    pub size_bytes: u64,
+    pub inline_images: usize, // Data URLs that could move out to image files
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingClient {
+    pub client_id: String,
+    pub client_name: String, // From the first commission found; stubs are named after it
+    pub commission_count: usize,
+    pub stub_created: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SplitGroup {
+    pub client_id: String,
+    pub folders: Vec<String>, // Relative to the data directory
+    pub target_folder: String, // Where a merge puts them: named after the client record when there is one
+    pub merged: usize, // Commissions moved into target_folder; 0 unless merging
+    pub conflicts: Vec<String>, // Commission ids left in place: target_folder already has a file by that name
+}
+
+// Clock drift between machines syncing the data folder shouldn't count as an anomaly
+const FUTURE_TOLERANCE_MINUTES: i64 = 5;
+
+pub struct HealthService;
+
+impl HealthService {
+    /// Scans the whole data directory for problems. Read-only: nothing is repaired or moved.
+    pub async fn run_health_check<R: Runtime>(app_handle: AppHandle<R>) -> Result<HealthReport, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut issues = Vec::new();
+        let mut files_checked = 0;
+
+        // Clients: clients/<id>.json
+        for (path, content) in FileStorage::read_directory_json_entries(&data_dir.join("clients"))? {
+            files_checked += 1;
+            let relative = Self::relative_path(&data_dir, &path);
+
+            match serde_json::from_str::<Client>(&content) {
+                Ok(client) => {
+                    if path.file_stem().and_then(|s| s.to_str()) != Some(client.id.as_str()) {
+                        issues.push(Self::issue("warning", "id_mismatch", &relative,
+                            format!("File name does not match client id {}", client.id)));
+                    }
+                }
+                Err(e) => issues.push(Self::issue("error", "unparseable", &relative,
+                    format!("Client file cannot be read: {}", e))),
+            }
+        }
+
+        // Commissions: <folder>/<client name>/<id>_<created_at>.json
+        let mut commissions: Vec<Commission> = Vec::new();
+        for folder in COMMISSION_FOLDERS {
+            for client_dir in CommissionRepository::client_dirs(&data_dir.join(folder))? {
+                for (path, content) in FileStorage::read_directory_json_entries(&client_dir)? {
+                    files_checked += 1;
+                    let relative = Self::relative_path(&data_dir, &path);
+
+                    match CommissionRepository::parse_commission(&content) {
+                        Ok(commission) => {
+                            Self::check_commission_file(&data_dir, folder, &path, &relative, &commission, &mut issues);
+                            commissions.push(commission);
+                        }
+                        Err(e) => issues.push(Self::issue("error", "unparseable", &relative,
+                            format!("Commission file cannot be read: {}", e))),
+                    }
+                }
+            }
+        }
+
+        // Images: referenced but missing, or present but unreferenced
+        for commission in &commissions {
+            let client_folder = FileStorage::sanitize_filename(&commission.client_name);
+
+            for image_path in commission.images.iter().filter(|p| !p.is_empty() && !p.starts_with("data:")) {
+                if ImageService::resolve_image_path(&data_dir, &commission.client_name, &commission.status, image_path).is_none() {
+                    let expected = format!("{}/{}/{}", CommissionRepository::folder_for_status(&commission.status), client_folder, image_path);
+                    issues.push(Self::issue("warning", "missing_image", &expected,
+                        format!("Image referenced by commission {} is missing", commission.id)));
+                }
+            }
+        }
+
+        for folder in COMMISSION_FOLDERS {
+            let folder_dir = data_dir.join(folder);
+            files_checked += FileStorage::list_files_recursive(&folder_dir)?
+                .iter()
+                .filter(|path| ImageService::is_stored_image(&folder_dir, path))
+                .count();
+        }
+
+        for orphan in ImageService::orphaned_image_files(&data_dir, &commissions)? {
+            issues.push(Self::issue("info", "orphaned_image", &Self::relative_path(&data_dir, &orphan),
+                "Image is not referenced by any commission".to_string()));
+        }
+
+        let healthy = issues.iter().all(|issue| issue.severity == "info");
+
+        Ok(HealthReport {
+            files_checked,
+            healthy,
+            issues,
+        })
+    }
+
+    /// Lists commissions whose stored status, payment status or priority is outside the allowed values.
+    /// Files are read as stored, so lenient status loading doesn't hide anything. Read-only.
+    pub async fn find_invalid_enum_values<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<EnumIssue>, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut issues = Vec::new();
+
+        for folder in COMMISSION_FOLDERS {
+            for client_dir in CommissionRepository::client_dirs(&data_dir.join(folder))? {
+                for (path, content) in FileStorage::read_directory_json_entries(&client_dir)? {
+                    // Unparseable files are the health check's business
+                    let Ok(commission) = CommissionRepository::parse_commission(&content) else {
+                        continue;
+                    };
+
+                    let fields = [
+                        ("status", &commission.status, ValidationService::validate_status(&commission.status)),
+                        ("payment_status", &commission.payment_status, ValidationService::validate_payment_status(&commission.payment_status)),
+                        ("priority", &commission.priority, ValidationService::validate_priority(&commission.priority)),
+                    ];
+                    for (field, value, result) in fields {
+                        if result.is_err() {
+                            issues.push(EnumIssue {
+                                path: Self::relative_path(&data_dir, &path),
+                                commission_id: commission.id.clone(),
+                                field: field.to_string(),
+                                value: value.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        issues.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.field.cmp(&b.field)));
+
+        Ok(issues)
+    }
+
+    /// Lists every client folder under pendings and history with its number of commission
+    /// files, and flags folders no client record maps to. Read-only.
+    pub async fn audit_client_folders<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<FolderAudit>, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let clients = ClientRepository::find_all(&app_handle).await?;
+
+        let mut audits = Vec::new();
+        for folder in COMMISSION_FOLDERS {
+            let client_dirs = CommissionRepository::client_dirs(&data_dir.join(folder))?;
+            for client_dir in &client_dirs {
+                let commission_files = FileStorage::read_directory_json_entries(client_dir)?.len();
+                // An empty history/<year> folder only holds other client folders
+                if commission_files == 0 && client_dirs.iter().any(|dir| dir.parent() == Some(client_dir.as_path())) {
+                    continue;
+                }
+
+                let folder_name = client_dir.file_name().unwrap_or_default().to_string_lossy();
+                let client_id = clients.iter()
+                    .find(|client| FileStorage::sanitize_filename(&client.name) == folder_name)
+                    .map(|client| client.id.clone());
+
+                audits.push(FolderAudit {
+                    path: Self::relative_path(&data_dir, client_dir),
+                    commission_files,
+                    orphaned: client_id.is_none(),
+                    client_id,
+                });
+            }
+        }
+
+        audits.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(audits)
+    }
+
+    /// Lists commissions stored in a client folder other than the one their client_name maps
+    /// to, which splits a client's work across folders. Read-only.
+    pub async fn find_folder_name_drift<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<DriftIssue>, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+
+        let mut issues: Vec<DriftIssue> = Self::stored_commissions(&data_dir)?
+            .into_iter()
+            .filter_map(|(path, commission)| {
+                let folder_name = path.parent()?.file_name()?.to_string_lossy().to_string();
+                let expected_folder = FileStorage::sanitize_filename(&commission.client_name);
+                (folder_name != expected_folder).then(|| DriftIssue {
+                    path: Self::relative_path(&data_dir, &path),
+                    commission_id: commission.id,
+                    client_name: commission.client_name,
+                    folder_name,
+                    expected_folder,
+                })
+            })
+            .collect();
+
+        issues.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(issues)
+    }
+
+    /// Moves each drifted commission file, and its images, into the folder its client_name
+    /// maps to. Returns how many commissions were moved.
+    pub async fn fix_folder_name_drift<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut moved = 0;
+
+        for (path, commission) in Self::stored_commissions(&data_dir)? {
+            let Some(folder_name) = path.parent().and_then(Path::file_name).map(|name| name.to_string_lossy().to_string()) else {
+                continue;
+            };
+            if folder_name == FileStorage::sanitize_filename(&commission.client_name) {
+                continue;
+            }
+
+            ImageService::relocate_commission_images(&data_dir, &commission.images, &folder_name, &commission.client_name)?;
+            let saved_file = CommissionRepository::save(&app_handle, &commission).await?;
+            if saved_file != path {
+                FileStorage::delete_file(&path)?;
+            }
+            moved += 1;
+        }
+
+        println!("Moved {} commissions into their client folders", moved);
+
+        Ok(moved)
+    }
+
+    /// Lists client ids that commissions refer to but that have no client record. With
+    /// create_stubs, a minimal record named after the commissions' client_name is saved for each.
+    pub async fn find_missing_client_records<R: Runtime>(
+        app_handle: AppHandle<R>,
+        create_stubs: bool,
+    ) -> Result<Vec<MissingClient>, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+
+        let mut referenced: BTreeMap<String, (String, usize)> = BTreeMap::new();
+        for (_, commission) in Self::stored_commissions(&data_dir)? {
+            if commission.client_id.is_empty() {
+                continue;
+            }
+            referenced.entry(commission.client_id)
+                .or_insert_with(|| (commission.client_name, 0))
+                .1 += 1;
+        }
+
+        let mut missing = Vec::new();
+        for (client_id, (client_name, commission_count)) in referenced {
+            if ClientRepository::exists(&app_handle, &client_id).await? {
+                continue;
+            }
+
+            let mut stub_created = false;
+            if create_stubs {
+                // Ids and names from imports weren't necessarily checked
+                match ValidationService::validate_id(&client_id)
+                    .and_then(|_| ValidationService::validate_name(&client_name, "Client name"))
+                {
+                    Ok(()) => {
+                        let now = chrono::Utc::now().to_rfc3339();
+                        let stub = Client {
+                            id: client_id.clone(),
+                            name: client_name.clone(),
+                            email: String::new(),
+                            contact: String::new(),
+                            profile_image: None,
+                            notes: Some("Created from commission records".to_string()),
+                            created_at: now.clone(),
+                            updated_at: now,
+                            contacts: Vec::new(),
+                            avatar_seed: None,
+                            communications: Vec::new(),
+                        };
+                        ClientRepository::save(&app_handle, &stub).await?;
+                        AuditRepository::record(&app_handle, "save", "client", &client_id);
+                        stub_created = true;
+                    }
+                    Err(e) => eprintln!("Not creating a client record for {}: {}", client_id, e),
+                }
+            }
+
+            missing.push(MissingClient {
+                client_id,
+                client_name,
+                commission_count,
+                stub_created,
+            });
+        }
+
+        Ok(missing)
+    }
+
+    /// Finds clients whose commissions sit in more than one client folder of the same parent
+    /// (pendings, history or a history year), going by the client_id inside the files. With
+    /// merge, each group is moved into one folder and client_name set to match it.
+    pub async fn find_split_client_folders<R: Runtime>(app_handle: AppHandle<R>, merge: bool) -> Result<Vec<SplitGroup>, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let clients = ClientRepository::find_all(&app_handle).await?;
+
+        // (folder holding the client folders, client_id) -> client folder -> its commissions
+        type ClientFolders = BTreeMap<PathBuf, Vec<(PathBuf, Commission)>>;
+        let mut groups: BTreeMap<(PathBuf, String), ClientFolders> = BTreeMap::new();
+        for (path, commission) in Self::stored_commissions(&data_dir)? {
+            let Some(client_dir) = path.parent() else {
+                continue;
+            };
+            let Some(parent_dir) = client_dir.parent() else {
+                continue;
+            };
+            if commission.client_id.is_empty() {
+                continue;
+            }
+            groups.entry((parent_dir.to_path_buf(), commission.client_id.clone()))
+                .or_default()
+                .entry(client_dir.to_path_buf())
+                .or_default()
+                .push((path, commission));
+        }
+
+        let mut splits = Vec::new();
+        for ((parent_dir, client_id), folders) in groups.into_iter().filter(|(_, folders)| folders.len() > 1) {
+            // Without a client record, the folder holding most of the commissions wins
+            let target_name = match clients.iter().find(|client| client.id == client_id) {
+                Some(client) => client.name.clone(),
+                None => folders.values()
+                    .max_by_key(|commissions| commissions.len())
+                    .and_then(|commissions| commissions.first())
+                    .map(|(_, commission)| commission.client_name.clone())
+                    .unwrap_or_default(),
+            };
+            let target_folder = FileStorage::sanitize_filename(&target_name);
+            let target_dir = parent_dir.join(&target_folder);
+
+            let mut group = SplitGroup {
+                client_id,
+                folders: folders.keys().map(|dir| Self::relative_path(&data_dir, dir)).collect(),
+                target_folder: Self::relative_path(&data_dir, &target_dir),
+                merged: 0,
+                conflicts: Vec::new(),
+            };
+
+            if merge {
+                for (client_dir, commissions) in folders.into_iter().filter(|(dir, _)| *dir != target_dir) {
+                    let folder_name = client_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+                    for (path, mut commission) in commissions {
+                        if path.file_name().is_some_and(|name| target_dir.join(name).exists()) {
+                            group.conflicts.push(commission.id);
+                            continue;
+                        }
+
+                        Self::move_merged_images(&data_dir, &mut commission, &folder_name, &target_folder)?;
+                        commission.client_name = target_name.clone();
+                        let saved_file = CommissionRepository::save(&app_handle, &commission).await?;
+                        if saved_file != path {
+                            FileStorage::delete_file(&path)?;
+                        }
+                        group.merged += 1;
+                    }
+
+                    // Only succeeds once nothing is left in them
+                    let _ = fs::remove_dir(data_dir.join("pendings").join(&folder_name).join("images"));
+                    let _ = fs::remove_dir(&client_dir);
+                }
+            }
+
+            splits.push(group);
+        }
+
+        Ok(splits)
+    }
+
+    /// Moves a commission's image files between client folders. An image whose name is taken
+    /// in the target folder by a different file gets a numbered name, and the commission is
+    /// pointed at it; an identical file there is used as is.
+    fn move_merged_images(
+        data_dir: &Path,
+        commission: &mut Commission,
+        from_folder: &str,
+        to_folder: &str,
+    ) -> Result<(), String> {
+        let status_folder = CommissionRepository::folder_for_status(&commission.status);
+        let mut image_folders = vec![status_folder];
+        if status_folder != "pendings" {
+            image_folders.push("pendings");
+        }
+
+        for index in 0..commission.images.len() {
+            let image_path = commission.images[index].clone();
+            if image_path.starts_with("data:") || ValidationService::validate_image_path(&image_path).is_err() {
+                continue;
+            }
+
+            for folder in &image_folders {
+                let source = data_dir.join(folder).join(from_folder).join(&image_path);
+                if !source.is_file() {
+                    continue;
+                }
+
+                let contents = fs::read(&source)
+                    .map_err(|e| format!("Failed to read image {}: {}", image_path, e))?;
+                let target_dir = data_dir.join(folder).join(to_folder);
+                let new_path = FileStorage::unused_or_identical_path(&target_dir, &image_path, &contents);
+                let target = target_dir.join(&new_path);
+
+                if target.exists() {
+                    FileStorage::delete_file(&source)?;
+                } else {
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent)
+                            .map_err(|e| format!("Failed to create images directory: {}", e))?;
+                    }
+                    fs::rename(&source, &target)
+                        .map_err(|e| format!("Failed to move image {}: {}", image_path, e))?;
+                }
+
+                if new_path != image_path {
+                    if commission.cover_image.as_ref() == Some(&image_path) {
+                        commission.cover_image = Some(new_path.clone());
+                    }
+                    commission.images[index] = new_path;
+                }
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists commission files larger than threshold_bytes, biggest first. Only those files are
+    /// read, to count the inline images that make up most of their size. Read-only.
+    pub async fn find_oversized_records<R: Runtime>(app_handle: AppHandle<R>, threshold_bytes: u64) -> Result<Vec<OversizedRecord>, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut records = Vec::new();
+
+        for folder in COMMISSION_FOLDERS {
+            for client_dir in CommissionRepository::client_dirs(&data_dir.join(folder))? {
+                let entries = fs::read_dir(&client_dir)
+                    .map_err(|e| format!("Failed to read client directory: {}", e))?;
+                for entry in entries {
+                    let path = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?.path();
+                    if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("json") {
+                        continue;
+                    }
+
+                    let size_bytes = fs::metadata(&path)
+                        .map_err(|e| format!("Failed to read file metadata: {}", e))?
+                        .len();
+                    if size_bytes <= threshold_bytes {
+                        continue;
+                    }
+
+                    let commission = fs::read_to_string(&path).ok()
+                        .and_then(|content| CommissionRepository::parse_commission(&content).ok());
+                    records.push(OversizedRecord {
+                        path: Self::relative_path(&data_dir, &path),
+                        commission_id: commission.as_ref().map(|c| c.id.clone()),
+                        size_bytes,
+                        inline_images: commission
+                            .map(|c| c.images.iter().filter(|image| image.starts_with("data:")).count())
+                            .unwrap_or(0),
+                    });
+                }
+            }
+        }
+
+        records.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.path.cmp(&b.path)));
+
+        Ok(records)
+    }
+
+    /// Lists commissions whose updated_at is earlier than created_at, or with either timestamp
+    /// in the future. Unparseable timestamps are left to the health check. Read-only.
+    pub async fn find_timestamp_anomalies<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<TimestampIssue>, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut issues = Vec::new();
+
+        for (path, commission) in Self::stored_commissions(&data_dir)? {
+            for kind in Self::timestamp_anomalies(&commission) {
+                issues.push(TimestampIssue {
+                    path: Self::relative_path(&data_dir, &path),
+                    commission_id: commission.id.clone(),
+                    kind: kind.to_string(),
+                    created_at: commission.created_at.clone(),
+                    updated_at: commission.updated_at.clone(),
+                });
+            }
+        }
+
+        issues.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.kind.cmp(&b.kind)));
+
+        Ok(issues)
+    }
+
+    /// Sets updated_at to created_at wherever it is earlier, and returns how many commissions
+    /// were fixed. Future timestamps are only reported: there is no telling what they should be.
+    pub async fn clamp_updated_timestamps<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut fixed = 0;
+
+        for (path, mut commission) in Self::stored_commissions(&data_dir)? {
+            if !Self::timestamp_anomalies(&commission).contains(&"updated_before_created") {
+                continue;
+            }
+
+            commission.updated_at = commission.created_at.clone();
+            let saved_file = CommissionRepository::save(&app_handle, &commission).await?;
+            // updated_at decides the history year, so the file may have moved
+            if saved_file != path {
+                FileStorage::delete_file(&path)?;
+            }
+            fixed += 1;
+        }
+
+        println!("Clamped updated_at on {} commissions", fixed);
+
+        Ok(fixed)
+    }
+
+    fn stored_commissions(data_dir: &Path) -> Result<Vec<(PathBuf, Commission)>, String> {
+        let mut commissions = Vec::new();
+        for folder in COMMISSION_FOLDERS {
+            for client_dir in CommissionRepository::client_dirs(&data_dir.join(folder))? {
+                for (path, content) in FileStorage::read_directory_json_entries(&client_dir)? {
+                    if let Ok(commission) = CommissionRepository::parse_commission(&content) {
+                        commissions.push((path, commission));
+                    }
+                }
+            }
+        }
+        Ok(commissions)
+    }
+
+    fn timestamp_anomalies(commission: &Commission) -> Vec<&'static str> {
+        let parse = |timestamp: &str| chrono::DateTime::parse_from_rfc3339(timestamp).ok();
+        let (created_at, updated_at) = (parse(&commission.created_at), parse(&commission.updated_at));
+        let latest_allowed = chrono::Utc::now() + chrono::Duration::minutes(FUTURE_TOLERANCE_MINUTES);
+
+        let mut anomalies = Vec::new();
+        if let (Some(created_at), Some(updated_at)) = (created_at, updated_at) {
+            if updated_at < created_at {
+                anomalies.push("updated_before_created");
+            }
+        }
+        if created_at.is_some_and(|t| t > latest_allowed) {
+            anomalies.push("created_in_future");
+        }
+        if updated_at.is_some_and(|t| t > latest_allowed) {
+            anomalies.push("updated_in_future");
+        }
+        anomalies
+    }
+
+    fn check_commission_file(
+        data_dir: &Path,
+        folder: &str,
+        path: &Path,
+        relative: &str,
+        commission: &Commission,
+        issues: &mut Vec<HealthIssue>,
+    ) {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        if !file_name.starts_with(&format!("{}_", commission.id)) {
+            issues.push(Self::issue("warning", "id_mismatch", relative,
+                format!("File name does not match commission id {}", commission.id)));
+        }
+
+        // Either history layout is fine; reads handle both
+        let expected_dirs = [false, true].map(|partition_history| {
+            CommissionRepository::file_path(data_dir, commission, partition_history).parent().map(Path::to_path_buf)
+        });
+        if CommissionRepository::folder_for_status(&commission.status) != folder {
+            issues.push(Self::issue("warning", "misfiled", relative,
+                format!("Commission with status {} is stored in {}", commission.status, folder)));
+        } else if !expected_dirs.iter().any(|dir| dir.as_deref() == path.parent()) {
+            issues.push(Self::issue("warning", "misfiled", relative,
+                format!("Commission belongs in the folder for client {}", commission.client_name)));
+        }
+    }
+
+    fn relative_path(data_dir: &Path, path: &Path) -> String {
+        path.strip_prefix(data_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    fn issue(severity: &str, category: &str, path: &str, message: String) -> HealthIssue {
+        HealthIssue {
+            severity: severity.to_string(),
+            category: category.to_string(),
+            path: path.to_string(),
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{client, commission, png, TestEnv};
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn health_check_reports_one_of_each_problem() {
+        let env = TestEnv::new();
+        let client_dir = env.data_dir().join("pendings").join("Name alice");
+        fs::create_dir_all(client_dir.join("images")).unwrap();
+
+        fs::write(client_dir.join("broken_2026.json"), "{ not json").unwrap();
+
+        let mut missing_image = commission("c1", "alice", "pending");
+        missing_image.images = vec!["images/c1_gone.png".to_string()];
+        block_on(CommissionRepository::save(env.app(), &missing_image)).unwrap();
+
+        let renamed = commission("c2", "alice", "pending");
+        fs::write(client_dir.join("other_2026.json"), serde_json::to_string(&renamed).unwrap()).unwrap();
+
+        let misfiled = commission("c3", "alice", "completed");
+        fs::write(client_dir.join("c3_2026.json"), serde_json::to_string(&misfiled).unwrap()).unwrap();
+
+        fs::write(client_dir.join("images").join("stray.png"), png(4, 4)).unwrap();
+
+        let report = block_on(HealthService::run_health_check(env.app().clone())).unwrap();
+        let mut categories: Vec<&str> = report.issues.iter().map(|issue| issue.category.as_str()).collect();
+        categories.sort();
+        assert_eq!(categories, vec!["id_mismatch", "misfiled", "missing_image", "orphaned_image", "unparseable"]);
+        assert!(!report.healthy);
+        assert!(client_dir.join("broken_2026.json").exists());
+    }
+
+    #[test]
+    fn unknown_payment_status_is_reported_without_changing_the_file() {
+        let env = TestEnv::new();
+        let mut edited = commission("c1", "alice", "pending");
+        edited.payment_status = "Partially".to_string();
+        let path = block_on(CommissionRepository::save(env.app(), &edited)).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c2", "alice", "pending"))).unwrap();
+        let before = fs::read(&path).unwrap();
+
+        let issues = block_on(HealthService::find_invalid_enum_values(env.app().clone())).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].commission_id.as_str(), issues[0].field.as_str(), issues[0].value.as_str()), ("c1", "payment_status", "Partially"));
+        assert_eq!(fs::read(&path).unwrap(), before);
+    }
+
+    #[test]
+    fn backwards_timestamps_are_reported_and_clamped() {
+        let env = TestEnv::new();
+        let mut backwards = commission("c1", "alice", "pending");
+        backwards.created_at = "2026-02-01T00:00:00Z".to_string();
+        backwards.updated_at = "2026-01-01T00:00:00Z".to_string();
+        block_on(CommissionRepository::save(env.app(), &backwards)).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c2", "alice", "pending"))).unwrap();
+
+        let issues = block_on(HealthService::find_timestamp_anomalies(env.app().clone())).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].commission_id.as_str(), issues[0].kind.as_str()), ("c1", "updated_before_created"));
+
+        assert_eq!(block_on(HealthService::clamp_updated_timestamps(env.app().clone())).unwrap(), 1);
+        assert!(block_on(HealthService::find_timestamp_anomalies(env.app().clone())).unwrap().is_empty());
+        let clamped = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        assert_eq!(clamped.updated_at, clamped.created_at);
+    }
+
+    #[test]
+    fn folder_audit_flags_a_folder_without_a_client_record() {
+        let env = TestEnv::new();
+        block_on(ClientRepository::save(env.app(), &client("alice", "Alice"))).unwrap();
+        for (id, client_name) in [("c1", "Alice"), ("c2", "Alice"), ("c3", "Ghost")] {
+            let mut c = commission(id, "alice", "pending");
+            c.client_name = client_name.to_string();
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+
+        let audits = block_on(HealthService::audit_client_folders(env.app().clone())).unwrap();
+        let summary: Vec<(&str, usize, bool)> = audits.iter()
+            .map(|audit| (audit.path.as_str(), audit.commission_files, audit.orphaned))
+            .collect();
+        assert_eq!(summary, vec![("pendings/Alice", 2, false), ("pendings/Ghost", 1, true)]);
+        assert_eq!(audits[0].client_id.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn misfiled_commission_is_reported_and_moved_back() {
+        let env = TestEnv::new();
+        let mut work = commission("c1", "alice", "pending");
+        work.client_name = "Alice".to_string();
+        let path = block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        let misfiled = env.data_dir().join("pendings").join("Bob").join(path.file_name().unwrap());
+        fs::create_dir_all(misfiled.parent().unwrap()).unwrap();
+        fs::rename(&path, &misfiled).unwrap();
+
+        let issues = block_on(HealthService::find_folder_name_drift(env.app().clone())).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].folder_name.as_str(), issues[0].expected_folder.as_str()), ("Bob", "Alice"));
+
+        assert_eq!(block_on(HealthService::fix_folder_name_drift(env.app().clone())).unwrap(), 1);
+        assert!(path.is_file() && !misfiled.exists());
+        assert!(block_on(HealthService::find_folder_name_drift(env.app().clone())).unwrap().is_empty());
+    }
+
+    #[test]
+    fn oversized_records_are_reported_above_the_threshold() {
+        let env = TestEnv::new();
+        block_on(CommissionRepository::save(env.app(), &commission("small", "alice", "pending"))).unwrap();
+        let mut large = commission("large", "alice", "completed");
+        large.images = vec![format!("data:image/png;base64,{}", "A".repeat(20_000)), "images/art.png".to_string()];
+        let large_path = block_on(CommissionRepository::save(env.app(), &large)).unwrap();
+        let large_size = fs::metadata(&large_path).unwrap().len();
+
+        let records = block_on(HealthService::find_oversized_records(env.app().clone(), 10_000)).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].commission_id.as_deref(), Some("large"));
+        assert_eq!(records[0].size_bytes, large_size);
+        assert_eq!(records[0].inline_images, 1);
+
+        // Exactly at the threshold is not oversized
+        assert!(block_on(HealthService::find_oversized_records(env.app().clone(), large_size)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn commissions_without_a_client_record_are_found_and_stubbed() {
+        let env = TestEnv::new();
+        block_on(ClientRepository::save(env.app(), &client("alice", "Alice"))).unwrap();
+        for work in [
+            commission("a1", "alice", "pending"),
+            commission("g1", "ghost", "pending"),
+            commission("g2", "ghost", "completed"),
+        ] {
+            block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        }
+
+        let missing = block_on(HealthService::find_missing_client_records(env.app().clone(), false)).unwrap();
+        let found: Vec<(&str, &str, usize, bool)> = missing.iter()
+            .map(|m| (m.client_id.as_str(), m.client_name.as_str(), m.commission_count, m.stub_created))
+            .collect();
+        assert_eq!(found, vec![("ghost", "Name ghost", 2, false)]);
+        assert!(!block_on(ClientRepository::exists(env.app(), "ghost")).unwrap());
+
+        let stubbed = block_on(HealthService::find_missing_client_records(env.app().clone(), true)).unwrap();
+        assert!(stubbed[0].stub_created);
+        let stub = block_on(ClientRepository::find_by_id(env.app(), "ghost")).unwrap().unwrap();
+        assert_eq!(stub.name, "Name ghost");
+        assert!(block_on(HealthService::find_missing_client_records(env.app().clone(), false)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn split_client_folders_are_merged_into_the_client_record_name() {
+        let env = TestEnv::new();
+        block_on(ClientRepository::save(env.app(), &client("alice", "Alice"))).unwrap();
+        let in_folder = |id: &str, client_name: &str| {
+            let mut work = commission(id, "alice", "pending");
+            work.client_name = client_name.to_string();
+            work
+        };
+        let mut moved = in_folder("c2", "Alice Old");
+        moved.images = vec!["images/c2_art.png".to_string()];
+        for work in [in_folder("c1", "Alice"), in_folder("c3", "Alice"), moved, in_folder("c3", "Alice Old")] {
+            block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        }
+        let pendings = env.data_dir().join("pendings");
+        let old_folder = pendings.join(FileStorage::sanitize_filename("Alice Old"));
+        fs::create_dir_all(old_folder.join("images")).unwrap();
+        fs::write(old_folder.join("images").join("c2_art.png"), png(4, 4)).unwrap();
+
+        let found = block_on(HealthService::find_split_client_folders(env.app().clone(), false)).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].folders.len(), 2);
+        assert_eq!(found[0].target_folder, "pendings/Alice");
+        assert_eq!(found[0].merged, 0);
+
+        let merged = block_on(HealthService::find_split_client_folders(env.app().clone(), true)).unwrap();
+        assert_eq!(merged[0].merged, 1);
+        // Both folders hold a c3 file, so that one stays where it is
+        assert_eq!(merged[0].conflicts, vec!["c3".to_string()]);
+
+        let c2 = block_on(CommissionRepository::find_by_id(env.app(), "c2", "pending")).unwrap().unwrap();
+        assert_eq!(c2.client_name, "Alice");
+        assert!(pendings.join("Alice").join(&c2.images[0]).is_file());
+        assert!(!old_folder.join("images").join("c2_art.png").exists());
+    }
+}
@@ -1,20 +1,102 @@
+use ab_glyph::{FontRef, PxScale};
+use image::codecs::jpeg::JpegEncoder;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use tauri::AppHandle;
-use crate::repository::FileStorage;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+use crate::repository::{CommissionRepository, ConfigRepository, FileStorage};
+use crate::models::Commission;
+use crate::repository::commission_repository::{COMMISSION_FOLDERS, FOLDER_STATUSES};
+use crate::repository::config_repository::{ImageSettings, WatermarkSettings};
 use super::validation_service::ValidationService;
 
+// Bundled with the frontend assets; compiled in so exports don't depend on system fonts
+const WATERMARK_FONT: &[u8] = include_bytes!("../../../src/assets/fonts/Roboto/static/Roboto-Bold.ttf");
+
+// Longest edge of the previews written to images/thumbs
+const THUMBNAIL_EDGE: u32 = 256;
+
+// Files below this size aren't worth a lossy re-encode
+const MIN_COMPRESSIBLE_IMAGE_BYTES: u64 = 32 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SavedImage {
+    pub relative_path: String,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DedupReport {
+    pub total_files: usize,
+    pub unique_files: usize,
+    pub total_bytes: u64,
+    pub reclaimable_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DedupResult {
+    pub files_removed: usize,
+    pub bytes_reclaimed: u64,
+    pub commissions_updated: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CompressionReport {
+    pub files_compressed: usize,
+    pub files_skipped: usize,
+    pub bytes_saved: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DownscaleReport {
+    pub files_downscaled: usize,
+    pub files_skipped: usize, // Oversized but not downscaled: undecodable or animated
+    pub bytes_saved: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FormatMismatch {
+    pub path: String, // Relative to the data directory
+    pub extension: String,
+    pub detected_format: String, // From the file's magic bytes, or "unknown"
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GalleryItem {
+    pub relative_path: String,
+    pub full_path: String,
+    pub thumbnail_path: Option<String>, // None when no thumbnail could be made; show the full image
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CorruptImage {
+    pub commission_id: String,
+    pub path: String, // Relative to the data directory
+    pub problem: String, // "corrupt", or "unsupported" for formats the decoder doesn't handle
+    pub message: String,
+}
+
+struct ProcessedImage {
+    data: Vec<u8>,
+    original: Option<Vec<u8>>, // Untouched upload, set only when the image was downscaled
+    warnings: Vec<String>,
+}
+
 pub struct ImageService;
 
 impl ImageService {
-    pub async fn save_commission_image(
-        app_handle: AppHandle,
+    pub async fn save_commission_image<R: Runtime>(
+        app_handle: AppHandle<R>,
         commission_id: String,
         client_name: String,
         image_data: Vec<u8>,
         filename: String,
-    ) -> Result<String, String> {
+    ) -> Result<SavedImage, String> {
         // Validate inputs
-        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_commission_id(&commission_id)?;
         ValidationService::validate_name(&client_name, "Client name")?;
         ValidationService::validate_filename(&filename)?;
         
@@ -35,14 +117,8 @@ impl ImageService {
             [0x89, 0x50, 0x4E, 0x47] => true, // PNG
             [0x47, 0x49, 0x46, 0x38] => true, // GIF
             [0x42, 0x4D, _, _] => true, // BMP
-            [0x52, 0x49, 0x46, 0x46] => {
-                // WebP (check for WEBP in bytes 8-12)
-                if image_data.len() >= 12 {
-                    &image_data[8..12] == b"WEBP"
-                } else {
-                    false
-                }
-            },
+            // WebP (check for WEBP in bytes 8-12)
+            [0x52, 0x49, 0x46, 0x46] => image_data.len() >= 12 && &image_data[8..12] == b"WEBP",
             _ => false,
         };
         
@@ -50,23 +126,1445 @@ impl ImageService {
             return Err("Invalid image format".to_string());
         }
         
+        let settings = ConfigRepository::load(&app_handle)?.images;
+        let mut processed = Self::apply_large_image_policy(image_data, &settings)?;
+        let mut sanitized_filename = FileStorage::sanitize_filename(&filename);
+        let mut converted_to_webp = false;
+        
+        if settings.convert_to_webp {
+            match Self::convert_to_webp(&processed) {
+                Ok(Some(converted)) => {
+                    processed = converted;
+                    converted_to_webp = true;
+                    sanitized_filename = Path::new(&sanitized_filename)
+                        .with_extension("webp")
+                        .to_string_lossy()
+                        .to_string();
+                }
+                Ok(None) => {}
+                Err(e) => processed.warnings.push(format!("Image was kept in its original format: {}", e)),
+            }
+        }
+        
         let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
         
         // Create images directory for the commission using sanitized client name
         let sanitized_client_name = FileStorage::sanitize_filename(&client_name);
         let client_dir = data_dir.join("pendings").join(&sanitized_client_name);
-        let images_dir = client_dir.join("images");
+        
+        // Either a commission ID prefix or a per-commission folder keeps names unique
+        let mut relative_path = if settings.image_layout == "per_commission" {
+            format!("images/{}/{}", commission_id, sanitized_filename)
+        } else {
+            format!("images/{}_{}", commission_id, sanitized_filename)
+        };
+        // a.png and a.jpg both become a.webp; the second mustn't replace the first
+        if converted_to_webp {
+            relative_path = FileStorage::unused_or_identical_path(&client_dir, &relative_path, &processed.data);
+        }
+        let image_file = client_dir.join(&relative_path);
+        let images_dir = image_file.parent().ok_or("Invalid image path")?.to_path_buf();
         fs::create_dir_all(&images_dir)
             .map_err(|e| format!("Failed to create images directory: {}", e))?;
         
-        // Generate unique filename with commission ID prefix using sanitized filename
-        let sanitized_filename = FileStorage::sanitize_filename(&filename);
-        let image_file = images_dir.join(format!("{}_{}", commission_id, sanitized_filename));
-        
-        fs::write(&image_file, image_data)
+        fs::write(&image_file, processed.data)
             .map_err(|e| format!("Failed to save image: {}", e))?;
         
+        // Keep the full-size upload next to the downscaled copy
+        if let Some(original_data) = processed.original {
+            let originals_dir = images_dir.join("originals");
+            fs::create_dir_all(&originals_dir)
+                .map_err(|e| format!("Failed to create originals directory: {}", e))?;
+            fs::write(originals_dir.join(image_file.file_name().unwrap()), original_data)
+                .map_err(|e| format!("Failed to save original image: {}", e))?;
+        }
+        
+        // A missing thumbnail can be backfilled later, so it doesn't fail the upload
+        let mut warnings = processed.warnings;
+        if settings.generate_thumbnails {
+            if let Err(e) = Self::write_thumbnail(&image_file) {
+                warnings.push(format!("Thumbnail was not created: {}", e));
+            }
+        }
+        
         // Return relative path
-        Ok(format!("images/{}", image_file.file_name().unwrap().to_str().unwrap()))
+        Ok(SavedImage {
+            relative_path,
+            warnings,
+        })
+    }
+
+    /// Creates thumbnails for stored images that don't have one yet. Files that can't be
+    /// decoded are logged and skipped. Returns how many thumbnails were written.
+    pub async fn backfill_thumbnails<R: Runtime>(app_handle: AppHandle<R>) -> Result<usize, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut created = 0;
+        
+        for folder in COMMISSION_FOLDERS {
+            for client_dir in CommissionRepository::client_dirs(&data_dir.join(folder))? {
+                let images_dir = client_dir.join("images");
+                if !images_dir.is_dir() {
+                    continue;
+                }
+                
+                for image_file in FileStorage::list_files_recursive(&images_dir)? {
+                    // originals/ and thumbs/ hold copies, not separate images
+                    let relative = image_file.strip_prefix(&images_dir).unwrap_or(&image_file);
+                    if Self::is_derived_image(relative) {
+                        continue;
+                    }
+                    
+                    let file_name = image_file.file_name().unwrap_or_default().to_string_lossy().to_string();
+                    if ValidationService::validate_filename(&file_name).is_err() {
+                        continue;
+                    }
+                    if Self::thumbnail_path(&image_file).is_some_and(|thumb| thumb.exists()) {
+                        continue;
+                    }
+                    
+                    match Self::write_thumbnail(&image_file) {
+                        Ok(()) => created += 1,
+                        Err(e) => eprintln!("Failed to create thumbnail for {}: {}", image_file.display(), e),
+                    }
+                }
+            }
+        }
+        
+        Ok(created)
+    }
+
+    fn thumbnail_path(image_file: &Path) -> Option<PathBuf> {
+        Self::derived_path(image_file, "thumbs")
+    }
+
+    fn write_thumbnail(image_file: &Path) -> Result<(), String> {
+        let thumbnail_file = Self::thumbnail_path(image_file)
+            .ok_or("Invalid image path")?;
+        
+        let image_data = fs::read(image_file)
+            .map_err(|e| format!("Failed to read image: {}", e))?;
+        let (width, height) = image::ImageReader::new(Cursor::new(&image_data))
+            .with_guessed_format()
+            .map_err(|e| format!("Failed to detect image format: {}", e))?
+            .into_dimensions()
+            .map_err(|e| format!("Failed to read image dimensions: {}", e))?;
+        
+        // Small images are their own thumbnail; resizing would only upscale them
+        let thumbnail_data = if width.max(height) <= THUMBNAIL_EDGE {
+            image_data
+        } else {
+            Self::downscale(&image_data, THUMBNAIL_EDGE)?
+        };
+        
+        if let Some(parent) = thumbnail_file.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create thumbnails directory: {}", e))?;
+        }
+        fs::write(&thumbnail_file, thumbnail_data)
+            .map_err(|e| format!("Failed to save thumbnail: {}", e))
+    }
+
+    /// Re-encodes the images of completed commissions as JPEG and points the commissions at the
+    /// new files. Pending and in-progress work is never touched, and neither are files that
+    /// other open or cancelled commissions still use.
+    pub async fn compress_completed_images<R: Runtime>(app_handle: AppHandle<R>, quality: u8) -> Result<CompressionReport, String> {
+        ValidationService::validate_jpeg_quality(quality)?;
+        
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut report = CompressionReport {
+            files_compressed: 0,
+            files_skipped: 0,
+            bytes_saved: 0,
+        };
+        
+        // Images live under pendings whatever the status, so after dedup one file can back
+        // both a finished commission and one still in progress
+        let mut used_elsewhere: HashSet<PathBuf> = HashSet::new();
+        for status in FOLDER_STATUSES.iter().filter(|status| **status != "completed") {
+            for commission in CommissionRepository::find_by_status(&app_handle, status).await? {
+                used_elsewhere.extend(commission.images.iter()
+                    .filter_map(|image| Self::resolve_image_path(&data_dir, &commission.client_name, &commission.status, image)));
+            }
+        }
+        
+        // Resolved up front: a file shared by several completed commissions is gone once the
+        // first of them has been compressed
+        let commissions: Vec<(Commission, Vec<Option<PathBuf>>)> = CommissionRepository::find_by_status(&app_handle, "completed").await?
+            .into_iter()
+            .map(|commission| {
+                let sources = commission.images.iter()
+                    .map(|relative_path| {
+                        if relative_path.starts_with("data:") || ValidationService::validate_image_path(relative_path).is_err() {
+                            return None;
+                        }
+                        Self::resolve_image_path(&data_dir, &commission.client_name, "completed", relative_path)
+                    })
+                    .collect();
+                (commission, sources)
+            })
+            .collect();
+        
+        // Source file -> its compressed copy, or None when it was left as it is
+        let mut compressed: HashMap<PathBuf, Option<String>> = HashMap::new();
+        
+        for (mut commission, sources) in commissions {
+            let mut changed = false;
+            
+            for (index, source) in sources.into_iter().enumerate() {
+                let Some(source) = source else {
+                    continue;
+                };
+                
+                let new_relative_path = match compressed.get(&source) {
+                    Some(new_relative_path) => new_relative_path.clone(),
+                    None => {
+                        let outcome = if used_elsewhere.contains(&source) {
+                            None
+                        } else {
+                            Self::compress_image_file(&source, &commission.images[index], quality)?
+                        };
+                        let new_relative_path = match outcome {
+                            Some((new_relative_path, saved)) => {
+                                report.files_compressed += 1;
+                                report.bytes_saved += saved;
+                                Some(new_relative_path)
+                            }
+                            None => {
+                                report.files_skipped += 1;
+                                None
+                            }
+                        };
+                        compressed.insert(source, new_relative_path.clone());
+                        new_relative_path
+                    }
+                };
+                
+                if let Some(new_relative_path) = new_relative_path {
+                    if commission.cover_image.as_ref() == Some(&commission.images[index]) {
+                        commission.cover_image = Some(new_relative_path.clone());
+                    }
+                    commission.images[index] = new_relative_path;
+                    changed = true;
+                }
+            }
+            
+            if changed {
+                // updated_at is left alone: for completed work it doubles as the completion time
+                CommissionRepository::save(&app_handle, &commission).await?;
+            }
+        }
+        
+        Ok(report)
+    }
+
+    /// Shrinks every stored image whose longest edge is over max_edge, in place, keeping the
+    /// aspect ratio. Kept originals and thumbnails are left alone, as are GIFs, whose animation
+    /// would be lost, and files that can't be decoded.
+    pub async fn downscale_all_images<R: Runtime>(app_handle: AppHandle<R>, max_edge: u32) -> Result<DownscaleReport, String> {
+        ValidationService::validate_max_image_edge(max_edge)?;
+        
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut report = DownscaleReport {
+            files_downscaled: 0,
+            files_skipped: 0,
+            bytes_saved: 0,
+        };
+        
+        for folder in COMMISSION_FOLDERS {
+            let folder_dir = data_dir.join(folder);
+            
+            for image_file in FileStorage::list_files_recursive(&folder_dir)? {
+                let Some((_, image_path)) = Self::stored_image_parts(&folder_dir, &image_file) else {
+                    continue;
+                };
+                if Self::is_derived_image(&image_path) {
+                    continue;
+                }
+                
+                let image_data = fs::read(&image_file)
+                    .map_err(|e| format!("Failed to read image: {}", e))?;
+                let dimensions = image::ImageReader::new(Cursor::new(&image_data))
+                    .with_guessed_format()
+                    .ok()
+                    .and_then(|reader| reader.into_dimensions().ok());
+                
+                let Some((width, height)) = dimensions else {
+                    continue;
+                };
+                if width.max(height) <= max_edge {
+                    continue;
+                }
+                
+                let is_gif = image::guess_format(&image_data).ok() == Some(image::ImageFormat::Gif);
+                let downscaled = if is_gif {
+                    None
+                } else {
+                    Self::downscale(&image_data, max_edge)
+                        .map_err(|e| eprintln!("Skipping {}: {}", image_file.display(), e))
+                        .ok()
+                };
+                let Some(downscaled) = downscaled else {
+                    report.files_skipped += 1;
+                    continue;
+                };
+                
+                fs::write(&image_file, &downscaled)
+                    .map_err(|e| format!("Failed to write downscaled image: {}", e))?;
+                report.files_downscaled += 1;
+                report.bytes_saved += (image_data.len() as u64).saturating_sub(downscaled.len() as u64);
+            }
+        }
+        
+        println!("Downscaled {} images, saving {} bytes", report.files_downscaled, report.bytes_saved);
+        
+        Ok(report)
+    }
+
+    /// Writes a JPEG copy next to the image and removes the original. Returns the new relative
+    /// path and the bytes saved, or None when the file was skipped.
+    fn compress_image_file(source: &Path, relative_path: &str, quality: u8) -> Result<Option<(String, u64)>, String> {
+        let original_size = fs::metadata(source)
+            .map_err(|e| format!("Failed to read image metadata: {}", e))?
+            .len();
+        let extension = source.extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        
+        // Tiny, already lossy (another pass only degrades it), or animated
+        if original_size < MIN_COMPRESSIBLE_IMAGE_BYTES || matches!(extension.as_str(), "jpg" | "jpeg" | "gif") {
+            return Ok(None);
+        }
+        
+        let target = source.with_extension("jpg");
+        if target.exists() {
+            return Ok(None);
+        }
+        
+        let content = fs::read(source)
+            .map_err(|e| format!("Failed to read image: {}", e))?;
+        let Ok(decoded) = image::load_from_memory(&content) else {
+            return Ok(None);
+        };
+        
+        let mut encoded = Vec::new();
+        image::DynamicImage::ImageRgb8(decoded.to_rgb8())
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut encoded, quality))
+            .map_err(|e| format!("Failed to encode image: {}", e))?;
+        
+        let encoded_size = encoded.len() as u64;
+        if encoded_size >= original_size {
+            return Ok(None);
+        }
+        
+        fs::write(&target, &encoded)
+            .map_err(|e| format!("Failed to write compressed image: {}", e))?;
+        fs::remove_file(source)
+            .map_err(|e| format!("Failed to remove original image: {}", e))?;
+        
+        let new_relative_path = Path::new(relative_path)
+            .with_extension("jpg")
+            .to_string_lossy()
+            .replace('\\', "/");
+        
+        Ok(Some((new_relative_path, original_size - encoded_size)))
+    }
+
+    /// Reports how much space identical image files take up. Read-only: nothing is merged or removed.
+    pub async fn estimate_dedup_savings<R: Runtime>(app_handle: AppHandle<R>) -> Result<DedupReport, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        
+        // Content hash -> (copies, size of one copy)
+        let mut groups: HashMap<Vec<u8>, (usize, u64)> = HashMap::new();
+        let mut total_files = 0;
+        let mut total_bytes = 0;
+        
+        for folder in COMMISSION_FOLDERS {
+            let folder_dir = data_dir.join(folder);
+            let files = FileStorage::list_files_recursive(&folder_dir)?;
+            
+            for file in files.iter().filter(|path| Self::is_stored_image(&folder_dir, path)) {
+                let content = fs::read(file)
+                    .map_err(|e| format!("Failed to read image {}: {}", file.display(), e))?;
+                let size = content.len() as u64;
+                
+                let entry = groups.entry(Sha256::digest(&content).to_vec()).or_insert((0, size));
+                entry.0 += 1;
+                total_files += 1;
+                total_bytes += size;
+            }
+        }
+        
+        let reclaimable_bytes = groups.values()
+            .map(|(copies, size)| (*copies as u64 - 1) * size)
+            .sum();
+        
+        Ok(DedupReport {
+            total_files,
+            unique_files: groups.len(),
+            total_bytes,
+            reclaimable_bytes,
+        })
+    }
+
+    /// Collapses identical image files within each client's images folder to a single copy
+    /// and points every commission that used a removed copy at the one that was kept. Files
+    /// are never merged across clients.
+    pub async fn deduplicate_all_images<R: Runtime>(app_handle: AppHandle<R>) -> Result<DedupResult, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        
+        // Removed file -> relative path of the copy that replaces it
+        let mut replacements: HashMap<PathBuf, String> = HashMap::new();
+        let mut bytes_reclaimed = 0;
+        
+        for folder in COMMISSION_FOLDERS {
+            for client_dir in CommissionRepository::client_dirs(&data_dir.join(folder))? {
+                let mut files: Vec<PathBuf> = FileStorage::list_files_recursive(&client_dir.join("images"))?
+                    .into_iter()
+                    .filter(|path| !Self::is_derived_image(path.strip_prefix(&client_dir).unwrap_or(path)))
+                    .collect();
+                // Sorted so the same copy is kept on every run
+                files.sort();
+                
+                let mut kept: HashMap<Vec<u8>, String> = HashMap::new();
+                for file in files {
+                    let content = fs::read(&file)
+                        .map_err(|e| format!("Failed to read image {}: {}", file.display(), e))?;
+                    let relative_path = file.strip_prefix(&client_dir).unwrap_or(&file)
+                        .to_string_lossy()
+                        .replace('\\', "/");
+                    
+                    let hash = Sha256::digest(&content).to_vec();
+                    if let Some(kept_path) = kept.get(&hash) {
+                        bytes_reclaimed += content.len() as u64;
+                        replacements.insert(file, kept_path.clone());
+                    } else {
+                        kept.insert(hash, relative_path);
+                    }
+                }
+            }
+        }
+        
+        // Repoint commissions before anything is deleted, while their paths still resolve
+        let mut commissions_updated = 0;
+        for status in FOLDER_STATUSES {
+            for mut commission in CommissionRepository::find_by_status(&app_handle, status).await? {
+                let mut changed = false;
+                let mut images: Vec<String> = Vec::with_capacity(commission.images.len());
+                
+                for image_path in &commission.images {
+                    let replacement = Self::resolve_image_path(&data_dir, &commission.client_name, &commission.status, image_path)
+                        .and_then(|resolved| replacements.get(&resolved));
+                    let image_path = match replacement {
+                        Some(kept_path) => {
+                            if commission.cover_image.as_ref() == Some(image_path) {
+                                commission.cover_image = Some(kept_path.clone());
+                            }
+                            changed = true;
+                            kept_path.clone()
+                        }
+                        None => image_path.clone(),
+                    };
+                    
+                    // Two entries may now name the same file
+                    if images.contains(&image_path) {
+                        changed = true;
+                    } else {
+                        images.push(image_path);
+                    }
+                }
+                
+                if changed {
+                    commission.images = images;
+                    CommissionRepository::save(&app_handle, &commission).await?;
+                    commissions_updated += 1;
+                }
+            }
+        }
+        
+        for duplicate in replacements.keys() {
+            FileStorage::delete_file(duplicate)?;
+            for derived in ["originals", "thumbs"] {
+                if let Some(derived_file) = Self::derived_path(duplicate, derived).filter(|path| path.is_file()) {
+                    FileStorage::delete_file(&derived_file)?;
+                }
+            }
+        }
+        
+        println!("Removed {} duplicate images ({} bytes) and updated {} commissions",
+            replacements.len(), bytes_reclaimed, commissions_updated);
+        
+        Ok(DedupResult {
+            files_removed: replacements.len(),
+            bytes_reclaimed,
+            commissions_updated,
+        })
+    }
+
+    /// Renames a commission's image files (and their originals/thumbnails) from one id to
+    /// another. Handles both layouts; paths not named after the old id are kept. Returns the
+    /// updated image list.
+    pub fn rekey_commission_images(
+        data_dir: &Path,
+        client_name: &str,
+        images: &[String],
+        old_id: &str,
+        new_id: &str,
+    ) -> Result<Vec<String>, String> {
+        let client_folder = FileStorage::sanitize_filename(client_name);
+        let per_commission_prefix = format!("images/{}/", old_id);
+        let prefixed_prefix = format!("images/{}_", old_id);
+        
+        let mut rekeyed = Vec::with_capacity(images.len());
+        for image_path in images {
+            let new_path = if let Some(rest) = image_path.strip_prefix(&per_commission_prefix) {
+                format!("images/{}/{}", new_id, rest)
+            } else if let Some(rest) = image_path.strip_prefix(&prefixed_prefix) {
+                format!("images/{}_{}", new_id, rest)
+            } else {
+                rekeyed.push(image_path.clone());
+                continue;
+            };
+            
+            if ValidationService::validate_image_path(image_path).is_err() {
+                rekeyed.push(image_path.clone());
+                continue;
+            }
+            
+            for client_dir in Self::client_folders(data_dir, &client_folder)? {
+                let (old_file, new_file) = (client_dir.join(image_path), client_dir.join(&new_path));
+                Self::move_if_exists(&old_file, &new_file)?;
+                
+                for derived in ["originals", "thumbs"] {
+                    if let (Some(old_derived), Some(new_derived)) = (
+                        Self::derived_path(&old_file, derived),
+                        Self::derived_path(&new_file, derived),
+                    ) {
+                        Self::move_if_exists(&old_derived, &new_derived)?;
+                    }
+                }
+                
+                // Drop the emptied images/<old id> folders; fails harmlessly if anything is left
+                if image_path.starts_with(&per_commission_prefix) {
+                    let old_folder = client_dir.join("images").join(old_id);
+                    for derived in ["originals", "thumbs"] {
+                        let _ = fs::remove_dir(old_folder.join(derived));
+                    }
+                    let _ = fs::remove_dir(old_folder);
+                }
+            }
+            
+            rekeyed.push(new_path);
+        }
+        
+        Ok(rekeyed)
+    }
+
+    // images/[<id>/]<file> -> images/[<id>/]<derived>/<file>
+    fn derived_path(image_file: &Path, derived: &str) -> Option<PathBuf> {
+        Some(image_file.parent()?.join(derived).join(image_file.file_name()?))
+    }
+
+    fn move_if_exists(source: &Path, target: &Path) -> Result<(), String> {
+        if !source.is_file() {
+            return Ok(());
+        }
+        
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create images directory: {}", e))?;
+        }
+        fs::rename(source, target)
+            .map_err(|e| format!("Failed to move image {}: {}", source.display(), e))
+    }
+
+    /// Full and thumbnail paths for each of a commission's stored images, in the commission's
+    /// order. Missing thumbnails are written on the way; images not on disk are left out.
+    pub async fn get_commission_gallery<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        status: String,
+    ) -> Result<Vec<GalleryItem>, String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_status(&status)?;
+        
+        let commission = CommissionRepository::find_by_id(&app_handle, &commission_id, &status).await?
+            .ok_or_else(|| format!("Commission {} not found", commission_id))?;
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        
+        let mut gallery = Vec::new();
+        for image_path in commission.images.iter().filter(|path| !path.starts_with("data:")) {
+            let Some(image_file) = Self::resolve_image_path(&data_dir, &commission.client_name, &commission.status, image_path) else {
+                continue;
+            };
+            
+            let thumbnail_file = Self::thumbnail_path(&image_file);
+            if thumbnail_file.as_ref().is_some_and(|thumb| !thumb.exists()) {
+                if let Err(e) = Self::write_thumbnail(&image_file) {
+                    eprintln!("Failed to create thumbnail for {}: {}", image_file.display(), e);
+                }
+            }
+            
+            gallery.push(GalleryItem {
+                relative_path: image_path.clone(),
+                full_path: image_file.to_string_lossy().to_string(),
+                thumbnail_path: thumbnail_file
+                    .filter(|thumb| thumb.exists())
+                    .map(|thumb| thumb.to_string_lossy().to_string()),
+            });
+        }
+        
+        Ok(gallery)
+    }
+
+    /// Ids of commissions that still keep an image inline as a data URL instead of a file.
+    pub async fn find_inline_image_commissions<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<String>, String> {
+        let mut ids = Vec::new();
+        for status in FOLDER_STATUSES {
+            ids.extend(CommissionRepository::find_by_status(&app_handle, status).await?
+                .into_iter()
+                .filter(|c| c.images.iter().any(|path| path.starts_with("data:image/")))
+                .map(|c| c.id));
+        }
+        ids.sort();
+        
+        Ok(ids)
+    }
+
+    /// Fully decodes every stored image a commission refers to and reports the ones that fail.
+    /// Missing files are the health check's business, and inline data URLs aren't files. Read-only.
+    pub async fn verify_image_integrity<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<CorruptImage>, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut problems = Vec::new();
+        
+        for status in FOLDER_STATUSES {
+            for commission in CommissionRepository::find_by_status(&app_handle, status).await? {
+                for image_path in commission.images.iter().filter(|path| !path.starts_with("data:")) {
+                    let Some(image_file) = Self::resolve_image_path(&data_dir, &commission.client_name, &commission.status, image_path) else {
+                        continue;
+                    };
+                    
+                    let decoded = image::ImageReader::open(&image_file)
+                        .map_err(image::ImageError::IoError)
+                        .and_then(|reader| reader.with_guessed_format().map_err(image::ImageError::IoError))
+                        .and_then(|reader| reader.decode());
+                    
+                    let problem = match decoded {
+                        Ok(_) => continue,
+                        Err(image::ImageError::Unsupported(e)) => ("unsupported", e.to_string()),
+                        Err(e) => ("corrupt", e.to_string()),
+                    };
+                    
+                    problems.push(CorruptImage {
+                        commission_id: commission.id.clone(),
+                        path: image_file.strip_prefix(&data_dir)
+                            .unwrap_or(&image_file)
+                            .to_string_lossy()
+                            .replace('\\', "/"),
+                        problem: problem.0.to_string(),
+                        message: problem.1,
+                    });
+                }
+            }
+        }
+        
+        problems.sort_by(|a, b| a.path.cmp(&b.path));
+        
+        Ok(problems)
+    }
+
+    /// Counts stored images per actual format, read from the magic bytes rather than the file
+    /// extension, most common first. Files in no recognised format count as "unknown". Read-only.
+    pub async fn get_image_format_breakdown<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<(String, usize)>, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for (_, detected) in Self::detected_image_formats(&data_dir)? {
+            *counts.entry(Self::format_name(detected)).or_insert(0) += 1;
+        }
+        
+        let mut breakdown: Vec<(String, usize)> = counts.into_iter().collect();
+        breakdown.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        
+        Ok(breakdown)
+    }
+
+    /// Lists stored images whose extension doesn't match the format of their contents, e.g. a
+    /// PNG saved as .jpg. Read-only.
+    pub async fn find_image_format_mismatches<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<FormatMismatch>, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        
+        let mut mismatches = Vec::new();
+        for (image_file, detected) in Self::detected_image_formats(&data_dir)? {
+            let extension = image_file.extension().unwrap_or_default().to_string_lossy().to_lowercase();
+            if detected.is_some() && image::ImageFormat::from_extension(&extension) == detected {
+                continue;
+            }
+            
+            mismatches.push(FormatMismatch {
+                path: image_file.strip_prefix(&data_dir)
+                    .unwrap_or(&image_file)
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+                extension,
+                detected_format: Self::format_name(detected),
+            });
+        }
+        
+        mismatches.sort_by(|a, b| a.path.cmp(&b.path));
+        
+        Ok(mismatches)
+    }
+
+    // Originals and thumbnails are copies, so only the stored images themselves are inspected
+    fn detected_image_formats(data_dir: &Path) -> Result<Vec<(PathBuf, Option<image::ImageFormat>)>, String> {
+        let mut formats = Vec::new();
+        for folder in COMMISSION_FOLDERS {
+            let folder_dir = data_dir.join(folder);
+            
+            for image_file in FileStorage::list_files_recursive(&folder_dir)? {
+                let is_original = Self::stored_image_parts(&folder_dir, &image_file)
+                    .is_some_and(|(_, image_path)| !Self::is_derived_image(&image_path));
+                if !is_original {
+                    continue;
+                }
+                
+                let content = fs::read(&image_file)
+                    .map_err(|e| format!("Failed to read image {}: {}", image_file.display(), e))?;
+                formats.push((image_file, image::guess_format(&content).ok()));
+            }
+        }
+        Ok(formats)
+    }
+
+    fn format_name(format: Option<image::ImageFormat>) -> String {
+        format
+            .and_then(|format| format.extensions_str().first())
+            .map(|extension| extension.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
+    /// Lists image files (relative to the data directory) that no commission refers to. Read-only.
+    pub async fn find_orphaned_images<R: Runtime>(app_handle: AppHandle<R>) -> Result<Vec<String>, String> {
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        
+        let mut commissions = Vec::new();
+        for status in FOLDER_STATUSES {
+            commissions.extend(CommissionRepository::find_by_status(&app_handle, status).await?);
+        }
+        
+        let orphans = Self::orphaned_image_files(&data_dir, &commissions)?;
+        
+        Ok(orphans.iter()
+            .map(|path| path.strip_prefix(&data_dir).unwrap_or(path).to_string_lossy().replace('\\', "/"))
+            .collect())
+    }
+
+    /// Image files under <folder>/<client>/images that none of the given commissions reference.
+    /// Originals kept under images/originals count as referenced along with their downscaled copy.
+    pub fn orphaned_image_files(data_dir: &Path, commissions: &[Commission]) -> Result<Vec<PathBuf>, String> {
+        // (client folder, path within it); the same image may sit under pendings or history
+        let referenced: HashSet<(String, PathBuf)> = commissions.iter()
+            .flat_map(|commission| {
+                let client_folder = FileStorage::sanitize_filename(&commission.client_name);
+                commission.images.iter()
+                    .filter(|path| !path.is_empty() && !path.starts_with("data:"))
+                    .map(move |path| (client_folder.clone(), PathBuf::from(path)))
+            })
+            .collect();
+        
+        let mut orphans = Vec::new();
+        for folder in COMMISSION_FOLDERS {
+            let folder_dir = data_dir.join(folder);
+            
+            for image_file in FileStorage::list_files_recursive(&folder_dir)? {
+                let Some((client_dir, image_path)) = Self::stored_image_parts(&folder_dir, &image_file) else {
+                    continue;
+                };
+                // history/<year>/<client> is still that client
+                let client_folder = client_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+                
+                // Originals and thumbnails belong to the image they were derived from
+                let image_path: PathBuf = image_path.components()
+                    .filter(|component| !Self::is_derived_folder(component.as_os_str()))
+                    .collect();
+                
+                if !referenced.contains(&(client_folder, image_path)) {
+                    orphans.push(image_file);
+                }
+            }
+        }
+        
+        orphans.sort();
+        Ok(orphans)
+    }
+
+    // Images live in an images/ folder under each client, alongside the commission JSON files
+    pub fn is_stored_image(folder_dir: &Path, path: &Path) -> bool {
+        Self::stored_image_parts(folder_dir, path).is_some()
+    }
+
+    /// Splits a file under <folder>/[<year>/]<client>/images/ into its client folder, relative to
+    /// the folder, and its path within the client folder (starting with images/). Only the
+    /// images/ folder directly inside a client folder counts, whatever the client is called.
+    fn stored_image_parts(folder_dir: &Path, path: &Path) -> Option<(PathBuf, PathBuf)> {
+        let relative = path.strip_prefix(folder_dir).ok()?;
+        let components: Vec<&std::ffi::OsStr> = relative.components().map(|c| c.as_os_str()).collect();
+        
+        let is_year = |name: &std::ffi::OsStr| name.len() == 4 && name.to_string_lossy().chars().all(|c| c.is_ascii_digit());
+        let in_year_folder = folder_dir.file_name().is_some_and(|name| name == "history")
+            && components.len() >= 4
+            && is_year(components[0])
+            && components[2] == "images";
+        let client_depth = if in_year_folder {
+            2
+        } else if components.len() >= 3 && components[1] == "images" {
+            1
+        } else {
+            return None;
+        };
+        
+        Some((
+            components[..client_depth].iter().collect(),
+            components[client_depth..].iter().collect(),
+        ))
+    }
+
+    // Copies derived from a stored image: images/[<id>/]originals/<file> and images/[<id>/]thumbs/<file>
+    fn is_derived_image(image_path: &Path) -> bool {
+        image_path.components().any(|component| Self::is_derived_folder(component.as_os_str()))
+    }
+
+    fn is_derived_folder(name: &std::ffi::OsStr) -> bool {
+        name == "originals" || name == "thumbs"
+    }
+
+    pub async fn export_watermarked_image<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        client_name: String,
+        status: String,
+        relative_path: String,
+        text: String,
+    ) -> Result<String, String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_name(&client_name, "Client name")?;
+        ValidationService::validate_status(&status)?;
+        ValidationService::validate_image_path(&relative_path)?;
+        ValidationService::validate_name(&text, "Watermark text")?;
+        
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let source_file = Self::resolve_image_path(&data_dir, &client_name, &status, &relative_path)
+            .ok_or_else(|| format!("Image {} not found", relative_path))?;
+        
+        let settings = ConfigRepository::load(&app_handle)?.watermark;
+        let source = image::open(&source_file)
+            .map_err(|e| format!("Failed to decode image: {}", e))?;
+        let watermarked = Self::apply_watermark(source.to_rgba8(), &text, &settings)?;
+        
+        // The original is never touched; the copy goes to the exports folder
+        let exports_dir = data_dir.join("exports");
+        fs::create_dir_all(&exports_dir)
+            .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+        
+        let stem = source_file.file_stem().and_then(|s| s.to_str()).unwrap_or("image");
+        let export_file = exports_dir.join(format!("{}_watermarked.png", stem));
+        watermarked.save_with_format(&export_file, image::ImageFormat::Png)
+            .map_err(|e| format!("Failed to save watermarked image: {}", e))?;
+        
+        Ok(export_file.to_string_lossy().to_string())
+    }
+
+    /// Finds a commission image on disk. Images are always saved under pendings, so completed
+    /// commissions fall back to that folder.
+    pub fn resolve_image_path(
+        data_dir: &Path,
+        client_name: &str,
+        status: &str,
+        relative_path: &str,
+    ) -> Option<PathBuf> {
+        let sanitized_client_name = FileStorage::sanitize_filename(client_name);
+        let status_folder = CommissionRepository::folder_for_status(status);
+        
+        [status_folder, "pendings"].iter()
+            .map(|folder| data_dir.join(folder).join(&sanitized_client_name).join(relative_path))
+            .find(|path| path.is_file())
+    }
+
+    // The folders named after a client under each commission folder, history/<year>/ included
+    fn client_folders(data_dir: &Path, client_folder: &str) -> Result<Vec<PathBuf>, String> {
+        let mut client_dirs = Vec::new();
+        for folder in COMMISSION_FOLDERS {
+            client_dirs.extend(CommissionRepository::client_dirs(&data_dir.join(folder))?
+                .into_iter()
+                .filter(|dir| dir.file_name().is_some_and(|name| name == client_folder)));
+        }
+        Ok(client_dirs)
+    }
+
+    /// Moves a commission's stored images (and their originals/thumbnails) to another client
+    /// folder, e.g. after the client was renamed. Inline data URLs and images that aren't on disk
+    /// are left alone.
+    pub fn relocate_commission_images(
+        data_dir: &Path,
+        images: &[String],
+        old_client_name: &str,
+        new_client_name: &str,
+    ) -> Result<(), String> {
+        let old_client_folder = FileStorage::sanitize_filename(old_client_name);
+        let new_client_folder = FileStorage::sanitize_filename(new_client_name);
+        if old_client_folder == new_client_folder {
+            return Ok(());
+        }
+        
+        for image_path in images.iter().filter(|path| !path.is_empty() && !path.starts_with("data:")) {
+            if ValidationService::validate_image_path(image_path).is_err() {
+                continue;
+            }
+            
+            for client_dir in Self::client_folders(data_dir, &old_client_folder)? {
+                let source = client_dir.join(image_path);
+                let target = client_dir.with_file_name(&new_client_folder).join(image_path);
+                Self::move_if_exists(&source, &target)?;
+                
+                for derived in ["originals", "thumbs"] {
+                    if let (Some(source_derived), Some(target_derived)) = (
+                        Self::derived_path(&source, derived),
+                        Self::derived_path(&target, derived),
+                    ) {
+                        Self::move_if_exists(&source_derived, &target_derived)?;
+                    }
+                }
+            }
+        }
+        
+        Ok(())
+    }
+
+    fn apply_watermark(
+        mut canvas: image::RgbaImage,
+        text: &str,
+        settings: &WatermarkSettings,
+    ) -> Result<image::RgbaImage, String> {
+        let font = FontRef::try_from_slice(WATERMARK_FONT)
+            .map_err(|e| format!("Failed to load watermark font: {}", e))?;
+        let scale = PxScale::from(settings.font_size);
+        let (text_width, text_height) = imageproc::drawing::text_size(scale, &font, text);
+        
+        // Render the tiled text as a coverage mask, then blend it in at the configured opacity
+        let (width, height) = canvas.dimensions();
+        let mut mask = image::GrayImage::new(width, height);
+        let step_x = text_width as i32 + settings.font_size as i32 * 2;
+        let step_y = text_height as i32 + settings.font_size as i32 * 2;
+        
+        for (row, y) in (0..height as i32).step_by(step_y.max(1) as usize).enumerate() {
+            // Offset every other row so the tiles form a staggered pattern
+            let offset = if row % 2 == 0 { 0 } else { step_x / 2 };
+            for x in (-offset..width as i32).step_by(step_x.max(1) as usize) {
+                imageproc::drawing::draw_text_mut(&mut mask, image::Luma([255]), x, y, scale, &font, text);
+            }
+        }
+        
+        let opacity = f32::from(settings.opacity_percent) / 100.0;
+        for (pixel, coverage) in canvas.pixels_mut().zip(mask.pixels()) {
+            let alpha = f32::from(coverage[0]) / 255.0 * opacity;
+            for channel in pixel.0.iter_mut().take(3) {
+                *channel = (f32::from(*channel) * (1.0 - alpha) + 255.0 * alpha).round() as u8;
+            }
+        }
+        
+        Ok(canvas)
+    }
+
+    fn apply_large_image_policy(
+        image_data: Vec<u8>,
+        settings: &ImageSettings,
+    ) -> Result<ProcessedImage, String> {
+        let unchanged = |data: Vec<u8>, warnings: Vec<String>| ProcessedImage { data, original: None, warnings };
+        
+        if settings.large_image_policy == "none" {
+            return Ok(unchanged(image_data, Vec::new()));
+        }
+        
+        // Only the header is read here; undecodable images are stored as-is
+        let dimensions = image::ImageReader::new(Cursor::new(&image_data))
+            .with_guessed_format()
+            .ok()
+            .and_then(|reader| reader.into_dimensions().ok());
+        
+        let Some((width, height)) = dimensions else {
+            return Ok(unchanged(image_data, Vec::new()));
+        };
+        
+        if width.max(height) <= settings.max_image_edge {
+            return Ok(unchanged(image_data, Vec::new()));
+        }
+        
+        // Re-encoding a GIF would drop its animation frames, so it only gets a warning
+        let is_gif = image::guess_format(&image_data).ok() == Some(image::ImageFormat::Gif);
+        if settings.large_image_policy == "downscale" && !is_gif {
+            let downscaled = Self::downscale(&image_data, settings.max_image_edge)?;
+            return Ok(ProcessedImage { data: downscaled, original: Some(image_data), warnings: Vec::new() });
+        }
+        
+        let warning = format!(
+            "Image is {}x{}, larger than the recommended {}px edge",
+            width, height, settings.max_image_edge
+        );
+        Ok(unchanged(image_data, vec![warning]))
+    }
+
+    /// Re-encodes a JPEG or PNG upload, and its kept original if any, as lossless WebP. None
+    /// for other formats (GIFs would lose their animation) and when WebP wouldn't be smaller,
+    /// as is common for JPEG photos.
+    fn convert_to_webp(processed: &ProcessedImage) -> Result<Option<ProcessedImage>, String> {
+        let format = image::guess_format(&processed.data).ok();
+        if !matches!(format, Some(image::ImageFormat::Jpeg | image::ImageFormat::Png)) {
+            return Ok(None);
+        }
+        
+        let data = Self::encode_webp(&processed.data)?;
+        if data.len() >= processed.data.len() {
+            return Ok(None);
+        }
+        
+        // The original is stored under the same file name, so it has to match the format
+        let original = processed.original.as_deref().map(Self::encode_webp).transpose()?;
+        
+        Ok(Some(ProcessedImage { data, original, warnings: processed.warnings.clone() }))
+    }
+
+    fn encode_webp(image_data: &[u8]) -> Result<Vec<u8>, String> {
+        let decoded = image::load_from_memory(image_data)
+            .map_err(|e| format!("Failed to decode image: {}", e))?;
+        
+        let mut output = Cursor::new(Vec::new());
+        decoded.write_to(&mut output, image::ImageFormat::WebP)
+            .map_err(|e| format!("Failed to encode WebP: {}", e))?;
+        
+        Ok(output.into_inner())
+    }
+
+    fn downscale(image_data: &[u8], max_edge: u32) -> Result<Vec<u8>, String> {
+        let format = image::guess_format(image_data)
+            .map_err(|e| format!("Failed to detect image format: {}", e))?;
+        let decoded = image::load_from_memory_with_format(image_data, format)
+            .map_err(|e| format!("Failed to decode image: {}", e))?;
+        
+        // resize() keeps the aspect ratio and fits the image inside max_edge x max_edge
+        let resized = decoded.resize(max_edge, max_edge, image::imageops::FilterType::Lanczos3);
+        
+        let mut output = Cursor::new(Vec::new());
+        resized.write_to(&mut output, format)
+            .map_err(|e| format!("Failed to encode image: {}", e))?;
+        
+        Ok(output.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{commission, png, TestEnv};
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn large_image_is_downscaled_to_the_threshold() {
+        let env = TestEnv::new();
+        env.configure(|config| {
+            config.images.large_image_policy = "downscale".to_string();
+            config.images.max_image_edge = 64;
+        });
+        
+        let saved = block_on(ImageService::save_commission_image(
+            env.app().clone(), "c1".to_string(), "Alice".to_string(), png(256, 128), "big.png".to_string(),
+        )).unwrap();
+        
+        let client_dir = env.data_dir().join("pendings").join("Alice");
+        let stored = image::open(client_dir.join(&saved.relative_path)).unwrap();
+        assert_eq!((stored.width(), stored.height()), (64, 32));
+        let original = image::open(client_dir.join("images").join("originals").join("c1_big.png")).unwrap();
+        assert_eq!(original.width(), 256);
+    }
+
+    #[test]
+    fn watermarked_copy_differs_from_the_source() {
+        let env = TestEnv::new();
+        let saved = block_on(ImageService::save_commission_image(
+            env.app().clone(), "c1".to_string(), "Alice".to_string(), png(200, 200), "wip.png".to_string(),
+        )).unwrap();
+        let source_file = env.data_dir().join("pendings").join("Alice").join(&saved.relative_path);
+        let source_bytes = fs::read(&source_file).unwrap();
+        
+        let export = block_on(ImageService::export_watermarked_image(
+            env.app().clone(), "c1".to_string(), "Alice".to_string(), "pending".to_string(),
+            saved.relative_path, "PREVIEW".to_string(),
+        )).unwrap();
+        
+        let source_pixels = image::load_from_memory(&source_bytes).unwrap().to_rgba8();
+        let exported_pixels = image::open(&export).unwrap().to_rgba8();
+        assert_ne!(source_pixels, exported_pixels);
+        assert_eq!(fs::read(&source_file).unwrap(), source_bytes);
+    }
+
+    #[test]
+    fn dedup_estimate_counts_one_reclaimable_copy() {
+        let env = TestEnv::new();
+        let save = |commission_id: &str, data: Vec<u8>| {
+            block_on(ImageService::save_commission_image(
+                env.app().clone(), commission_id.to_string(), "Alice".to_string(), data, "ref.png".to_string(),
+            )).unwrap();
+        };
+        save("c1", png(40, 40));
+        save("c2", png(40, 40));
+        save("c3", png(50, 20));
+        
+        let report = block_on(ImageService::estimate_dedup_savings(env.app().clone())).unwrap();
+        assert_eq!(report.total_files, 3);
+        assert_eq!(report.unique_files, 2);
+        assert_eq!(report.reclaimable_bytes, png(40, 40).len() as u64);
+    }
+
+    /// A PNG of noise, which compresses poorly losslessly and well as JPEG.
+    fn noisy_png(seed: u32) -> Vec<u8> {
+        let mut state = seed;
+        let noise = image::RgbImage::from_fn(160, 160, |_, _| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let [r, g, b, _] = state.to_le_bytes();
+            image::Rgb([r, g, b])
+        });
+        let mut bytes = Vec::new();
+        noise.write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png).unwrap();
+        bytes
+    }
+
+    fn save_image(env: &TestEnv, commission_id: &str, data: Vec<u8>) -> String {
+        block_on(ImageService::save_commission_image(
+            env.app().clone(), commission_id.to_string(), "Alice".to_string(), data, "art.png".to_string(),
+        )).unwrap().relative_path
+    }
+
+    #[test]
+    fn compressing_shrinks_history_images_and_leaves_pending_ones() {
+        let env = TestEnv::new();
+        let images_dir = env.data_dir().join("pendings").join("Alice");
+        let mut done = commission("c1", "alice", "completed");
+        done.client_name = "Alice".to_string();
+        done.images = vec![save_image(&env, "c1", noisy_png(1))];
+        let mut open = commission("c2", "alice", "pending");
+        open.client_name = "Alice".to_string();
+        open.images = vec![save_image(&env, "c2", noisy_png(2))];
+        block_on(CommissionRepository::save(env.app(), &done)).unwrap();
+        block_on(CommissionRepository::save(env.app(), &open)).unwrap();
+        let pending_bytes = fs::read(images_dir.join(&open.images[0])).unwrap();
+        let original_size = fs::metadata(images_dir.join(&done.images[0])).unwrap().len();
+        
+        let report = block_on(ImageService::compress_completed_images(env.app().clone(), 60)).unwrap();
+        assert_eq!(report.files_compressed, 1);
+        
+        let compressed = block_on(CommissionRepository::find_by_id(env.app(), "c1", "completed")).unwrap().unwrap();
+        assert_eq!(compressed.images, vec!["images/c1_art.jpg".to_string()]);
+        assert!(fs::metadata(images_dir.join(&compressed.images[0])).unwrap().len() < original_size);
+        assert!(!images_dir.join(&done.images[0]).exists());
+        assert_eq!(fs::read(images_dir.join(&open.images[0])).unwrap(), pending_bytes);
+    }
+
+    #[test]
+    fn compressing_keeps_files_shared_with_pending_work() {
+        let env = TestEnv::new();
+        let shared = save_image(&env, "c1", noisy_png(3));
+        let mut done = commission("c1", "alice", "completed");
+        done.client_name = "Alice".to_string();
+        done.images = vec![shared.clone()];
+        let mut open = commission("c2", "alice", "pending");
+        open.client_name = "Alice".to_string();
+        open.images = vec![shared.clone()];
+        block_on(CommissionRepository::save(env.app(), &done)).unwrap();
+        block_on(CommissionRepository::save(env.app(), &open)).unwrap();
+        
+        let report = block_on(ImageService::compress_completed_images(env.app().clone(), 60)).unwrap();
+        assert_eq!((report.files_compressed, report.files_skipped), (0, 1));
+        
+        assert!(env.data_dir().join("pendings").join("Alice").join(&shared).exists());
+        let unchanged = block_on(CommissionRepository::find_by_id(env.app(), "c1", "completed")).unwrap().unwrap();
+        assert_eq!(unchanged.images, vec![shared]);
+    }
+
+    #[test]
+    fn orphan_report_lists_only_unreferenced_files() {
+        let env = TestEnv::new();
+        let mut referencing = commission("c1", "alice", "pending");
+        referencing.client_name = "Alice".to_string();
+        referencing.images = vec![save_image(&env, "c1", png(8, 8))];
+        block_on(CommissionRepository::save(env.app(), &referencing)).unwrap();
+        save_image(&env, "c9", png(9, 9));
+        
+        let orphans = block_on(ImageService::find_orphaned_images(env.app().clone())).unwrap();
+        assert_eq!(orphans, vec!["pendings/Alice/images/c9_art.png".to_string()]);
+    }
+
+    #[test]
+    fn backfill_creates_missing_thumbnails() {
+        let env = TestEnv::new();
+        let relative_path = save_image(&env, "c1", png(600, 300));
+        let image_file = env.data_dir().join("pendings").join("Alice").join(&relative_path);
+        let thumb = ImageService::thumbnail_path(&image_file).unwrap();
+        assert!(!thumb.exists());
+        
+        assert_eq!(block_on(ImageService::backfill_thumbnails(env.app().clone())).unwrap(), 1);
+        assert!(image::open(&thumb).unwrap().width() <= THUMBNAIL_EDGE);
+        assert_eq!(block_on(ImageService::backfill_thumbnails(env.app().clone())).unwrap(), 0);
+    }
+
+    #[test]
+    fn per_commission_layout_scopes_same_named_files() {
+        let env = TestEnv::new();
+        env.configure(|config| config.images.image_layout = "per_commission".to_string());
+        let save = |commission_id: &str| {
+            block_on(ImageService::save_commission_image(
+                env.app().clone(), commission_id.to_string(), "Alice".to_string(), png(30, 30), "ref.png".to_string(),
+            ))
+        };
+        
+        let first = save("c1").unwrap().relative_path;
+        let second = save("c2").unwrap().relative_path;
+        assert_eq!(first, "images/c1/ref.png");
+        assert_eq!(second, "images/c2/ref.png");
+        let client_dir = env.data_dir().join("pendings").join("Alice");
+        assert!(client_dir.join(&first).is_file() && client_dir.join(&second).is_file());
+        
+        // Those folder names are taken by originals/ and thumbs/
+        assert!(save("thumbs").is_err());
+        assert!(save("Originals").is_err());
+    }
+
+    #[test]
+    fn only_the_images_folder_under_a_client_counts_as_stored() {
+        let pendings = Path::new("data").join("pendings");
+        assert!(ImageService::is_stored_image(&pendings, &pendings.join("images").join("images").join("c1_a.png")));
+        assert!(!ImageService::is_stored_image(&pendings, &pendings.join("Alice").join("c1.json")));
+        assert!(!ImageService::is_stored_image(&pendings, &pendings.join("Alice").join("exports").join("images").join("a.png")));
+        
+        let history = Path::new("data").join("history");
+        let partitioned = history.join("2026").join("Alice").join("images").join("c1_a.png");
+        assert!(ImageService::is_stored_image(&history, &partitioned));
+        assert!(!ImageService::is_stored_image(&pendings, &pendings.join("2026").join("Alice").join("images").join("a.png")));
+    }
+
+    #[test]
+    fn global_dedup_makes_commissions_share_one_file() {
+        let env = TestEnv::new();
+        let mut first = commission("c1", "alice", "pending");
+        first.client_name = "Alice".to_string();
+        first.images = vec![save_image(&env, "c1", png(40, 40))];
+        let mut second = commission("c2", "alice", "pending");
+        second.client_name = "Alice".to_string();
+        second.images = vec![save_image(&env, "c2", png(40, 40))];
+        block_on(CommissionRepository::save(env.app(), &first)).unwrap();
+        block_on(CommissionRepository::save(env.app(), &second)).unwrap();
+        
+        let result = block_on(ImageService::deduplicate_all_images(env.app().clone())).unwrap();
+        assert_eq!((result.files_removed, result.commissions_updated), (1, 1));
+        
+        let first = block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().unwrap();
+        let second = block_on(CommissionRepository::find_by_id(env.app(), "c2", "pending")).unwrap().unwrap();
+        assert_eq!(first.images, second.images);
+        let client_dir = env.data_dir().join("pendings").join("Alice");
+        assert!(client_dir.join(&first.images[0]).is_file());
+        assert!(!client_dir.join("images").join("c2_art.png").exists());
+    }
+
+    #[test]
+    fn inline_image_commissions_are_found() {
+        let env = TestEnv::new();
+        let mut inline = commission("inline", "alice", "pending");
+        inline.images = vec!["images/inline_a.png".to_string(), "data:image/png;base64,iVBORw0KGgo=".to_string()];
+        let mut on_disk = commission("on_disk", "alice", "completed");
+        on_disk.images = vec!["images/on_disk_a.png".to_string()];
+        block_on(CommissionRepository::save(env.app(), &inline)).unwrap();
+        block_on(CommissionRepository::save(env.app(), &on_disk)).unwrap();
+        
+        let ids = block_on(ImageService::find_inline_image_commissions(env.app().clone())).unwrap();
+        assert_eq!(ids, vec!["inline".to_string()]);
+    }
+
+    #[test]
+    fn webp_conversion_shrinks_and_numbers_colliding_names() {
+        let env = TestEnv::new();
+        env.configure(|config| config.images.convert_to_webp = true);
+        let save = |data: Vec<u8>, filename: &str| {
+            block_on(ImageService::save_commission_image(
+                env.app().clone(), "c1".to_string(), "Alice".to_string(), data, filename.to_string(),
+            )).unwrap().relative_path
+        };
+        let client_dir = env.data_dir().join("pendings").join("Alice");
+        
+        let source = png(200, 200);
+        let stored = save(source.clone(), "art.png");
+        assert_eq!(stored, "images/c1_art.webp");
+        let stored_bytes = fs::read(client_dir.join(&stored)).unwrap();
+        assert_eq!(image::guess_format(&stored_bytes).unwrap(), image::ImageFormat::WebP);
+        assert!(stored_bytes.len() < source.len());
+        
+        let mut jpeg = Vec::new();
+        image::RgbImage::from_pixel(120, 80, image::Rgb([10, 20, 30]))
+            .write_to(&mut Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)
+            .unwrap();
+        assert_eq!(save(jpeg, "art.jpg"), "images/c1_art_1.webp");
+        assert_eq!(fs::read(client_dir.join(&stored)).unwrap(), stored_bytes);
+        
+        // The same picture again reuses the file it already has
+        assert_eq!(save(source, "art.png"), "images/c1_art.webp");
+    }
+
+    #[test]
+    fn gallery_pairs_each_image_with_a_thumbnail() {
+        let env = TestEnv::new();
+        env.configure(|config| config.images.generate_thumbnails = false);
+        let mut work = commission("c1", "alice", "pending");
+        work.client_name = "Alice".to_string();
+        work.images = vec![
+            save_image(&env, "c1", png(400, 300)),
+            block_on(ImageService::save_commission_image(
+                env.app().clone(), "c1".to_string(), "Alice".to_string(), png(300, 400), "sketch.png".to_string(),
+            )).unwrap().relative_path,
+            "images/c1_missing.png".to_string(),
+        ];
+        block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        
+        let gallery = block_on(ImageService::get_commission_gallery(env.app().clone(), "c1".to_string(), "pending".to_string())).unwrap();
+        let paths: Vec<&str> = gallery.iter().map(|item| item.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["images/c1_art.png", "images/c1_sketch.png"]);
+        for item in &gallery {
+            assert!(Path::new(&item.full_path).is_file());
+            let thumbnail = item.thumbnail_path.as_ref().unwrap();
+            assert!(image::open(thumbnail).unwrap().width() <= THUMBNAIL_EDGE);
+        }
+    }
+
+    #[test]
+    fn integrity_check_reports_truncated_and_unsupported_images() {
+        let env = TestEnv::new();
+        let images_dir = env.data_dir().join("pendings").join("Alice");
+        let valid = save_image(&env, "c1", noisy_png(1));
+        let truncated = block_on(ImageService::save_commission_image(
+            env.app().clone(), "c1".to_string(), "Alice".to_string(), noisy_png(2), "sketch.png".to_string(),
+        )).unwrap().relative_path;
+        let bytes = fs::read(images_dir.join(&truncated)).unwrap();
+        fs::write(images_dir.join(&truncated), &bytes[..bytes.len() / 2]).unwrap();
+        fs::write(images_dir.join("images").join("sketch.svg"), "<svg xmlns=\"http://www.w3.org/2000/svg\"/>").unwrap();
+        let mut work = commission("c1", "alice", "pending");
+        work.client_name = "Alice".to_string();
+        work.images = vec![valid, truncated.clone(), "images/sketch.svg".to_string()];
+        block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+        
+        let problems = block_on(ImageService::verify_image_integrity(env.app().clone())).unwrap();
+        let found: Vec<(&str, &str, &str)> = problems.iter()
+            .map(|p| (p.commission_id.as_str(), p.path.as_str(), p.problem.as_str()))
+            .collect();
+        let truncated_path = format!("pendings/Alice/{}", truncated);
+        let mut expected = vec![
+            ("c1", truncated_path.as_str(), "corrupt"),
+            ("c1", "pendings/Alice/images/sketch.svg", "unsupported"),
+        ];
+        expected.sort_by(|a, b| a.1.cmp(b.1));
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn downscaling_everything_shrinks_only_oversized_images() {
+        let env = TestEnv::new();
+        let images_dir = env.data_dir().join("pendings").join("Alice");
+        let large = save_image(&env, "c1", png(600, 300));
+        let small = save_image(&env, "c2", png(100, 100));
+        let small_before = fs::read(images_dir.join(&small)).unwrap();
+        fs::write(images_dir.join("images").join("broken.png"), [0x89, 0x50, 0x4E, 0x47, 0, 0]).unwrap();
+        
+        assert!(block_on(ImageService::downscale_all_images(env.app().clone(), 100)).is_err());
+        let report = block_on(ImageService::downscale_all_images(env.app().clone(), 300)).unwrap();
+        
+        assert_eq!(report.files_downscaled, 1);
+        assert_eq!(report.files_skipped, 0);
+        assert!(report.bytes_saved > 0);
+        assert_eq!(image::image_dimensions(images_dir.join(&large)).unwrap(), (300, 150));
+        assert_eq!(fs::read(images_dir.join(&small)).unwrap(), small_before);
+    }
+
+    #[test]
+    fn orphan_scan_covers_cancelled_work_and_history_years() {
+        let env = TestEnv::new();
+        let cancelled_image = save_image(&env, "c1", noisy_png(1));
+        let mut dropped = commission("c1", "alice", "cancelled");
+        dropped.client_name = "Alice".to_string();
+        dropped.images = vec![cancelled_image];
+        let mut done = commission("c2", "alice", "completed");
+        done.client_name = "Alice".to_string();
+        done.images = vec!["images/c2_art.png".to_string()];
+        for work in [&dropped, &done] {
+            block_on(CommissionRepository::save(env.app(), work)).unwrap();
+        }
+        
+        // history/<year>/<client> is Alice's folder too
+        let year_images = env.data_dir().join("history").join("2025").join("Alice").join("images");
+        fs::create_dir_all(&year_images).unwrap();
+        fs::write(year_images.join("c2_art.png"), png(4, 4)).unwrap();
+        fs::write(year_images.join("stray.png"), png(4, 4)).unwrap();
+        
+        let orphans = block_on(ImageService::find_orphaned_images(env.app().clone())).unwrap();
+        assert_eq!(orphans, vec!["history/2025/Alice/images/stray.png".to_string()]);
+    }
+
+    #[test]
+    fn thumbnails_are_backfilled_inside_history_year_folders() {
+        let env = TestEnv::new();
+        let year_images = env.data_dir().join("history").join("2025").join("Alice").join("images");
+        fs::create_dir_all(&year_images).unwrap();
+        fs::write(year_images.join("c1_art.png"), png(4, 4)).unwrap();
+        
+        assert_eq!(block_on(ImageService::backfill_thumbnails(env.app().clone())).unwrap(), 1);
+        assert!(year_images.join("thumbs").join("c1_art.png").is_file());
+    }
+
+    #[test]
+    fn format_breakdown_reads_magic_bytes_and_flags_mismatched_extensions() {
+        let env = TestEnv::new();
+        let images_dir = env.data_dir().join("pendings").join("Alice").join("images");
+        fs::create_dir_all(images_dir.join("thumbs")).unwrap();
+        let mut jpeg = Vec::new();
+        image::RgbImage::new(4, 4).write_to(&mut Cursor::new(&mut jpeg), image::ImageFormat::Jpeg).unwrap();
+        fs::write(images_dir.join("c1_art.png"), png(4, 4)).unwrap();
+        fs::write(images_dir.join("c2_photo.jpg"), &jpeg).unwrap();
+        fs::write(images_dir.join("c3_renamed.png"), &jpeg).unwrap();
+        // Copies aren't images of their own
+        fs::write(images_dir.join("thumbs").join("c1_art.png"), png(4, 4)).unwrap();
+        
+        let breakdown = block_on(ImageService::get_image_format_breakdown(env.app().clone())).unwrap();
+        assert_eq!(breakdown, vec![("jpg".to_string(), 2), ("png".to_string(), 1)]);
+        
+        let mismatches = block_on(ImageService::find_image_format_mismatches(env.app().clone())).unwrap();
+        let found: Vec<(&str, &str, &str)> = mismatches.iter()
+            .map(|m| (m.path.as_str(), m.extension.as_str(), m.detected_format.as_str()))
+            .collect();
+        assert_eq!(found, vec![("pendings/Alice/images/c3_renamed.png", "png", "jpg")]);
+    }
+
+    #[test]
+    fn relocating_moves_originals_and_thumbnails_with_the_image() {
+        let env = TestEnv::new();
+        env.configure(|config| {
+            config.images.large_image_policy = "downscale".to_string();
+            config.images.max_image_edge = 64;
+        });
+        let images = vec![save_image(&env, "c1", png(256, 128))];
+        block_on(ImageService::backfill_thumbnails(env.app().clone())).unwrap();
+        
+        ImageService::relocate_commission_images(env.data_dir(), &images, "Alice", "Alicia").unwrap();
+        
+        let old_file = env.data_dir().join("pendings").join("Alice").join(&images[0]);
+        let new_file = env.data_dir().join("pendings").join("Alicia").join(&images[0]);
+        assert!(new_file.is_file() && !old_file.exists());
+        for derived in ["originals", "thumbs"] {
+            assert!(ImageService::derived_path(&new_file, derived).unwrap().is_file(), "{} not moved", derived);
+            assert!(!ImageService::derived_path(&old_file, derived).unwrap().exists(), "{} left behind", derived);
+        }
     }
 }
@@ -0,0 +1,454 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+use crate::repository::{ClientRepository, CommissionRepository, FileStorage};
+use crate::repository::commission_repository::COMMISSION_FOLDERS;
+use crate::repository::file_storage::{CURRENT_SCHEMA_VERSION, EXPORT_MARKER_FILE};
+use crate::models::{Client, Commission};
+use super::validation_service::{ValidationService, MAX_DESCRIPTION_LENGTH};
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportSummary {
+    pub clients_imported: usize,
+    pub commissions_imported: usize,
+    pub images_imported: usize,
+    pub skipped: Vec<String>,
+}
+
+// Enough to judge a folder without reading a large export in full
+const MAX_INSPECTED_FILES: usize = 50;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportInspection {
+    pub has_export_marker: bool,
+    pub export_app_version: Option<String>,
+    pub schema_version: Option<u64>, // From the marker, else inferred from the sampled records
+    pub compatible: bool,
+    pub clients: usize,
+    pub commissions: usize,
+    pub has_legacy_prices: bool,
+    pub sampled_files: usize,
+    pub unreadable_files: usize,
+}
+
+pub struct ImportService;
+
+impl ImportService {
+    pub fn validate_import_dir(import_path: &str) -> Result<PathBuf, String> {
+        // Validate import path to prevent path traversal
+        if import_path.is_empty() {
+            return Err("Import path cannot be empty".to_string());
+        }
+        
+        if import_path.contains("..") || import_path.contains("~") {
+            return Err("Invalid import path - path traversal detected".to_string());
+        }
+        
+        // Only allow paths within specific safe directories
+        let import_dir = PathBuf::from(import_path);
+        if !import_dir.is_absolute() {
+            return Err("Import path must be absolute".to_string());
+        }
+        
+        // Verify the path exists and is a directory
+        if !import_dir.exists() {
+            return Err("Import directory does not exist".to_string());
+        }
+        
+        if !import_dir.is_dir() {
+            return Err("Import path must be a directory".to_string());
+        }
+        
+        // Additional security: Check if import directory is within allowed locations
+        let home_dir = std::env::var("HOME").unwrap_or_default();
+        let allowed_prefixes = [
+            "/tmp/",
+            "/var/tmp/",
+            &format!("{}/Downloads/", home_dir),
+            &format!("{}/Documents/", home_dir),
+            &format!("{}/Desktop/", home_dir),
+        ];
+        
+        let import_path_str = import_dir.to_string_lossy();
+        if !allowed_prefixes.iter().any(|prefix| import_path_str.starts_with(prefix)) {
+            return Err("Import path not in allowed location".to_string());
+        }
+        
+        Ok(import_dir)
+    }
+
+    /// Describes what an import folder contains without copying anything.
+    pub async fn inspect_import<R: Runtime>(
+        _app_handle: AppHandle<R>,
+        import_path: String,
+    ) -> Result<ImportInspection, String> {
+        let import_dir = Self::validate_import_dir(&import_path)?;
+        let mut inspection = ImportInspection::default();
+        
+        let marker = fs::read_to_string(import_dir.join(EXPORT_MARKER_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+        inspection.has_export_marker = FileStorage::has_export_marker(&import_dir);
+        if inspection.has_export_marker {
+            let marker = marker.unwrap_or_default();
+            inspection.export_app_version = marker.get("version").and_then(|v| v.as_str()).map(|v| v.to_string());
+            // Markers written before schema versioning imply the first versioned layout
+            inspection.schema_version = Some(marker.get("schema_version").and_then(|v| v.as_u64()).unwrap_or(CURRENT_SCHEMA_VERSION));
+        }
+        
+        let client_files = Self::json_files(&import_dir.join("clients"))?;
+        inspection.clients = client_files.len();
+        for file_path in client_files.iter().take(MAX_INSPECTED_FILES) {
+            inspection.sampled_files += 1;
+            let readable = fs::read_to_string(file_path)
+                .ok()
+                .is_some_and(|json| serde_json::from_str::<Client>(&json).is_ok());
+            if !readable {
+                inspection.unreadable_files += 1;
+            }
+        }
+        
+        let mut commission_files = Vec::new();
+        for folder in COMMISSION_FOLDERS {
+            let folder_dir = import_dir.join(folder);
+            if !folder_dir.is_dir() {
+                continue;
+            }
+            
+            for client_dir in CommissionRepository::client_dirs(&folder_dir)? {
+                commission_files.extend(Self::json_files(&client_dir)?);
+            }
+        }
+        inspection.commissions = commission_files.len();
+        
+        let mut inferred_version = None;
+        for file_path in commission_files.iter().take(MAX_INSPECTED_FILES) {
+            inspection.sampled_files += 1;
+            let record = fs::read_to_string(file_path)
+                .ok()
+                .filter(|json| CommissionRepository::parse_commission(json).is_ok())
+                .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok());
+            
+            let Some(record) = record else {
+                inspection.unreadable_files += 1;
+                continue;
+            };
+            
+            let version = if record.get("price_cents").and_then(|p| p.as_i64()).is_some() {
+                CURRENT_SCHEMA_VERSION
+            } else {
+                inspection.has_legacy_prices = true;
+                1
+            };
+            inferred_version = inferred_version.max(Some(version));
+        }
+        
+        if inspection.schema_version.is_none() {
+            inspection.schema_version = inferred_version;
+        }
+        inspection.compatible = inspection.schema_version.is_some_and(|v| v <= CURRENT_SCHEMA_VERSION)
+            && inspection.unreadable_files == 0;
+        
+        Ok(inspection)
+    }
+
+    /// Imports an app-produced export, relaxing the name/description rules that may have
+    /// tightened since the records were written.
+    ///
+    /// Security boundary: the export marker is a plain file and proves nothing about who made
+    /// the bundle, so it only unlocks content rules. Ids, image paths and every name that
+    /// becomes part of a path are still checked for traversal and dangerous characters, so a
+    /// forged marker can at worst import unusual text.
+    pub async fn import_trusted_data<R: Runtime>(
+        app_handle: AppHandle<R>,
+        import_path: String,
+    ) -> Result<ImportSummary, String> {
+        let import_dir = Self::validate_import_dir(&import_path)?;
+        
+        if !FileStorage::has_export_marker(&import_dir) {
+            return Err("Import folder is not a CommFlow export (marker file missing)".to_string());
+        }
+        
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+        let mut summary = ImportSummary::default();
+        
+        for file_path in Self::json_files(&import_dir.join("clients"))? {
+            let result = fs::read_to_string(&file_path)
+                .map_err(|e| format!("Failed to read file: {}", e))
+                .and_then(|json| serde_json::from_str::<Client>(&json).map_err(|e| format!("Failed to parse client: {}", e)))
+                .and_then(|client| Self::validate_trusted_client(&client).map(|_| client));
+            
+            match result {
+                Ok(client) => {
+                    ClientRepository::save(&app_handle, &client).await?;
+                    summary.clients_imported += 1;
+                }
+                Err(e) => summary.skipped.push(format!("{}: {}", file_path.display(), e)),
+            }
+        }
+        
+        for folder in COMMISSION_FOLDERS {
+            let folder_dir = import_dir.join(folder);
+            if !folder_dir.is_dir() {
+                continue;
+            }
+            
+            // Exports may use either history layout
+            for client_dir in CommissionRepository::client_dirs(&folder_dir)? {
+                for file_path in Self::json_files(&client_dir)? {
+                    let result = fs::read_to_string(&file_path)
+                        .map_err(|e| format!("Failed to read file: {}", e))
+                        .and_then(|json| CommissionRepository::parse_commission(&json))
+                        .and_then(|commission| Self::validate_trusted_commission(&commission).map(|_| commission));
+                    
+                    match result {
+                        Ok(commission) => {
+                            CommissionRepository::save(&app_handle, &commission).await?;
+                            summary.commissions_imported += 1;
+                        }
+                        Err(e) => summary.skipped.push(format!("{}: {}", file_path.display(), e)),
+                    }
+                }
+                
+                let folder_name = FileStorage::sanitize_filename(&client_dir.file_name().unwrap_or_default().to_string_lossy());
+                let target_images_dir = data_dir.join(folder).join(folder_name).join("images");
+                summary.images_imported += Self::copy_images(&client_dir.join("images"), &target_images_dir, &mut summary.skipped)?;
+            }
+        }
+        
+        Ok(summary)
+    }
+
+    /// Imports a dump from before the clients/pendings/history layout: one folder of client
+    /// and commission JSON files side by side. Each file is classified by its fields and saved
+    /// where the current layout puts it; files that fit neither shape are reported as skipped.
+    pub async fn import_legacy_export<R: Runtime>(
+        app_handle: AppHandle<R>,
+        import_path: String,
+    ) -> Result<ImportSummary, String> {
+        let import_dir = Self::validate_import_dir(&import_path)?;
+        let mut summary = ImportSummary::default();
+        
+        let mut files = Self::json_files(&import_dir)?;
+        files.sort();
+        
+        for file_path in files {
+            let json = match fs::read_to_string(&file_path) {
+                Ok(json) => json,
+                Err(e) => {
+                    summary.skipped.push(format!("{}: Failed to read file: {}", file_path.display(), e));
+                    continue;
+                }
+            };
+            
+            let skip_reason = match Self::legacy_record_kind(&json) {
+                Some("client") => {
+                    let client = serde_json::from_str::<Client>(&json)
+                        .map_err(|e| format!("Failed to parse client: {}", e))
+                        .and_then(|client| Self::validate_trusted_client(&client).map(|_| client));
+                    match client {
+                        Ok(client) => {
+                            ClientRepository::save(&app_handle, &client).await?;
+                            summary.clients_imported += 1;
+                            continue;
+                        }
+                        Err(e) => e,
+                    }
+                }
+                Some(_) => {
+                    let commission = CommissionRepository::parse_commission(&json)
+                        .and_then(|commission| Self::validate_trusted_commission(&commission).map(|_| commission));
+                    match commission {
+                        Ok(commission) => {
+                            CommissionRepository::save(&app_handle, &commission).await?;
+                            summary.commissions_imported += 1;
+                            continue;
+                        }
+                        Err(e) => e,
+                    }
+                }
+                None => "Not recognizable as a client or a commission".to_string(),
+            };
+            
+            summary.skipped.push(format!("{}: {}", file_path.display(), skip_reason));
+        }
+        
+        println!(
+            "Imported {} clients and {} commissions from legacy export, skipped {}",
+            summary.clients_imported, summary.commissions_imported, summary.skipped.len()
+        );
+        
+        Ok(summary)
+    }
+
+    // Commissions always carry a status and their client's id; clients have neither
+    fn legacy_record_kind(json: &str) -> Option<&'static str> {
+        let value: serde_json::Value = serde_json::from_str(json).ok()?;
+        let record = value.as_object()?;
+        
+        if record.contains_key("status") && record.contains_key("client_id") {
+            Some("commission")
+        } else if record.contains_key("name") && !record.contains_key("status") && !record.contains_key("client_id") {
+            Some("client")
+        } else {
+            None
+        }
+    }
+
+    fn validate_trusted_client(client: &Client) -> Result<(), String> {
+        ValidationService::validate_id(&client.id)?;
+        ValidationService::validate_trusted_name(&client.name, "Client name")?;
+        ValidationService::validate_trusted_text(&client.email, "Email", MAX_DESCRIPTION_LENGTH)?;
+        ValidationService::validate_trusted_text(&client.contact, "Contact", MAX_DESCRIPTION_LENGTH)?;
+        
+        if client.created_at.is_empty() || client.updated_at.is_empty() {
+            return Err("Timestamps cannot be empty".to_string());
+        }
+        
+        Ok(())
+    }
+
+    fn validate_trusted_commission(commission: &Commission) -> Result<(), String> {
+        ValidationService::validate_commission_id(&commission.id)?;
+        ValidationService::validate_id(&commission.client_id)?;
+        ValidationService::validate_trusted_name(&commission.client_name, "Client name")?;
+        ValidationService::validate_trusted_text(&commission.title, "Commission title", MAX_DESCRIPTION_LENGTH)?;
+        ValidationService::validate_trusted_text(&commission.description, "Description", MAX_DESCRIPTION_LENGTH)?;
+        ValidationService::validate_price_cents(commission.price_cents)?;
+        ValidationService::validate_payment_status(&commission.payment_status)?;
+        ValidationService::validate_status(&commission.status)?;
+        
+        for image_path in commission.images.iter().filter(|path| !path.is_empty()) {
+            ValidationService::validate_image_path(image_path)?;
+        }
+        
+        if commission.created_at.is_empty() || commission.updated_at.is_empty() {
+            return Err("Timestamps cannot be empty".to_string());
+        }
+        
+        Ok(())
+    }
+
+    fn json_files(dir_path: &Path) -> Result<Vec<PathBuf>, String> {
+        if !dir_path.is_dir() {
+            return Ok(Vec::new());
+        }
+        
+        let entries = fs::read_dir(dir_path)
+            .map_err(|e| format!("Failed to read import directory: {}", e))?;
+        
+        let mut files = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?.path();
+            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json") {
+                files.push(path);
+            }
+        }
+        
+        Ok(files)
+    }
+
+    fn copy_images(source_dir: &Path, target_dir: &Path, skipped: &mut Vec<String>) -> Result<usize, String> {
+        if !source_dir.is_dir() {
+            return Ok(0);
+        }
+        
+        let entries = fs::read_dir(source_dir)
+            .map_err(|e| format!("Failed to read import images directory: {}", e))?;
+        
+        let mut copied = 0;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let source_file = entry.path();
+            if !source_file.is_file() {
+                continue;
+            }
+            
+            let filename = entry.file_name().to_string_lossy().to_string();
+            if let Err(e) = ValidationService::validate_filename(&filename) {
+                skipped.push(format!("{}: {}", source_file.display(), e));
+                continue;
+            }
+            
+            fs::create_dir_all(target_dir)
+                .map_err(|e| format!("Failed to create images directory: {}", e))?;
+            fs::copy(&source_file, target_dir.join(FileStorage::sanitize_filename(&filename)))
+                .map_err(|e| format!("Failed to copy image: {}", e))?;
+            copied += 1;
+        }
+        
+        Ok(copied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::ClientService;
+    use crate::test_support::{client, commission, TestEnv};
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn trusted_import_accepts_a_name_normal_validation_rejects() {
+        let env = TestEnv::new();
+        let legacy = client("acme", "Acme: Studio");
+        assert!(block_on(ClientService::create_client(env.app().clone(), legacy.clone())).is_err());
+        
+        let import_dir = env.data_dir().join("import");
+        fs::create_dir_all(import_dir.join("clients")).unwrap();
+        fs::write(import_dir.join(EXPORT_MARKER_FILE), FileStorage::export_marker_json()).unwrap();
+        fs::write(import_dir.join("clients").join("acme.json"), serde_json::to_string(&legacy).unwrap()).unwrap();
+        
+        let summary = block_on(ImportService::import_trusted_data(
+            env.app().clone(), import_dir.to_string_lossy().to_string(),
+        )).unwrap();
+        assert_eq!(summary.clients_imported, 1);
+        let imported = block_on(ClientRepository::find_by_id(env.app(), "acme")).unwrap().unwrap();
+        assert_eq!(imported.name, "Acme: Studio");
+    }
+
+    #[test]
+    fn inspection_flags_legacy_price_files_without_copying() {
+        let env = TestEnv::new();
+        let client_dir = env.data_dir().join("import").join("pendings").join("Alice");
+        fs::create_dir_all(&client_dir).unwrap();
+        let legacy = serde_json::json!({
+            "id": "c1", "client_id": "alice", "client_name": "Alice", "title": "Old", "description": "",
+            "price": 12.5, "payment_status": "Not Paid", "status": "pending",
+            "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:00:00Z", "images": []
+        });
+        fs::write(client_dir.join("c1_2024.json"), legacy.to_string()).unwrap();
+        
+        let inspection = block_on(ImportService::inspect_import(
+            env.app().clone(), env.data_dir().join("import").to_string_lossy().to_string(),
+        )).unwrap();
+        assert!(inspection.has_legacy_prices);
+        assert_eq!(inspection.commissions, 1);
+        assert_eq!(inspection.schema_version, Some(1));
+        assert!(!env.data_dir().join("pendings").join("Alice").exists());
+    }
+
+    #[test]
+    fn legacy_flat_export_is_filed_by_record_kind() {
+        let env = TestEnv::new();
+        let dump = env.data_dir().join("dump");
+        fs::create_dir_all(&dump).unwrap();
+        let mut done = commission("c2", "alice", "completed");
+        done.client_name = "Alice".to_string();
+        let mut open = commission("c1", "alice", "pending");
+        open.client_name = "Alice".to_string();
+        fs::write(dump.join("alice.json"), serde_json::to_string(&client("alice", "Alice")).unwrap()).unwrap();
+        fs::write(dump.join("c1.json"), serde_json::to_string(&open).unwrap()).unwrap();
+        fs::write(dump.join("c2.json"), serde_json::to_string(&done).unwrap()).unwrap();
+        fs::write(dump.join("settings.json"), r#"{"theme": "dark"}"#).unwrap();
+        
+        let summary = block_on(ImportService::import_legacy_export(env.app().clone(), dump.to_string_lossy().to_string())).unwrap();
+        assert_eq!((summary.clients_imported, summary.commissions_imported), (1, 2));
+        assert_eq!(summary.skipped.len(), 1);
+        assert!(summary.skipped[0].contains("settings.json"));
+        
+        assert!(block_on(ClientRepository::exists(env.app(), "alice")).unwrap());
+        assert!(block_on(CommissionRepository::find_by_id(env.app(), "c1", "pending")).unwrap().is_some());
+        assert!(block_on(CommissionRepository::find_by_id(env.app(), "c2", "completed")).unwrap().is_some());
+    }
+}
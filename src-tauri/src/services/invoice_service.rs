@@ -0,0 +1,367 @@
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime};
+use crate::repository::{ClientRepository, CommissionRepository, FileStorage};
+use crate::models::{Client, Commission};
+use super::commission_service::CommissionService;
+use super::validation_service::ValidationService;
+
+// A4 in PDF points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const LINE_HEIGHT: f32 = 18.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatementLine {
+    pub commission_id: String,
+    pub title: String,
+    pub status: String,
+    pub created_at: String,
+    pub completed_at: Option<String>, // updated_at of completed commissions
+    pub currency: String,
+    pub total_cents: i64,
+    pub paid_cents: i64,
+    pub balance_cents: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Statement {
+    pub client_id: String,
+    pub client_name: String,
+    pub period: String, // "2026-02"
+    pub lines: Vec<StatementLine>,
+    pub total_cents: i64,
+    pub paid_cents: i64,
+    pub balance_cents: i64,
+}
+
+pub struct InvoiceService;
+
+impl InvoiceService {
+    /// Renders a one-page invoice into the destination folder and returns the file path.
+    pub async fn export_invoice_pdf<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        status: String,
+        dest: String,
+    ) -> Result<String, String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_status(&status)?;
+
+        let dest_dir = PathBuf::from(&dest);
+        if !dest_dir.is_dir() {
+            return Err("Invoice destination must be an existing folder".to_string());
+        }
+
+        let commission = CommissionRepository::find_by_id(&app_handle, &commission_id, &status).await?
+            .ok_or_else(|| format!("Commission {} not found", commission_id))?;
+
+        // Fall back to what the commission remembers if the client record is gone
+        let client = ClientRepository::find_by_id(&app_handle, &commission.client_id).await?;
+
+        let pdf_bytes = Self::render_invoice(&commission, client.as_ref());
+
+        let invoice_file = dest_dir.join(format!("invoice_{}.pdf", commission.id));
+        fs::write(&invoice_file, pdf_bytes)
+            .map_err(|e| format!("Failed to write invoice: {}", e))?;
+
+        Ok(invoice_file.to_string_lossy().to_string())
+    }
+
+    /// Writes every commission with a balance still owed to a CSV in the destination folder,
+    /// largest balance first, and returns the file path.
+    pub async fn export_unpaid_csv<R: Runtime>(app_handle: AppHandle<R>, dest: String) -> Result<String, String> {
+        let dest_dir = PathBuf::from(&dest);
+        if !dest_dir.is_dir() {
+            return Err("CSV destination must be an existing folder".to_string());
+        }
+
+        // Cancelled work isn't owed, whatever was paid towards it
+        let mut unpaid: Vec<Commission> = Vec::new();
+        for status in ["pending", "completed"] {
+            unpaid.extend(CommissionRepository::find_by_status(&app_handle, status).await?
+                .into_iter()
+                .filter(|c| c.paid_cents < c.price_cents));
+        }
+        unpaid.sort_by(|a, b| (b.price_cents - b.paid_cents).cmp(&(a.price_cents - a.paid_cents))
+            .then_with(|| a.created_at.cmp(&b.created_at)));
+
+        let clients = ClientRepository::find_all(&app_handle).await?;
+        let now = chrono::Utc::now();
+
+        let mut csv = String::from("client_name,contact,title,total,paid,balance,age_days\n");
+        for commission in &unpaid {
+            let client = clients.iter().find(|c| c.id == commission.client_id);
+            let client_name = client.map(|c| c.name.as_str()).unwrap_or(&commission.client_name);
+            let contact = client
+                .map(|c| if c.email.is_empty() { c.contact.as_str() } else { c.email.as_str() })
+                .unwrap_or("");
+            let breakdown = CommissionService::breakdown_for(commission);
+            let age_days = chrono::DateTime::parse_from_rfc3339(&commission.created_at)
+                .map(|created| (now - created.with_timezone(&chrono::Utc)).num_days().max(0).to_string())
+                .unwrap_or_default();
+
+            let row = [
+                FileStorage::csv_field(client_name),
+                FileStorage::csv_field(contact),
+                FileStorage::csv_field(&commission.title),
+                Self::format_amount(breakdown.total_cents),
+                Self::format_amount(commission.paid_cents),
+                Self::format_amount(breakdown.total_cents - commission.paid_cents),
+                age_days,
+            ];
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+
+        let csv_file = dest_dir.join(format!("unpaid_commissions_{}.csv", now.format("%Y-%m-%d")));
+        fs::write(&csv_file, csv)
+            .map_err(|e| format!("Failed to write CSV: {}", e))?;
+
+        println!("Exported {} unpaid commissions to {}", unpaid.len(), csv_file.display());
+
+        Ok(csv_file.to_string_lossy().to_string())
+    }
+
+    /// Collects a client's commissions created or completed in the given month, oldest first.
+    /// Completion is taken from updated_at of completed commissions, as elsewhere.
+    pub async fn get_client_monthly_statement<R: Runtime>(
+        app_handle: AppHandle<R>,
+        client_id: String,
+        year: i32,
+        month: u32,
+    ) -> Result<Statement, String> {
+        ValidationService::validate_id(&client_id)?;
+        ValidationService::validate_statement_month(year, month)?;
+
+        let client = ClientRepository::find_by_id(&app_handle, &client_id).await?
+            .ok_or_else(|| format!("Client {} not found", client_id))?;
+
+        let period = format!("{:04}-{:02}", year, month);
+        let in_period = |timestamp: &str| {
+            chrono::DateTime::parse_from_rfc3339(timestamp)
+                .map(|t| t.with_timezone(&chrono::Utc).format("%Y-%m").to_string() == period)
+                .unwrap_or(false)
+        };
+
+        let mut commissions = CommissionService::get_client_commissions(app_handle, client_id.clone()).await?;
+        commissions.reverse();
+
+        let mut lines = Vec::new();
+        // Cancelled work isn't owed, so it stays off the statement
+        for commission in commissions.into_iter().filter(|c| c.status != "cancelled") {
+            let completed_at = (commission.status == "completed").then(|| commission.updated_at.clone());
+            if !in_period(&commission.created_at) && !completed_at.as_deref().is_some_and(in_period) {
+                continue;
+            }
+
+            lines.push(StatementLine {
+                total_cents: commission.price_cents,
+                paid_cents: commission.paid_cents,
+                balance_cents: commission.price_cents - commission.paid_cents,
+                commission_id: commission.id,
+                title: commission.title,
+                status: commission.status,
+                created_at: commission.created_at,
+                completed_at,
+                currency: commission.currency,
+            });
+        }
+
+        let total_cents = lines.iter().map(|line| line.total_cents).sum();
+        let paid_cents = lines.iter().map(|line| line.paid_cents).sum();
+
+        Ok(Statement {
+            client_id: client.id,
+            client_name: client.name,
+            period,
+            lines,
+            total_cents,
+            paid_cents,
+            balance_cents: total_cents - paid_cents,
+        })
+    }
+
+    fn render_invoice(commission: &Commission, client: Option<&Client>) -> Vec<u8> {
+        let breakdown = CommissionService::breakdown_for(commission);
+        let balance_cents = commission.price_cents - commission.paid_cents;
+        let currency = &commission.currency;
+
+        let client_name = client.map(|c| c.name.as_str()).unwrap_or(&commission.client_name);
+        let mut bill_to = vec![client_name.to_string()];
+        if let Some(client) = client {
+            bill_to.extend([client.email.as_str(), client.contact.as_str()]
+                .into_iter()
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_string()));
+            bill_to.extend(client.contacts.iter().map(|c| format!("{}: {}", c.kind, c.value)));
+        }
+
+        let mut lines: Vec<(f32, String)> = vec![
+            (22.0, "INVOICE".to_string()),
+            // Clients quote the reference number; legacy commissions without one use the id
+            (11.0, match commission.reference_number {
+                Some(reference_number) => format!("Invoice #: {}", reference_number),
+                None => format!("Invoice #: {}", commission.id),
+            }),
+            (11.0, format!("Date: {}", chrono::Utc::now().format("%Y-%m-%d"))),
+        ];
+        if let Some(due_date) = &commission.due_date {
+            lines.push((11.0, format!("Due: {}", due_date.split('T').next().unwrap_or(due_date))));
+        }
+        lines.push((11.0, String::new()));
+        lines.push((13.0, "Bill to".to_string()));
+        lines.extend(bill_to.into_iter().map(|line| (11.0, line)));
+        lines.push((11.0, String::new()));
+        lines.push((13.0, commission.title.clone()));
+        lines.push((11.0, format!("Base price: {}", Self::format_cents(breakdown.base_cents, currency))));
+        lines.push((11.0, format!("Tax: {}", Self::format_cents(breakdown.tax_cents, currency))));
+        lines.push((11.0, format!("Fees: {}", Self::format_cents(breakdown.fee_cents, currency))));
+        lines.push((13.0, format!("Total: {}", Self::format_cents(breakdown.total_cents, currency))));
+        lines.push((11.0, String::new()));
+        lines.push((11.0, format!("Paid: {}", Self::format_cents(commission.paid_cents, currency))));
+        lines.push((13.0, format!("Balance due: {}", Self::format_cents(balance_cents, currency))));
+
+        let catalog_id = Ref::new(1);
+        let page_tree_id = Ref::new(2);
+        let page_id = Ref::new(3);
+        let font_id = Ref::new(4);
+        let content_id = Ref::new(5);
+        let font_name = Name(b"F1");
+
+        let mut pdf = Pdf::new();
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id).kids([page_id]).count(1);
+
+        let mut page = pdf.page(page_id);
+        page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        page.parent(page_tree_id);
+        page.contents(content_id);
+        page.resources().fonts().pair(font_name, font_id);
+        page.finish();
+
+        // Built-in font, so nothing needs embedding
+        pdf.type1_font(font_id)
+            .base_font(Name(b"Helvetica"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+        let mut content = Content::new();
+        let mut y = PAGE_HEIGHT - MARGIN;
+        for (size, text) in &lines {
+            if y < MARGIN {
+                break;
+            }
+            content.begin_text();
+            content.set_font(font_name, *size);
+            content.next_line(MARGIN, y);
+            content.show(Str(&Self::encode_text(text)));
+            content.end_text();
+            y -= LINE_HEIGHT.max(*size * 1.4);
+        }
+        pdf.stream(content_id, &content.finish());
+
+        pdf.finish()
+    }
+
+    // Currency codes rather than symbols: most symbols are outside WinAnsi
+    fn format_cents(cents: i64, currency: &str) -> String {
+        format!("{} {}", Self::format_amount(cents), currency)
+    }
+
+    // Plain decimal so spreadsheets read it as a number
+    fn format_amount(cents: i64) -> String {
+        let sign = if cents < 0 { "-" } else { "" };
+        format!("{}{}.{:02}", sign, cents.abs() / 100, cents.abs() % 100)
+    }
+
+    // WinAnsi matches Latin-1 for printable characters; anything outside it becomes '?'
+    fn encode_text(text: &str) -> Vec<u8> {
+        text.chars()
+            .map(|c| match u32::from(c) {
+                code @ (0x20..=0x7E | 0xA0..=0xFF) => code as u8,
+                _ => b'?',
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{client, commission, TestEnv};
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn invoice_is_a_pdf_in_the_commission_currency() {
+        let env = TestEnv::new();
+        block_on(ClientRepository::save(env.app(), &client("alice", "Alice"))).unwrap();
+        let mut invoiced = commission("c1", "alice", "pending");
+        invoiced.currency = "EUR".to_string();
+        invoiced.reference_number = Some(42);
+        block_on(CommissionRepository::save(env.app(), &invoiced)).unwrap();
+
+        let path = block_on(InvoiceService::export_invoice_pdf(
+            env.app().clone(), "c1".to_string(), "pending".to_string(), env.data_dir().to_string_lossy().to_string(),
+        )).unwrap();
+
+        let pdf = fs::read(path).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("Invoice #: 42"));
+        assert!(text.contains("Total: 10.00 EUR"));
+        assert!(!text.contains('$'));
+    }
+
+    #[test]
+    fn unpaid_csv_excludes_paid_work_and_sorts_by_balance() {
+        let env = TestEnv::new();
+        let mut alice = client("alice", "Alice");
+        alice.email = "alice@example.com".to_string();
+        block_on(ClientRepository::save(env.app(), &alice)).unwrap();
+        for (id, status, paid) in [("paid", "completed", 1000), ("part", "pending", 700), ("owed", "completed", 0)] {
+            let mut c = commission(id, "alice", status);
+            c.paid_cents = paid;
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+
+        let path = block_on(InvoiceService::export_unpaid_csv(
+            env.app().clone(), env.data_dir().to_string_lossy().to_string(),
+        )).unwrap();
+
+        let csv = fs::read_to_string(path).unwrap();
+        let rows: Vec<&str> = csv.lines().skip(1).collect();
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].starts_with("Alice,alice@example.com,Title owed,10.00,0.00,10.00,"));
+        assert!(rows[1].starts_with("Alice,alice@example.com,Title part,10.00,7.00,3.00,"));
+    }
+
+    #[test]
+    fn monthly_statement_totals_the_months_commissions() {
+        let env = TestEnv::new();
+        block_on(ClientRepository::save(env.app(), &client("alice", "Alice"))).unwrap();
+        for (id, client_id, status, created_at, updated_at, price, paid) in [
+            ("started", "alice", "pending", "2026-03-05T10:00:00Z", "2026-03-05T10:00:00Z", 1500, 300),
+            ("finished", "alice", "completed", "2026-01-10T10:00:00Z", "2026-03-20T10:00:00Z", 2000, 2000),
+            ("february", "alice", "pending", "2026-02-10T10:00:00Z", "2026-03-01T10:00:00Z", 900, 0),
+            ("other", "bob", "pending", "2026-03-06T10:00:00Z", "2026-03-06T10:00:00Z", 700, 0),
+        ] {
+            let mut c = commission(id, client_id, status);
+            c.created_at = created_at.to_string();
+            c.updated_at = updated_at.to_string();
+            c.price_cents = price;
+            c.paid_cents = paid;
+            block_on(CommissionRepository::save(env.app(), &c)).unwrap();
+        }
+
+        let statement = block_on(InvoiceService::get_client_monthly_statement(env.app().clone(), "alice".to_string(), 2026, 3)).unwrap();
+        let ids: Vec<&str> = statement.lines.iter().map(|line| line.commission_id.as_str()).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&"started") && ids.contains(&"finished"));
+        assert_eq!(statement.period, "2026-03");
+        assert_eq!((statement.total_cents, statement.paid_cents, statement.balance_cents), (3500, 2300, 1200));
+
+        assert!(block_on(InvoiceService::get_client_monthly_statement(env.app().clone(), "alice".to_string(), 2026, 13)).is_err());
+    }
+}
@@ -1,8 +1,26 @@
+pub mod backup_service;
+pub mod bundle_service;
 pub mod client_service;
 pub mod commission_service;
+pub mod data_service;
+pub mod health_service;
 pub mod image_service;
+pub mod import_service;
+pub mod invoice_service;
+pub mod search_service;
+pub mod settings_service;
+pub mod share_service;
 pub mod validation_service;
 
+pub use backup_service::BackupService;
+pub use bundle_service::BundleService;
 pub use client_service::ClientService;
 pub use commission_service::CommissionService;
+pub use data_service::DataService;
+pub use health_service::HealthService;
 pub use image_service::ImageService;
+pub use import_service::ImportService;
+pub use invoice_service::InvoiceService;
+pub use search_service::SearchService;
+pub use settings_service::SettingsService;
+pub use share_service::ShareService;
@@ -0,0 +1,60 @@
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+use crate::models::{Client, Commission};
+use super::client_service::ClientService;
+use super::commission_service::CommissionService;
+
+// Keeps the response small enough for a search-as-you-type dropdown
+const MAX_RESULTS_PER_CATEGORY: usize = 25;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResults {
+    pub clients: Vec<Client>,
+    pub commissions: Vec<Commission>,
+    pub truncated: bool,
+}
+
+pub struct SearchService;
+
+impl SearchService {
+    pub async fn global_search<R: Runtime>(app_handle: AppHandle<R>, query: String) -> Result<SearchResults, String> {
+        let mut clients = ClientService::search_clients(app_handle.clone(), query.clone()).await?;
+        let mut commissions = CommissionService::search_commissions(app_handle, query).await?;
+        
+        let truncated = clients.len() > MAX_RESULTS_PER_CATEGORY
+            || commissions.len() > MAX_RESULTS_PER_CATEGORY;
+        clients.truncate(MAX_RESULTS_PER_CATEGORY);
+        commissions.truncate(MAX_RESULTS_PER_CATEGORY);
+        
+        Ok(SearchResults {
+            clients,
+            commissions,
+            truncated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{ClientRepository, CommissionRepository};
+    use crate::test_support::{client, commission, TestEnv};
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn global_search_matches_across_clients_and_commissions() {
+        let env = TestEnv::new();
+        block_on(ClientRepository::save(env.app(), &client("aurora", "Aurora Studio"))).unwrap();
+        block_on(ClientRepository::save(env.app(), &client("bob", "Bob"))).unwrap();
+        let mut matching = commission("c1", "bob", "pending");
+        matching.description = "Aurora borealis background".to_string();
+        block_on(CommissionRepository::save(env.app(), &matching)).unwrap();
+        block_on(CommissionRepository::save(env.app(), &commission("c2", "bob", "pending"))).unwrap();
+        
+        let results = block_on(SearchService::global_search(env.app().clone(), "aurora".to_string())).unwrap();
+        let client_ids: Vec<&str> = results.clients.iter().map(|c| c.id.as_str()).collect();
+        let commission_ids: Vec<&str> = results.commissions.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(client_ids, vec!["aurora"]);
+        assert_eq!(commission_ids, vec!["c1"]);
+        assert!(!results.truncated);
+    }
+}
@@ -0,0 +1,205 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use tauri::{AppHandle, Runtime};
+use crate::repository::ConfigRepository;
+use crate::repository::config_repository::{AppConfig, ImageSettings, PricingSettings, StorageSettings, WatermarkSettings};
+use super::validation_service::ValidationService;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigReport {
+    pub exists: bool,
+    pub valid: bool,
+    pub parse_error: Option<String>, // Set when the file isn't JSON or a value has the wrong type
+    pub unknown_keys: Vec<String>, // Dotted paths such as "images.max_edge"; ignored on load
+    pub invalid_settings: Vec<String>,
+}
+
+pub struct SettingsService;
+
+impl SettingsService {
+    pub async fn get_image_settings<R: Runtime>(app_handle: AppHandle<R>) -> Result<ImageSettings, String> {
+        let config = ConfigRepository::load(&app_handle)?;
+        Ok(config.images)
+    }
+
+    pub async fn update_image_settings<R: Runtime>(
+        app_handle: AppHandle<R>,
+        settings: ImageSettings,
+    ) -> Result<(), String> {
+        ValidationService::validate_large_image_policy(&settings.large_image_policy)?;
+        ValidationService::validate_max_image_edge(settings.max_image_edge)?;
+        ValidationService::validate_image_layout(&settings.image_layout)?;
+        
+        let mut config = ConfigRepository::load(&app_handle)?;
+        config.images = settings;
+        
+        ConfigRepository::save(&app_handle, &config)
+    }
+
+    pub async fn get_storage_settings<R: Runtime>(app_handle: AppHandle<R>) -> Result<StorageSettings, String> {
+        let config = ConfigRepository::load(&app_handle)?;
+        Ok(config.storage)
+    }
+
+    pub async fn update_storage_settings<R: Runtime>(
+        app_handle: AppHandle<R>,
+        settings: StorageSettings,
+    ) -> Result<(), String> {
+        let mut config = ConfigRepository::load(&app_handle)?;
+        config.storage = settings;
+        
+        ConfigRepository::save(&app_handle, &config)
+    }
+
+    pub async fn get_watermark_settings<R: Runtime>(app_handle: AppHandle<R>) -> Result<WatermarkSettings, String> {
+        let config = ConfigRepository::load(&app_handle)?;
+        Ok(config.watermark)
+    }
+
+    pub async fn update_watermark_settings<R: Runtime>(
+        app_handle: AppHandle<R>,
+        settings: WatermarkSettings,
+    ) -> Result<(), String> {
+        ValidationService::validate_watermark_settings(settings.opacity_percent, settings.font_size)?;
+        
+        let mut config = ConfigRepository::load(&app_handle)?;
+        config.watermark = settings;
+        
+        ConfigRepository::save(&app_handle, &config)
+    }
+
+    pub async fn get_pricing_settings<R: Runtime>(app_handle: AppHandle<R>) -> Result<PricingSettings, String> {
+        let config = ConfigRepository::load(&app_handle)?;
+        Ok(config.pricing)
+    }
+
+    pub async fn update_pricing_settings<R: Runtime>(
+        app_handle: AppHandle<R>,
+        settings: PricingSettings,
+    ) -> Result<(), String> {
+        ValidationService::validate_price_cents(settings.hourly_rate_cents)?;
+        
+        let mut config = ConfigRepository::load(&app_handle)?;
+        config.pricing = settings;
+        
+        ConfigRepository::save(&app_handle, &config)
+    }
+
+    /// Checks the config file without loading it. A config that fails to parse is replaced by
+    /// defaults on load, so this is how the problem gets surfaced.
+    pub async fn validate_config<R: Runtime>(app_handle: AppHandle<R>) -> Result<ConfigReport, String> {
+        let config_file = ConfigRepository::config_path(&app_handle)?;
+        let mut report = ConfigReport {
+            exists: config_file.exists(),
+            valid: true,
+            parse_error: None,
+            unknown_keys: Vec::new(),
+            invalid_settings: Vec::new(),
+        };
+        if !report.exists {
+            return Ok(report);
+        }
+        
+        let config_json = fs::read_to_string(&config_file)
+            .map_err(|e| format!("Failed to read config file: {}", e))?;
+        let raw: Value = match serde_json::from_str(&config_json) {
+            Ok(raw) => raw,
+            Err(e) => {
+                report.valid = false;
+                report.parse_error = Some(e.to_string());
+                return Ok(report);
+            }
+        };
+        
+        let known = serde_json::to_value(AppConfig::default())
+            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        Self::collect_unknown_keys(&raw, &known, "", &mut report.unknown_keys);
+        
+        match serde_json::from_value::<AppConfig>(raw) {
+            Ok(config) => {
+                let checks = [
+                    ValidationService::validate_large_image_policy(&config.images.large_image_policy),
+                    ValidationService::validate_max_image_edge(config.images.max_image_edge),
+                    ValidationService::validate_image_layout(&config.images.image_layout),
+                    ValidationService::validate_watermark_settings(config.watermark.opacity_percent, config.watermark.font_size),
+                    ValidationService::validate_price_cents(config.pricing.hourly_rate_cents),
+                ];
+                report.invalid_settings = checks.into_iter().filter_map(Result::err).collect();
+            }
+            Err(e) => report.parse_error = Some(e.to_string()),
+        }
+        
+        report.valid = report.parse_error.is_none() && report.invalid_settings.is_empty();
+        Ok(report)
+    }
+
+    /// Moves the current config aside as config.json.broken_<timestamp> and writes the
+    /// default settings. Returns the backup path, or None when there was no config file.
+    pub async fn reset_config_to_defaults<R: Runtime>(app_handle: AppHandle<R>) -> Result<Option<String>, String> {
+        let config_file = ConfigRepository::config_path(&app_handle)?;
+        
+        let backup = if config_file.exists() {
+            let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+            let backup_file = config_file.with_file_name(format!(
+                "{}.broken_{}",
+                config_file.file_name().unwrap_or_default().to_string_lossy(),
+                timestamp
+            ));
+            fs::copy(&config_file, &backup_file)
+                .map_err(|e| format!("Failed to back up config file: {}", e))?;
+            Some(backup_file.to_string_lossy().to_string())
+        } else {
+            None
+        };
+        
+        ConfigRepository::save(&app_handle, &AppConfig::default())?;
+        println!("Config reset to defaults{}", backup.as_ref().map(|b| format!("; previous config kept at {}", b)).unwrap_or_default());
+        
+        Ok(backup)
+    }
+
+    fn collect_unknown_keys(raw: &Value, known: &Value, prefix: &str, unknown: &mut Vec<String>) {
+        let (Some(raw), Some(known)) = (raw.as_object(), known.as_object()) else {
+            return;
+        };
+        for (key, value) in raw {
+            let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+            match known.get(key) {
+                Some(known_value) => Self::collect_unknown_keys(value, known_value, &path, unknown),
+                None => unknown.push(path),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestEnv;
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn malformed_config_loads_as_defaults_until_reset() {
+        let env = TestEnv::new();
+        env.configure(|config| config.pricing.hourly_rate_cents = 2500);
+        let config_file = ConfigRepository::config_path(env.app()).unwrap();
+        let broken = "{ \"pricing\": { \"hourly_rate_cents\": 2500 ";
+        fs::write(&config_file, broken).unwrap();
+        
+        let loaded = ConfigRepository::load(env.app()).unwrap();
+        assert_eq!(loaded.pricing.hourly_rate_cents, AppConfig::default().pricing.hourly_rate_cents);
+        let report = block_on(SettingsService::validate_config(env.app().clone())).unwrap();
+        assert!(report.exists);
+        assert!(!report.valid);
+        assert!(report.parse_error.is_some());
+        
+        let backup = block_on(SettingsService::reset_config_to_defaults(env.app().clone())).unwrap().expect("backup path");
+        let backed_up = fs::read_to_string(&backup).unwrap();
+        fs::remove_file(&backup).unwrap();
+        assert_eq!(backed_up, broken);
+        
+        let report = block_on(SettingsService::validate_config(env.app().clone())).unwrap();
+        assert!(report.valid, "{:?}", report);
+        assert!(report.unknown_keys.is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime};
+use crate::repository::{CommissionRepository, FileStorage};
+use crate::models::Commission;
+use super::image_service::ImageService;
+use super::validation_service::ValidationService;
+
+const PROGRESS_PAGE_STYLE: &str = "body{font-family:sans-serif;max-width:960px;margin:2rem auto;padding:0 1rem;color:#222}\
+.status{font-weight:bold}.images img{max-width:100%;margin:1rem 0;display:block}";
+
+pub struct ShareService;
+
+impl ShareService {
+    /// Writes a single HTML page showing a client how their commission is going: title, status,
+    /// due date and images, which are embedded so the file can be sent on its own. Only those
+    /// fields are written; prices, payments, descriptions and client details never are.
+    pub async fn export_progress_snapshot<R: Runtime>(
+        app_handle: AppHandle<R>,
+        commission_id: String,
+        status: String,
+        dest: String,
+    ) -> Result<String, String> {
+        ValidationService::validate_id(&commission_id)?;
+        ValidationService::validate_status(&status)?;
+
+        let dest_dir = PathBuf::from(&dest);
+        if !dest_dir.is_dir() {
+            return Err("Snapshot destination must be an existing folder".to_string());
+        }
+
+        let commission = CommissionRepository::find_by_id(&app_handle, &commission_id, &status).await?
+            .ok_or_else(|| format!("Commission {} not found", commission_id))?;
+        let data_dir = FileStorage::get_app_data_dir(&app_handle)?;
+
+        let mut images = Vec::new();
+        for image_path in commission.images.iter().filter(|path| !path.is_empty()) {
+            if image_path.starts_with("data:image/") {
+                images.push(image_path.clone());
+                continue;
+            }
+            let Some(image_file) = ImageService::resolve_image_path(&data_dir, &commission.client_name, &commission.status, image_path) else {
+                eprintln!("Image {} of commission {} is missing; leaving it out of the snapshot", image_path, commission.id);
+                continue;
+            };
+            let content = fs::read(&image_file)
+                .map_err(|e| format!("Failed to read image {}: {}", image_path, e))?;
+            match image::guess_format(&content) {
+                Ok(format) => images.push(format!("data:{};base64,{}", format.to_mime_type(), BASE64.encode(&content))),
+                Err(_) => eprintln!("Image {} is in an unknown format; leaving it out of the snapshot", image_path),
+            }
+        }
+
+        let snapshot_file = dest_dir.join(format!("progress_{}.html", commission.id));
+        fs::write(&snapshot_file, Self::render_progress_page(&commission, &images))
+            .map_err(|e| format!("Failed to write progress snapshot: {}", e))?;
+
+        Ok(snapshot_file.to_string_lossy().to_string())
+    }
+
+    fn render_progress_page(commission: &Commission, images: &[String]) -> String {
+        let status_label = match commission.status.as_str() {
+            "pending" => "Not started yet",
+            "in-progress" => "In progress",
+            "completed" => "Completed",
+            "cancelled" => "Cancelled",
+            _ => "Unknown",
+        };
+        let date = |timestamp: &str| {
+            chrono::DateTime::parse_from_rfc3339(timestamp)
+                .map(|t| t.format("%B %-d, %Y").to_string())
+                .ok()
+        };
+
+        let title = Self::escape_html(&commission.title);
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+            title, PROGRESS_PAGE_STYLE, title
+        );
+        html.push_str(&format!("<p class=\"status\">{}</p>\n", status_label));
+        if let Some(due_date) = commission.due_date.as_deref().and_then(date) {
+            html.push_str(&format!("<p>Due {}</p>\n", due_date));
+        }
+        if let Some(updated_at) = date(&commission.updated_at) {
+            html.push_str(&format!("<p>Last updated {}</p>\n", updated_at));
+        }
+
+        html.push_str("<div class=\"images\">\n");
+        for image in images {
+            html.push_str(&format!("<img src=\"{}\" alt=\"\">\n", Self::escape_html(image)));
+        }
+        html.push_str("</div>\n</body>\n</html>\n");
+
+        html
+    }
+
+    fn escape_html(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&#39;")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{commission, png, TestEnv};
+    use tauri::async_runtime::block_on;
+    #[test]
+    fn progress_page_leaves_out_notes_prices_and_client_details() {
+        let env = TestEnv::new();
+        let saved = block_on(ImageService::save_commission_image(
+            env.app().clone(), "c1".to_string(), "Alice Private".to_string(), png(4, 4), "wip.png".to_string(),
+        )).unwrap();
+        let mut work = commission("c1", "alice-secret-id", "in-progress");
+        work.client_name = "Alice Private".to_string();
+        work.title = "Fox <portrait>".to_string();
+        work.description = "Internal note: asked for a discount".to_string();
+        work.price_cents = 98_765;
+        work.paid_cents = 43_210;
+        work.images = vec![saved.relative_path];
+        block_on(CommissionRepository::save(env.app(), &work)).unwrap();
+
+        let path = block_on(ShareService::export_progress_snapshot(
+            env.app().clone(), "c1".to_string(), "in-progress".to_string(), env.data_dir().to_string_lossy().to_string(),
+        )).unwrap();
+        let html = fs::read_to_string(path).unwrap();
+
+        assert!(html.contains("Fox &lt;portrait&gt;"));
+        assert!(html.contains("In progress"));
+        assert!(html.contains("data:image/png;base64,"));
+        for private in ["Internal note", "discount", "98765", "987.65", "43210", "432.10", "Alice Private", "alice-secret-id"] {
+            assert!(!html.contains(private), "{} leaked into the page", private);
+        }
+    }
+}
@@ -1,17 +1,82 @@
 use regex::Regex;
+use serde::Serialize;
+use crate::repository::commission_repository::COMMISSION_STATUSES;
 
 // Security validation constants
 const MAX_ID_LENGTH: usize = 64;
 const MAX_NAME_LENGTH: usize = 255;
-const MAX_DESCRIPTION_LENGTH: usize = 10000;
+pub const MAX_DESCRIPTION_LENGTH: usize = 10000;
 const MAX_EMAIL_LENGTH: usize = 320;
 const MAX_CONTACT_LENGTH: usize = 50;
+const MAX_CONTACT_KIND_LENGTH: usize = 32;
+const MAX_TAG_LENGTH: usize = 32;
+const MAX_COMMUNICATION_NOTE_LENGTH: usize = 1000;
+// E.164 allows at most 15 digits; anything under 7 can't be dialled
+const MIN_PHONE_DIGITS: usize = 7;
+const MAX_PHONE_DIGITS: usize = 15;
+pub const PHONE_CONTACT_KINDS: [&str; 4] = ["phone", "sms", "whatsapp", "signal"];
+const HANDLE_CONTACT_KINDS: [&str; 7] = ["discord", "twitter", "x", "instagram", "telegram", "bluesky", "tiktok"];
 const MAX_FILENAME_LENGTH: usize = 255;
+const MIN_IMAGE_EDGE: u32 = 256;
+const MAX_IMAGE_EDGE: u32 = 16384;
+// Hard ceiling checked before any scanning, whatever the field's own limit
+const MAX_RAW_INPUT_LENGTH: usize = 1024 * 1024;
+const MAX_PRICE_CENTS: i64 = 99_999_999_999; // $9,999,999.99
+// A year of full-time work; anything above is a typo
+const MAX_ESTIMATED_HOURS: f64 = 2000.0;
+// The per-commission image layout puts these next to images/<id>/ folders
+const RESERVED_COMMISSION_IDS: [&str; 2] = ["originals", "thumbs"];
+pub const ALLOWED_IMAGE_EXTENSIONS: [&str; 6] = ["jpg", "jpeg", "png", "gif", "bmp", "webp"];
+
+/// The limits enforced here, for the frontend to mirror in its own checks.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationLimits {
+    pub max_id_length: usize,
+    pub max_name_length: usize,
+    pub max_description_length: usize,
+    pub max_email_length: usize,
+    pub max_contact_length: usize,
+    pub max_filename_length: usize,
+    pub max_price_cents: i64,
+    pub min_image_edge: u32,
+    pub max_image_edge: u32,
+}
 
 pub struct ValidationService;
 
 impl ValidationService {
+    pub fn limits() -> ValidationLimits {
+        ValidationLimits {
+            max_id_length: MAX_ID_LENGTH,
+            max_name_length: MAX_NAME_LENGTH,
+            max_description_length: MAX_DESCRIPTION_LENGTH,
+            max_email_length: MAX_EMAIL_LENGTH,
+            max_contact_length: MAX_CONTACT_LENGTH,
+            max_filename_length: MAX_FILENAME_LENGTH,
+            max_price_cents: MAX_PRICE_CENTS,
+            min_image_edge: MIN_IMAGE_EDGE,
+            max_image_edge: MAX_IMAGE_EDGE,
+        }
+    }
+
+    /// Rejects oversized input, NUL bytes and other control characters. NUL in particular can
+    /// silently truncate a path at the OS layer.
+    pub fn reject_control_and_nul(value: &str, field_name: &str) -> Result<(), String> {
+        if value.len() > MAX_RAW_INPUT_LENGTH {
+            return Err(format!("{} too long", field_name));
+        }
+        if value.contains('\0') {
+            return Err(format!("{} contains a NUL byte", field_name));
+        }
+        if value.chars().any(char::is_control) {
+            return Err(format!("{} contains control characters", field_name));
+        }
+        
+        Ok(())
+    }
+
     pub fn validate_id(id: &str) -> Result<(), String> {
+        Self::reject_control_and_nul(id, "ID")?;
         if id.is_empty() {
             return Err("ID cannot be empty".to_string());
         }
@@ -29,6 +94,7 @@ impl ValidationService {
     }
 
     pub fn validate_name(name: &str, field_name: &str) -> Result<(), String> {
+        Self::reject_control_and_nul(name, field_name)?;
         if name.is_empty() {
             return Err(format!("{} cannot be empty", field_name));
         }
@@ -47,6 +113,43 @@ impl ValidationService {
         Ok(())
     }
 
+    /// A commission id must also not collide with the folders derived images are kept in.
+    pub fn validate_commission_id(id: &str) -> Result<(), String> {
+        Self::validate_id(id)?;
+        if RESERVED_COMMISSION_IDS.iter().any(|reserved| reserved.eq_ignore_ascii_case(id)) {
+            return Err(format!("\"{}\" is reserved and cannot be used as a commission ID", id));
+        }
+        
+        Ok(())
+    }
+
+    /// Relaxed name check for trusted imports: allows characters that older versions accepted,
+    /// but still blocks anything that could escape a folder when the name becomes a path.
+    pub fn validate_trusted_name(name: &str, field_name: &str) -> Result<(), String> {
+        if name.is_empty() {
+            return Err(format!("{} cannot be empty", field_name));
+        }
+        Self::validate_trusted_text(name, field_name, MAX_NAME_LENGTH)?;
+        
+        if name.contains("..") || name.contains('/') || name.contains('\\') {
+            return Err(format!("{} contains path characters", field_name));
+        }
+        
+        Ok(())
+    }
+
+    /// Relaxed free-text check for trusted imports: only length and control characters are enforced.
+    pub fn validate_trusted_text(value: &str, field_name: &str, max_length: usize) -> Result<(), String> {
+        if value.len() > max_length {
+            return Err(format!("{} too long (max {} chars)", field_name, max_length));
+        }
+        if value.chars().any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t')) {
+            return Err(format!("{} contains control characters", field_name));
+        }
+        
+        Ok(())
+    }
+
     pub fn validate_email(email: &str) -> Result<(), String> {
         if email.is_empty() {
             return Ok(()); // Email is optional
@@ -86,6 +189,89 @@ impl ValidationService {
         Ok(())
     }
 
+    pub fn validate_contact_kind(kind: &str) -> Result<(), String> {
+        if kind.is_empty() {
+            return Err("Contact kind cannot be empty".to_string());
+        }
+        if kind.len() > MAX_CONTACT_KIND_LENGTH {
+            return Err(format!("Contact kind too long (max {} chars)", MAX_CONTACT_KIND_LENGTH));
+        }
+        
+        let re = Regex::new(r"^[a-z0-9_-]+$").unwrap();
+        if !re.is_match(kind) {
+            return Err("Contact kind contains invalid characters (only lowercase letters, digits, - and _ allowed)".to_string());
+        }
+        
+        Ok(())
+    }
+
+    pub fn validate_communication_note(note: &str) -> Result<(), String> {
+        if note.trim().is_empty() {
+            return Err("Note cannot be empty".to_string());
+        }
+        if note.len() > MAX_COMMUNICATION_NOTE_LENGTH {
+            return Err(format!("Note too long (max {} chars)", MAX_COMMUNICATION_NOTE_LENGTH));
+        }
+        if note.contains('\0') {
+            return Err("Note contains a NUL byte".to_string());
+        }
+        
+        // Same screening as descriptions
+        if note.contains("<script") || note.contains("javascript:") ||
+           note.contains("onload=") || note.contains("onerror=") {
+            return Err("Note contains potentially dangerous content".to_string());
+        }
+        
+        Ok(())
+    }
+
+    pub fn validate_tag(tag: &str) -> Result<(), String> {
+        if tag.is_empty() {
+            return Err("Tag cannot be empty".to_string());
+        }
+        if tag.len() > MAX_TAG_LENGTH {
+            return Err(format!("Tag too long (max {} chars)", MAX_TAG_LENGTH));
+        }
+        
+        let re = Regex::new(r"^[a-z0-9_-]+$").unwrap();
+        if !re.is_match(tag) {
+            return Err("Tag contains invalid characters (only lowercase letters, digits, - and _ allowed)".to_string());
+        }
+        
+        Ok(())
+    }
+
+    /// Brings a structured contact value into a consistent shape for its kind: phone numbers
+    /// keep only digits (and a leading +), handles lose a leading @. Other kinds are trimmed.
+    pub fn normalize_contact(kind: &str, value: &str) -> Result<String, String> {
+        let value = value.trim();
+        
+        if PHONE_CONTACT_KINDS.contains(&kind) {
+            let (prefix, number) = match value.strip_prefix('+') {
+                Some(rest) => ("+", rest),
+                None => ("", value),
+            };
+            let digits: String = number.chars()
+                .filter(|c| !matches!(c, ' ' | '-' | '.' | '(' | ')'))
+                .collect();
+            
+            if !digits.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!("Phone number '{}' contains characters other than digits", value));
+            }
+            if !(MIN_PHONE_DIGITS..=MAX_PHONE_DIGITS).contains(&digits.len()) {
+                return Err(format!("Phone number '{}' must have between {} and {} digits", value, MIN_PHONE_DIGITS, MAX_PHONE_DIGITS));
+            }
+            
+            return Ok(format!("{}{}", prefix, digits));
+        }
+        
+        if HANDLE_CONTACT_KINDS.contains(&kind) {
+            return Ok(value.strip_prefix('@').unwrap_or(value).to_string());
+        }
+        
+        Ok(value.to_string())
+    }
+
     pub fn validate_description(description: &str) -> Result<(), String> {
         if description.len() > MAX_DESCRIPTION_LENGTH {
             return Err(format!("Description too long (max {} chars)", MAX_DESCRIPTION_LENGTH));
@@ -101,6 +287,7 @@ impl ValidationService {
     }
 
     pub fn validate_filename(filename: &str) -> Result<(), String> {
+        Self::reject_control_and_nul(filename, "Filename")?;
         if filename.is_empty() {
             return Err("Filename cannot be empty".to_string());
         }
@@ -117,9 +304,8 @@ impl ValidationService {
         }
         
         // Only allow specific file extensions for images
-        let allowed_extensions = ["jpg", "jpeg", "png", "gif", "bmp", "webp"];
         if let Some(extension) = filename.rsplit('.').next() {
-            if !allowed_extensions.contains(&extension.to_lowercase().as_str()) {
+            if !ALLOWED_IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str()) {
                 return Err("Invalid file extension".to_string());
             }
         } else {
@@ -130,9 +316,10 @@ impl ValidationService {
     }
 
     pub fn validate_status(status: &str) -> Result<(), String> {
-        match status {
-            "pending" | "in-progress" | "completed" => Ok(()),
-            _ => Err("Invalid status value".to_string()),
+        if COMMISSION_STATUSES.contains(&status) {
+            Ok(())
+        } else {
+            Err("Invalid status value".to_string())
         }
     }
 
@@ -143,17 +330,164 @@ impl ValidationService {
         }
     }
 
+    pub fn validate_priority(priority: &str) -> Result<(), String> {
+        match priority {
+            "Low" | "Normal" | "High" => Ok(()),
+            _ => Err("Priority must be Low, Normal or High".to_string()),
+        }
+    }
+
     pub fn validate_price_cents(price_cents: i64) -> Result<(), String> {
         if price_cents < 0 {
             return Err("Price cannot be negative".to_string());
         }
-        if price_cents > 999_999_999_99 { // Max $9,999,999.99
+        if price_cents > MAX_PRICE_CENTS {
             return Err("Price too large".to_string());
         }
         
         Ok(())
     }
 
+    pub fn validate_estimated_hours(hours: f64) -> Result<(), String> {
+        if !hours.is_finite() || hours < 0.0 {
+            return Err("Estimated hours must be a positive number".to_string());
+        }
+        if hours > MAX_ESTIMATED_HOURS {
+            return Err(format!("Estimated hours too large (max {})", MAX_ESTIMATED_HOURS));
+        }
+        
+        Ok(())
+    }
+
+    pub fn validate_paid_cents(paid_cents: i64, price_cents: i64) -> Result<(), String> {
+        if paid_cents < 0 {
+            return Err("Paid amount cannot be negative".to_string());
+        }
+        if paid_cents > price_cents {
+            return Err("Paid amount cannot exceed the price".to_string());
+        }
+        
+        Ok(())
+    }
+
+    pub fn validate_deposit_cents(deposit_cents: i64, price_cents: i64) -> Result<(), String> {
+        if deposit_cents < 0 {
+            return Err("Deposit cannot be negative".to_string());
+        }
+        if deposit_cents > price_cents {
+            return Err("Deposit cannot exceed the price".to_string());
+        }
+        
+        Ok(())
+    }
+
+    pub fn validate_refund_policy(kind: &str, kept_percent: u8) -> Result<(), String> {
+        match kind {
+            "full_refund" | "keep_deposit" => Ok(()),
+            "percentage_kept" if kept_percent <= 100 => Ok(()),
+            "percentage_kept" => Err("Kept percentage must be between 0 and 100".to_string()),
+            _ => Err("Refund policy must be full_refund, keep_deposit or percentage_kept".to_string()),
+        }
+    }
+
+    pub fn validate_tax_and_fee(tax_cents: i64, fee_cents: i64, price_cents: i64) -> Result<(), String> {
+        if tax_cents < 0 {
+            return Err("Tax cannot be negative".to_string());
+        }
+        if fee_cents < 0 {
+            return Err("Fees cannot be negative".to_string());
+        }
+        if tax_cents.saturating_add(fee_cents) > price_cents {
+            return Err("Tax and fees cannot exceed the price".to_string());
+        }
+        
+        Ok(())
+    }
+
+    pub fn validate_jpeg_quality(quality: u8) -> Result<(), String> {
+        if quality == 0 || quality > 100 {
+            return Err("Quality must be between 1 and 100".to_string());
+        }
+        
+        Ok(())
+    }
+
+    pub fn validate_currency(currency: &str) -> Result<(), String> {
+        let re = Regex::new(r"^[A-Z]{3}$").unwrap();
+        if !re.is_match(currency) {
+            return Err("Currency must be a three-letter code like USD".to_string());
+        }
+        
+        Ok(())
+    }
+
+    pub fn validate_due_date(due_date: &str) -> Result<(), String> {
+        if chrono::DateTime::parse_from_rfc3339(due_date).is_err() {
+            return Err("Due date must be an RFC3339 timestamp".to_string());
+        }
+        
+        Ok(())
+    }
+
+    pub fn validate_cadence_granularity(granularity: &str) -> Result<(), String> {
+        match granularity {
+            "week" | "month" => Ok(()),
+            _ => Err("Granularity must be \"week\" or \"month\"".to_string()),
+        }
+    }
+
+    pub fn validate_statement_month(year: i32, month: u32) -> Result<(), String> {
+        if !(1..=12).contains(&month) {
+            return Err("Month must be between 1 and 12".to_string());
+        }
+        
+        if !(2000..=9999).contains(&year) {
+            return Err("Year must be between 2000 and 9999".to_string());
+        }
+        
+        Ok(())
+    }
+
+    pub fn validate_restore_mode(mode: &str) -> Result<(), String> {
+        match mode {
+            "overwrite" | "newer_wins" | "skip" => Ok(()),
+            _ => Err("Restore mode must be \"overwrite\", \"newer_wins\" or \"skip\"".to_string()),
+        }
+    }
+
+    pub fn validate_large_image_policy(policy: &str) -> Result<(), String> {
+        match policy {
+            "none" | "warn" | "downscale" => Ok(()),
+            _ => Err("Invalid large image policy value".to_string()),
+        }
+    }
+
+    pub fn validate_image_layout(layout: &str) -> Result<(), String> {
+        match layout {
+            "prefixed" | "per_commission" => Ok(()),
+            _ => Err("Invalid image layout value".to_string()),
+        }
+    }
+
+    pub fn validate_max_image_edge(max_edge: u32) -> Result<(), String> {
+        if !(MIN_IMAGE_EDGE..=MAX_IMAGE_EDGE).contains(&max_edge) {
+            return Err(format!("Image edge limit must be between {} and {} pixels", MIN_IMAGE_EDGE, MAX_IMAGE_EDGE));
+        }
+        
+        Ok(())
+    }
+
+    pub fn validate_watermark_settings(opacity_percent: u8, font_size: f32) -> Result<(), String> {
+        if opacity_percent == 0 || opacity_percent > 100 {
+            return Err("Watermark opacity must be between 1 and 100 percent".to_string());
+        }
+        if !(8.0..=512.0).contains(&font_size) {
+            return Err("Watermark font size must be between 8 and 512".to_string());
+        }
+        
+        Ok(())
+    }
+
     pub fn validate_image_path(image_path: &str) -> Result<(), String> {
         println!("Validating image path: '{}'", image_path);
         
@@ -163,6 +497,8 @@ impl ValidationService {
             return Ok(());
         }
         
+        Self::reject_control_and_nul(image_path, "Image path")?;
+        
         // Prevent path traversal attacks for file paths
         if image_path.contains("..") {
             println!("Path traversal detected in: '{}'", image_path);
@@ -170,11 +506,9 @@ impl ValidationService {
         }
         
         // Allow simple filenames (no path separators) or paths within images directory
-        if image_path.contains("/") {
-            if !image_path.starts_with("images/") {
-                println!("Invalid path format (contains / but doesn't start with images/): '{}'", image_path);
-                return Err("Invalid image path detected".to_string());
-            }
+        if image_path.contains("/") && !image_path.starts_with("images/") {
+            println!("Invalid path format (contains / but doesn't start with images/): '{}'", image_path);
+            return Err("Invalid image path detected".to_string());
         }
         
         // Reject dangerous characters in any path
@@ -188,3 +522,31 @@ impl ValidationService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_with_nul_bytes_are_rejected() {
+        let err = ValidationService::validate_name("Alice\0.json", "Client name").unwrap_err();
+        assert!(err.contains("NUL"), "{}", err);
+        assert!(ValidationService::validate_filename("ref\0.png").is_err());
+    }
+
+    #[test]
+    fn oversized_input_is_rejected_before_pattern_checks() {
+        let huge = "a".repeat(MAX_RAW_INPUT_LENGTH + 1);
+        assert_eq!(ValidationService::validate_id(&huge).unwrap_err(), "ID too long");
+        assert!(ValidationService::validate_name(&huge, "Client name").is_err());
+    }
+
+    #[test]
+    fn contacts_are_normalized_by_kind() {
+        assert_eq!(ValidationService::normalize_contact("phone", "(555) 123-4567").unwrap(), "5551234567");
+        assert_eq!(ValidationService::normalize_contact("whatsapp", "+44 20 7946 0958").unwrap(), "+442079460958");
+        assert!(ValidationService::normalize_contact("phone", "555-CALL-NOW").is_err());
+        assert!(ValidationService::normalize_contact("phone", "12-34").is_err());
+        assert_eq!(ValidationService::normalize_contact("discord", " @artist ").unwrap(), "artist");
+    }
+}
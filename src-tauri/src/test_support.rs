@@ -0,0 +1,99 @@
+// Shared setup for tests that exercise services against a scratch data directory
+use crate::repository::config_repository::{AppConfig, ConfigRepository};
+use crate::repository::file_storage::DATA_DIR_ENV_VAR;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use tauri::test::{mock_app, MockRuntime};
+use tauri::AppHandle;
+
+// The data directory comes from a process-wide env var, so tests using it run one at a time
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+pub struct TestEnv {
+    app: AppHandle<MockRuntime>,
+    data_dir: PathBuf,
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl TestEnv {
+    pub fn new() -> Self {
+        let guard = ENV_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let data_dir = std::env::temp_dir().join(format!("commflow_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        std::fs::create_dir_all(&data_dir).expect("create test data dir");
+        std::env::set_var(DATA_DIR_ENV_VAR, &data_dir);
+
+        let app = mock_app().handle().clone();
+        // The config file sits next to the test binary; start every test from defaults
+        let _ = std::fs::remove_file(ConfigRepository::config_path(&app).expect("config path"));
+
+        TestEnv { app, data_dir, _guard: guard }
+    }
+
+    pub fn app(&self) -> &AppHandle<MockRuntime> {
+        &self.app
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Changes the saved config, starting from whatever the test already set.
+    pub fn configure(&self, change: impl FnOnce(&mut AppConfig)) {
+        let mut config = ConfigRepository::load(&self.app).expect("load config");
+        change(&mut config);
+        ConfigRepository::save(&self.app, &config).expect("save config");
+    }
+}
+
+impl Drop for TestEnv {
+    fn drop(&mut self) {
+        if let Ok(path) = ConfigRepository::config_path(&self.app) {
+            let _ = std::fs::remove_file(path);
+        }
+        let _ = std::fs::remove_dir_all(&self.data_dir);
+        std::env::remove_var(DATA_DIR_ENV_VAR);
+    }
+}
+
+/// Builds a minimal valid client record for tests to adjust.
+pub fn client(id: &str, name: &str) -> crate::models::Client {
+    serde_json::from_value(json!({
+        "id": id,
+        "name": name,
+        "email": "",
+        "contact": "",
+        "profile_image": null,
+        "created_at": "2026-01-01T00:00:00Z",
+        "updated_at": "2026-01-01T00:00:00Z"
+    }))
+    .expect("valid test client")
+}
+/// Builds a minimal valid commission record for tests to adjust.
+pub fn commission(id: &str, client_id: &str, status: &str) -> crate::models::Commission {
+    serde_json::from_value(json!({
+        "id": id,
+        "client_id": client_id,
+        "client_name": format!("Name {}", client_id),
+        "title": format!("Title {}", id),
+        "description": "",
+        "price_cents": 1000,
+        "payment_status": "Not Paid",
+        "status": status,
+        "created_at": "2026-01-01T00:00:00Z",
+        "updated_at": "2026-01-01T00:00:00Z",
+        "images": []
+    }))
+    .expect("valid test commission")
+}
+
+
+/// Encodes a solid-colour PNG of the given size.
+pub fn png(width: u32, height: u32) -> Vec<u8> {
+    let image = image::RgbImage::from_pixel(width, height, image::Rgb([200, 80, 40]));
+    let mut bytes = Vec::new();
+    image.write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png).expect("encode test png");
+    bytes
+}
@@ -35,6 +35,11 @@ export interface Commission {
   images: string[]; // File paths relative to data directory for portability
 }
 
+export interface SavedImage {
+  relative_path: string;
+  warnings: string[]; // Non-fatal notices such as oversized images
+}
+
 /**
  * Tauri-based storage layer abstraction.
  * All file operations are handled by Rust backend for security and performance.
@@ -124,7 +129,7 @@ export class DataStorage {
     clientName: string,
     imageData: Uint8Array,
     filename: string
-  ): Promise<string> {
+  ): Promise<SavedImage> {
     return invoke('save_commission_image', {
       commissionId: commissionId,
       clientName: clientName,